@@ -1,9 +1,17 @@
+use std::path::Path;
 use std::rc::Rc;
 
 use crate::{
     cache::{self, MetaDataCache},
-    cli::{EntryFormat, OutputOptions, TimelineFormat},
-    ntds::{self, Computer, DataTable, Group, LinkTable, ObjectType, Person, Schema, SdTable},
+    cli::{
+        AdcsObjects, EntryFormat, ExportOptions, OutputFormat, OutputOptions, TimelineFormat,
+        TopologyObjects,
+    },
+    crypto::{BootKey, PekList},
+    ntds::{
+        self, Computer, DataTable, Group, KnownPasswords, LinkTable, ObjectType, Person, Schema,
+        SdTable,
+    },
     object_tree::ObjectTree,
     EntryId, EsedbInfo, SerializationType,
 };
@@ -65,6 +73,126 @@ impl<'info, 'db> CDatabase<'info, 'db> {
         })
     }
 
+    /// decrypts the password encryption keys (PEK) using the boot key of the
+    /// domain controller. After this, the PEK list is available to all
+    /// functions which need to decrypt secret attributes.
+    pub fn set_boot_key(&mut self, boot_key: &BootKey) -> anyhow::Result<()> {
+        let pek_list = self.data_table.decrypt_pek_list(boot_key)?;
+        self.data_table.set_pek_list(Some(pek_list));
+        Ok(())
+    }
+
+    /// returns the decrypted password encryption keys, if a boot key has been set
+    pub fn pek_list(&self) -> Option<&PekList> {
+        self.data_table.pek_list().as_ref()
+    }
+
+    pub fn show_pek_list(&self) -> anyhow::Result<()> {
+        self.data_table.show_pek_list()
+    }
+
+    pub fn show_hashes(&self, format: Option<OutputFormat>) -> anyhow::Result<()> {
+        self.data_table.show_hashes(format)
+    }
+
+    pub fn export_hashes(&self, options: &ExportOptions, output_dir: &Path) -> anyhow::Result<()> {
+        self.data_table.export_hashes(options, output_dir)
+    }
+
+    pub fn show_password_audit<T: SerializationType>(
+        &self,
+        format: OutputFormat,
+        known_passwords: &KnownPasswords,
+    ) -> anyhow::Result<()> {
+        self.data_table
+            .show_password_audit::<T>(format, known_passwords)
+    }
+
+    pub fn show_supplemental_credentials(
+        &self,
+        format: Option<OutputFormat>,
+        cleartext_only: bool,
+    ) -> anyhow::Result<()> {
+        self.data_table
+            .show_supplemental_credentials(format, cleartext_only)
+    }
+
+    pub fn show_gmsa_passwords(&self, format: Option<OutputFormat>) -> anyhow::Result<()> {
+        self.data_table.show_gmsa_passwords(format)
+    }
+
+    pub fn show_bitlocker_recovery_information(&self, format: OutputFormat) -> anyhow::Result<()> {
+        self.data_table.show_bitlocker_recovery_information(format)
+    }
+
+    pub fn show_lsa_secrets(&self, format: OutputFormat) -> anyhow::Result<()> {
+        self.data_table.show_lsa_secrets(format)
+    }
+
+    pub fn export_backup_keys(&self, output_dir: &Path) -> anyhow::Result<()> {
+        self.data_table.export_backup_keys(output_dir)
+    }
+
+    pub fn show_trusted_domains(&self, format: OutputFormat) -> anyhow::Result<()> {
+        self.data_table.show_trusted_domains(format)
+    }
+
+    pub fn show_group_memberships(
+        &self,
+        format: OutputFormat,
+        include_absent: bool,
+    ) -> anyhow::Result<()> {
+        self.data_table.show_group_memberships(format, include_absent)
+    }
+
+    pub fn show_shadow_credentials(&self, format: OutputFormat) -> anyhow::Result<()> {
+        self.data_table.show_shadow_credentials(format)
+    }
+
+    pub fn show_anomalies(&self, format: OutputFormat) -> anyhow::Result<()> {
+        self.data_table.show_anomalies(format)
+    }
+
+    pub fn show_organizational_units(&self, format: OutputFormat) -> anyhow::Result<()> {
+        self.data_table.show_organizational_units(format)
+    }
+
+    pub fn show_group_policy_objects(&self, format: OutputFormat) -> anyhow::Result<()> {
+        self.data_table.show_group_policy_objects(format)
+    }
+
+    pub fn show_domain_summary(&self, format: OutputFormat) -> anyhow::Result<()> {
+        self.data_table.show_domain_summary(format)
+    }
+
+    pub fn show_password_policies(&self, format: OutputFormat) -> anyhow::Result<()> {
+        self.data_table.show_password_policies(format)
+    }
+
+    pub fn show_dns_records(&self, format: OutputFormat) -> anyhow::Result<()> {
+        self.data_table.show_dns_records(format)
+    }
+
+    pub fn export_zone_files(&self, output_dir: &Path) -> anyhow::Result<()> {
+        self.data_table.export_zone_files(output_dir)
+    }
+
+    pub fn show_topology(
+        &self,
+        objects: TopologyObjects,
+        format: OutputFormat,
+    ) -> anyhow::Result<()> {
+        self.data_table.show_topology(objects, format)
+    }
+
+    pub fn show_certificate_services(
+        &self,
+        objects: AdcsObjects,
+        format: OutputFormat,
+    ) -> anyhow::Result<()> {
+        self.data_table.show_certificate_services(objects, format)
+    }
+
     pub fn show_users<T: SerializationType>(&self, options: &OutputOptions) -> anyhow::Result<()> {
         self.show_typed_objects::<Person<T>>(options, ObjectType::Person)
     }
@@ -80,6 +208,18 @@ impl<'info, 'db> CDatabase<'info, 'db> {
         self.show_typed_objects::<Computer<T>>(options, ObjectType::Computer)
     }
 
+    pub fn show_objects_of_class<T: SerializationType>(
+        &self,
+        options: &OutputOptions,
+        class_name: &str,
+    ) -> anyhow::Result<()> {
+        self.data_table.show_objects_of_class::<T>(options, class_name)
+    }
+
+    pub fn show_laps<T: SerializationType>(&self, options: &OutputOptions) -> anyhow::Result<()> {
+        self.data_table.show_laps::<T>(options)
+    }
+
     pub fn show_typed_objects<O: ntds::FromDataTable + ntds::IsMemberOf>(
         &self,
         options: &OutputOptions,
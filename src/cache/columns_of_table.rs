@@ -31,6 +31,13 @@ impl ColumnsOfTable {
     pub fn iter(&self) -> impl Iterator<Item = &Column> {
         self.ids.iter()
     }
+
+    /// returns the column with the given name, if the table has one
+    pub fn get(&self, name: &str) -> Option<&Column> {
+        self.names
+            .get(name)
+            .map(|index| self.ids.index(*(index.deref()) as usize))
+    }
 }
 
 impl Index<ColumnIndex> for ColumnsOfTable {
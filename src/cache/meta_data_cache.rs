@@ -54,6 +54,9 @@ pub struct MetaDataCache {
     record_by_guid: HashMap<Guid, RecordPointer>,
     attributes: HashMap<i32, String>,
 
+    #[getset(skip)]
+    attribute_ids: HashMap<String, i32>,
+
     #[getset(get = "pub")]
     root: RecordPointer,
 }
@@ -209,11 +212,18 @@ impl TryFrom<&EsedbInfo<'_>> for MetaDataCache {
         }
         bar.finish_and_clear();
 
+        // `lDAPDisplayName`s are case-insensitive
+        let attribute_ids = attributes
+            .iter()
+            .map(|(id, name)| (name.to_lowercase(), *id))
+            .collect();
+
         Ok(Self {
             records,
             record_rows,
             children_of,
             attributes,
+            attribute_ids,
             record_by_guid,
             root: root.expect("no root object found"),
         })
@@ -304,6 +314,18 @@ impl MetaDataCache {
         }
     }
 
+    /// returns the `attributeID` of the attribute with the given `lDAPDisplayName`
+    pub fn attribute_id(&self, ldap_display_name: &str) -> Option<i32> {
+        self.attribute_ids
+            .get(&ldap_display_name.to_lowercase())
+            .copied()
+    }
+
+    /// returns the `lDAPDisplayName` of the attribute with the given `attributeID`
+    pub fn attribute_name(&self, attribute_id: i32) -> Option<&str> {
+        self.attributes.get(&attribute_id).map(|name| &name[..])
+    }
+
     pub fn ptr_from_guid(&self, guid: &Guid) -> Option<&RecordPointer> {
         self.record_by_guid.get(guid)
     }
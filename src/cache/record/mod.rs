@@ -109,4 +109,27 @@ impl<'info, 'db> Record<'info, 'db> {
             columns,
         })
     }
+
+    /// calls `function` with all values of a multi-valued column. In contrast
+    /// to [`WithValue::with_value`], which only returns the type of the values
+    /// of a multi-valued column, every single value is read.
+    pub fn with_values<T>(
+        &self,
+        index: ColumnIndex,
+        mut function: impl FnMut(&[Value]) -> anyhow::Result<T>,
+    ) -> anyhow::Result<T> {
+        if self.record.is_multi(*index)? {
+            let values = self
+                .record
+                .multi(*index)?
+                .iter_values()?
+                .map(|value| value.map(Value::from))
+                .collect::<std::io::Result<Vec<_>>>()?;
+            function(&values)
+        } else {
+            self.with_value(index, |value| {
+                function(value.map(std::slice::from_ref).unwrap_or_default())
+            })
+        }
+    }
 }
@@ -25,6 +25,19 @@ where
     #[getset(get = "pub")]
     link_base_id: ColumnIndex,
 
+    #[getset(get = "pub")]
+    link_data_id: ColumnIndex,
+
+    /// the time when the value has been removed (only for absent values)
+    #[getset(get = "pub")]
+    link_deltime_id: Option<ColumnIndex>,
+
+    #[getset(get = "pub")]
+    link_usnchanged_id: Option<ColumnIndex>,
+
+    #[getset(get = "pub")]
+    link_metadata_id: Option<ColumnIndex>,
+
     // this is needed for `::all_atributes`
     columns: Rc<ColumnsOfTable>,
 }
@@ -47,6 +60,10 @@ where
             link_dnt_id: *columns["link_DNT"].index(),
             backlink_dnt_id: *columns["backlink_DNT"].index(),
             link_base_id: *columns["link_base"].index(),
+            link_data_id: *columns["link_data"].index(),
+            link_deltime_id: columns.get("link_deltime").map(|c| *c.index()),
+            link_usnchanged_id: columns.get("link_usnchanged").map(|c| *c.index()),
+            link_metadata_id: columns.get("link_metadata").map(|c| *c.index()),
             columns: Rc::new(ColumnsOfTable::try_from(table)?)
         })
     }
@@ -0,0 +1,18 @@
+use strum::Display;
+
+#[derive(clap::ValueEnum, Clone, Copy, Display, Hash, Eq, PartialEq)]
+pub enum AdcsObjects {
+    /// show all certificate templates, their permissions and the
+    /// misconfigurations which allow privilege escalation (ESC1 to ESC4)
+    #[strum(serialize = "templates")]
+    Templates,
+
+    /// show all certification authorities which issue certificates
+    #[strum(serialize = "enrollment-services")]
+    EnrollmentServices,
+
+    /// show the certificates of the trusted root and intermediate
+    /// certification authorities and of `NTAuthCertificates`
+    #[strum(serialize = "certificates")]
+    Certificates,
+}
@@ -1,6 +1,11 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
 use clap::Parser;
 use getset::Getters;
 
+use crate::crypto::{BootKey, SystemHive};
+
 use super::Commands;
 
 
@@ -14,6 +19,43 @@ pub struct Args {
     /// name of the file to analyze
     pub(crate) ntds_file: String,
 
+    /// SYSTEM registry hive of the domain controller, used to obtain the boot key
+    #[clap(long("system"), global = true)]
+    pub(crate) system_hive: Option<String>,
+
+    /// boot key of the domain controller (32 hex digits)
+    #[clap(long("bootkey"), global = true, conflicts_with = "system_hive")]
+    pub(crate) boot_key: Option<String>,
+
+    /// replace all secrets (hashes, keys and cleartext passwords) by `REDACTED`
+    #[clap(long("redact"), global = true)]
+    pub(crate) redact: bool,
+
+    /// replace SIDs, account names, UPNs and DNs by pseudonyms (requires `--redact`)
+    #[clap(long("pseudonymize"), global = true, requires = "redact")]
+    pub(crate) pseudonymize: bool,
+
+    /// CSV file which contains the mapping between original values and their
+    /// pseudonyms. Existing mappings are read from this file, and all mappings
+    /// are written to it after the command has finished
+    #[clap(long("pseudonym-map"), global = true, requires = "pseudonymize")]
+    pub(crate) pseudonym_map: Option<PathBuf>,
+
     #[clap(flatten)]
     pub(crate) verbose: clap_verbosity_flag::Verbosity,
 }
+
+impl Args {
+    /// returns the boot key, which is either specified directly or
+    /// read from the SYSTEM registry hive
+    pub fn read_boot_key(&self) -> anyhow::Result<Option<BootKey>> {
+        if let Some(boot_key) = self.boot_key() {
+            Ok(Some(BootKey::from_str(boot_key)?))
+        } else if let Some(system_hive) = self.system_hive() {
+            let hive = SystemHive::from_path(system_hive)?;
+            Ok(Some(BootKey::from_system_hive(&hive)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
@@ -1,7 +1,11 @@
+use std::path::PathBuf;
+
 use clap::{Subcommand, ValueEnum};
 use strum::Display;
 
-use super::{EntryFormat, MemberOfAttribute, OutputFormat};
+use super::{
+    AdcsObjects, CrackerFormat, EntryFormat, MemberOfAttribute, OutputFormat, TopologyObjects,
+};
 
 #[derive(Subcommand)]
 pub enum Commands {
@@ -27,6 +31,25 @@ pub enum Commands {
         /// specify which attribute shall be used to display group memberships
         #[clap(long("member-of"), default_value_t=MemberOfAttribute::Rdn)]
         member_of_attribute: MemberOfAttribute,
+
+        /// decrypt and display the NT and LM password histories (requires
+        /// `--system` or `--bootkey`)
+        #[clap(long("password-history"))]
+        password_history: bool,
+
+        /// display the key credentials (`msDS-KeyCredentialLink`)
+        #[clap(long("key-credentials"))]
+        key_credentials: bool,
+
+        /// display the effective password policy (including fine-grained
+        /// password policies) and the time when the password expires
+        #[clap(long("password-policy"))]
+        password_policy: bool,
+
+        /// display the decoded replication metadata (`replPropertyMetaData`)
+        /// of every object. This option is ignored when CSV-Output is selected
+        #[clap(long("replication-metadata"))]
+        replication_metadata: bool,
     },
 
     /// Display groups
@@ -51,6 +74,11 @@ pub enum Commands {
         /// specify which attribute shall be used to display group memberships
         #[clap(long("member-of"), default_value_t=MemberOfAttribute::Rdn)]
         member_of_attribute: MemberOfAttribute,
+
+        /// display the decoded replication metadata (`replPropertyMetaData`)
+        /// of every object. This option is ignored when CSV-Output is selected
+        #[clap(long("replication-metadata"))]
+        replication_metadata: bool,
     },
 
     /// display computer accounts
@@ -75,6 +103,45 @@ pub enum Commands {
         /// specify which attribute shall be used to display group memberships
         #[clap(long("member-of"), default_value_t=MemberOfAttribute::Rdn)]
         member_of_attribute: MemberOfAttribute,
+
+        /// decrypt and display the NT and LM password histories (requires
+        /// `--system` or `--bootkey`)
+        #[clap(long("password-history"))]
+        password_history: bool,
+
+        /// display the key credentials (`msDS-KeyCredentialLink`)
+        #[clap(long("key-credentials"))]
+        key_credentials: bool,
+
+        /// display the decoded replication metadata (`replPropertyMetaData`)
+        /// of every object. This option is ignored when CSV-Output is selected
+        #[clap(long("replication-metadata"))]
+        replication_metadata: bool,
+    },
+
+    /// display all objects of a class (including its subclasses), such as
+    /// `organizationalUnit` or `msDS-GroupManagedServiceAccount`
+    Objects {
+        /// `lDAPDisplayName` of the class
+        #[clap(short('C'), long("class"))]
+        class: String,
+
+        /// Output format
+        #[clap(value_enum, short('F'), long("format"), default_value_t = OutputFormat::Csv)]
+        format: OutputFormat,
+
+        /// include the distinguished name (DN) in the output.
+        ///
+        /// Note that this
+        /// property is not an attribute of the AD entry iself; instead it is
+        /// constructed from the relative DN (RDN) of the entry and
+        /// all of its parents. That's why this property is normally not shown.
+        #[clap(short('D'), long("include-dn"))]
+        include_dn: bool,
+
+        /// specify which attribute shall be used to display group memberships
+        #[clap(long("member-of"), default_value_t=MemberOfAttribute::Rdn)]
+        member_of_attribute: MemberOfAttribute,
     },
 
     /// create a timeline (in flow-record format)
@@ -87,6 +154,17 @@ pub enum Commands {
         #[clap(long("include-deleted"))]
         include_deleted: bool,
 
+        /// also create one event for every attribute change which is recorded
+        /// in the replication metadata
+        #[clap(long("attribute-changes"))]
+        attribute_changes: bool,
+
+        /// also create events for every member which has been added to or
+        /// removed from a group, which are taken from the linked value
+        /// replication metadata
+        #[clap(long("membership-changes"))]
+        membership_changes: bool,
+
         /// output format
         #[clap(short('F'), long("format"), default_value_t=TimelineFormat::Record)]
         format: TimelineFormat,
@@ -130,6 +208,260 @@ pub enum Commands {
         #[clap(short('i'), long("ignore-case"))]
         ignore_case: bool,
     },
+
+    /// display the decrypted password encryption keys (PEK)
+    ///
+    /// This requires the boot key of the domain controller, which must be
+    /// specified using `--system` or `--bootkey`
+    Pek,
+
+    /// display the NT and LM hashes of all user and computer accounts
+    ///
+    /// This requires the boot key of the domain controller, which must be
+    /// specified using `--system` or `--bootkey`
+    Hashes {
+        /// Output format. If no format is specified, the hashes are displayed
+        /// in the format used by secretsdump (`domain\sam:rid:lm:nt:::`)
+        #[clap(value_enum, short('F'), long("format"))]
+        format: Option<OutputFormat>,
+    },
+
+    /// display Kerberos keys, WDigest hashes and cleartext passwords from
+    /// the `supplementalCredentials` of all user and computer accounts
+    ///
+    /// This requires the boot key of the domain controller, which must be
+    /// specified using `--system` or `--bootkey`
+    Credentials {
+        /// Output format. If no format is specified, the current Kerberos keys
+        /// and cleartext passwords are displayed in the format used by
+        /// secretsdump (`domain\sam:keytype:key`)
+        #[clap(value_enum, short('F'), long("format"))]
+        format: Option<OutputFormat>,
+
+        /// display only cleartext passwords (of accounts with reversible encryption)
+        #[clap(long("cleartext-only"))]
+        cleartext_only: bool,
+    },
+
+    /// export the NT and LM hashes in a format which can directly be used
+    /// by hashcat or John the Ripper
+    ///
+    /// This requires the boot key of the domain controller, which must be
+    /// specified using `--system` or `--bootkey`
+    Export {
+        /// directory where the hash files will be created (one file per hash type)
+        #[clap(short('o'), long("output-dir"), default_value = ".")]
+        output_dir: PathBuf,
+
+        /// password cracker whose input format should be used
+        #[clap(value_enum, short('F'), long("format"), default_value_t = CrackerFormat::Hashcat)]
+        format: CrackerFormat,
+
+        /// also export the password histories
+        #[clap(long("history"))]
+        include_history: bool,
+
+        /// skip accounts which are disabled
+        #[clap(long("exclude-disabled"))]
+        exclude_disabled: bool,
+
+        /// skip computer accounts
+        #[clap(long("exclude-machine-accounts"))]
+        exclude_machine_accounts: bool,
+
+        /// skip deleted accounts
+        #[clap(long("exclude-deleted"))]
+        exclude_deleted: bool,
+    },
+
+    /// audit the password hashes of all accounts for reused, empty and known passwords
+    ///
+    /// This requires the boot key of the domain controller, which must be
+    /// specified using `--system` or `--bootkey`
+    Audit {
+        /// Output format
+        #[clap(value_enum, short('F'), long("format"), default_value_t = OutputFormat::Csv)]
+        format: OutputFormat,
+
+        /// file with known NT hashes, either one hash per line or in the
+        /// form `hash:password` (such as the hashcat potfile)
+        #[clap(long("known-hashes"))]
+        known_hashes: Option<PathBuf>,
+
+        /// file with one password per line, which will be hashed and
+        /// compared to the NT hashes of all accounts
+        #[clap(short('w'), long("wordlist"))]
+        wordlist: Option<PathBuf>,
+
+        /// specify which attribute shall be used to display group memberships
+        #[clap(long("member-of"), default_value_t=MemberOfAttribute::Rdn)]
+        member_of_attribute: MemberOfAttribute,
+    },
+
+    /// derive the current and previous passwords of all group managed
+    /// service accounts (gMSA) from the KDS root keys
+    ///
+    /// This requires the boot key of the domain controller, which must be
+    /// specified using `--system` or `--bootkey`
+    Gmsa {
+        /// Output format. If no format is specified, the NT hashes and AES
+        /// keys are displayed in the format used by secretsdump
+        #[clap(value_enum, short('F'), long("format"))]
+        format: Option<OutputFormat>,
+    },
+
+    /// display all computers whose local administrator password is managed
+    /// by LAPS (legacy Microsoft LAPS and Windows LAPS)
+    ///
+    /// Passwords which have been encrypted by Windows LAPS can only be
+    /// decrypted if the boot key of the domain controller is specified
+    /// using `--system` or `--bootkey`
+    Laps {
+        /// Output format
+        #[clap(value_enum, short('F'), long("format"), default_value_t = OutputFormat::Csv)]
+        format: OutputFormat,
+
+        /// include the distinguished name (DN) in the output
+        #[clap(short('D'), long("include-dn"))]
+        include_dn: bool,
+
+        /// specify which attribute shall be used to display group memberships
+        #[clap(long("member-of"), default_value_t=MemberOfAttribute::Rdn)]
+        member_of_attribute: MemberOfAttribute,
+    },
+
+    /// display the BitLocker recovery passwords and key packages of all computers
+    Bitlocker {
+        /// Output format
+        #[clap(value_enum, short('F'), long("format"), default_value_t = OutputFormat::Csv)]
+        format: OutputFormat,
+    },
+
+    /// decrypt the LSA secret objects, such as the DPAPI domain backup keys
+    ///
+    /// This requires the boot key of the domain controller, which must be
+    /// specified using `--system` or `--bootkey`
+    Secrets {
+        /// Output format
+        #[clap(value_enum, short('F'), long("format"), default_value_t = OutputFormat::Csv)]
+        format: OutputFormat,
+
+        /// export the DPAPI domain backup keys (as PVK, PEM and DER files)
+        /// into this directory
+        #[clap(short('o'), long("export-dir"))]
+        export_dir: Option<PathBuf>,
+    },
+
+    /// display the trust relationships to other domains
+    ///
+    /// If the boot key of the domain controller is specified using
+    /// `--system` or `--bootkey`, the trust keys are decrypted as well
+    Trusts {
+        /// Output format
+        #[clap(value_enum, short('F'), long("format"), default_value_t = OutputFormat::Csv)]
+        format: OutputFormat,
+    },
+
+    /// display all key credentials (`msDS-KeyCredentialLink`) which do not
+    /// belong to a registered device, which might indicate "Shadow Credentials"
+    ShadowCredentials {
+        /// Output format
+        #[clap(value_enum, short('F'), long("format"), default_value_t = OutputFormat::Csv)]
+        format: OutputFormat,
+    },
+
+    /// display all group memberships (values of the `member` attribute),
+    /// including when and on which DC they have been added
+    Memberships {
+        /// Output format
+        #[clap(value_enum, short('F'), long("format"), default_value_t = OutputFormat::Csv)]
+        format: OutputFormat,
+
+        /// include also the members which have been removed from their
+        /// groups, but which are still stored as absent linked values
+        #[clap(long("history"))]
+        history: bool,
+    },
+
+    /// cross-check the timestamps, update sequence numbers and replication
+    /// metadata of all objects to detect manipulations, such as timestomping,
+    /// USN rollbacks or objects injected using DCShadow
+    Anomalies {
+        /// Output format
+        #[clap(value_enum, short('F'), long("format"), default_value_t = OutputFormat::Csv)]
+        format: OutputFormat,
+    },
+
+    /// display the domain and all organizational units, together with the
+    /// GPOs which are linked to them and which apply to them
+    Ou {
+        /// Output format
+        #[clap(value_enum, short('F'), long("format"), default_value_t = OutputFormat::Csv)]
+        format: OutputFormat,
+    },
+
+    /// display all Group Policy Objects, together with the containers they
+    /// are linked to
+    Gpo {
+        /// Output format
+        #[clap(value_enum, short('F'), long("format"), default_value_t = OutputFormat::Csv)]
+        format: OutputFormat,
+    },
+
+    /// display the names, functional levels and the default password policy
+    /// of the domain, together with the tombstone lifetime and the state of
+    /// the AD Recycle Bin
+    Domain {
+        /// Output format
+        #[clap(value_enum, short('F'), long("format"), default_value_t = OutputFormat::Csv)]
+        format: OutputFormat,
+    },
+
+    /// display the default password policy of the domain and all
+    /// fine-grained password policies (`msDS-PasswordSettings`)
+    PasswordPolicies {
+        /// Output format
+        #[clap(value_enum, short('F'), long("format"), default_value_t = OutputFormat::Csv)]
+        format: OutputFormat,
+    },
+
+    /// display the records of all AD-integrated DNS zones, including
+    /// tombstoned records of deleted nodes
+    Dns {
+        /// Output format
+        #[clap(value_enum, short('F'), long("format"), default_value_t = OutputFormat::Csv)]
+        format: OutputFormat,
+
+        /// instead of displaying the records, export one BIND zone file per
+        /// zone into this directory
+        #[clap(short('o'), long("zone-dir"))]
+        zone_dir: Option<PathBuf>,
+    },
+
+    /// display the sites, subnets, site links and servers of the forest,
+    /// including domain controllers which have already been demoted
+    Topology {
+        /// Output format
+        #[clap(value_enum, short('F'), long("format"), default_value_t = OutputFormat::Csv)]
+        format: OutputFormat,
+
+        /// the type of objects to display
+        #[clap(value_enum, short('t'), long("type"), default_value_t = TopologyObjects::Servers)]
+        objects: TopologyObjects,
+    },
+
+    /// display the certificate templates, certification authorities and
+    /// certificates of Active Directory Certificate Services, and flag
+    /// vulnerable certificate templates
+    Adcs {
+        /// Output format
+        #[clap(value_enum, short('F'), long("format"), default_value_t = OutputFormat::Csv)]
+        format: OutputFormat,
+
+        /// the type of objects to display
+        #[clap(value_enum, short('t'), long("type"), default_value_t = AdcsObjects::Templates)]
+        objects: AdcsObjects,
+    },
 }
 
 impl Commands {
@@ -140,24 +472,38 @@ impl Commands {
                 show_all,
                 include_dn: _,
                 member_of_attribute: _,
+                password_history: _,
+                key_credentials: _,
+                password_policy: _,
+                replication_metadata: _,
             }
             | Commands::User {
                 format: OutputFormat::JsonLines,
                 show_all,
                 include_dn: _,
                 member_of_attribute: _,
+                password_history: _,
+                key_credentials: _,
+                password_policy: _,
+                replication_metadata: _,
             }
             | Commands::Computer {
                 format: OutputFormat::Json,
                 show_all,
                 include_dn: _,
                 member_of_attribute: _,
+                password_history: _,
+                key_credentials: _,
+                replication_metadata: _,
             }
             | Commands::Computer {
                 format: OutputFormat::JsonLines,
                 show_all,
                 include_dn: _,
                 member_of_attribute: _,
+                password_history: _,
+                key_credentials: _,
+                replication_metadata: _,
             } => *show_all,
             _ => false,
         }
@@ -170,19 +516,29 @@ impl Commands {
                 show_all: _,
                 include_dn,
                 member_of_attribute: _,
+                password_history: _,
+                key_credentials: _,
+                password_policy: _,
+                replication_metadata: _,
             }
             | Commands::Group {
                 format: _,
                 show_all: _,
                 include_dn,
                 member_of_attribute: _,
+                replication_metadata: _,
             } => *include_dn,
             Commands::Computer {
                 format: _,
                 show_all: _,
                 include_dn,
                 member_of_attribute: _,
+                password_history: _,
+                key_credentials: _,
+                replication_metadata: _,
             } => *include_dn,
+            Commands::Laps { include_dn, .. } => *include_dn,
+            Commands::Objects { include_dn, .. } => *include_dn,
             _ => false,
         }
     }
@@ -194,23 +550,94 @@ impl Commands {
                 show_all: _,
                 include_dn: _,
                 member_of_attribute,
+                password_history: _,
+                key_credentials: _,
+                password_policy: _,
+                replication_metadata: _,
             } => *member_of_attribute,
             Commands::Group {
                 format: _,
                 show_all: _,
                 include_dn: _,
                 member_of_attribute,
+                replication_metadata: _,
             } => *member_of_attribute,
             Commands::Computer {
                 format: _,
                 show_all: _,
                 include_dn: _,
                 member_of_attribute,
+                password_history: _,
+                key_credentials: _,
+                replication_metadata: _,
+            } => *member_of_attribute,
+            Commands::Audit {
+                member_of_attribute,
+                ..
+            } => *member_of_attribute,
+            Commands::Laps {
+                member_of_attribute,
+                ..
+            } => *member_of_attribute,
+            Commands::Objects {
+                member_of_attribute,
+                ..
             } => *member_of_attribute,
             _ => MemberOfAttribute::Rdn,
         }
     }
 
+    pub fn show_password_history(&self) -> bool {
+        match self {
+            Commands::User {
+                password_history, ..
+            }
+            | Commands::Computer {
+                password_history, ..
+            } => *password_history,
+            _ => false,
+        }
+    }
+
+    pub fn show_key_credentials(&self) -> bool {
+        match self {
+            Commands::User {
+                key_credentials, ..
+            }
+            | Commands::Computer {
+                key_credentials, ..
+            } => *key_credentials,
+            _ => false,
+        }
+    }
+
+    pub fn show_replication_metadata(&self) -> bool {
+        match self {
+            Commands::User {
+                replication_metadata,
+                ..
+            }
+            | Commands::Group {
+                replication_metadata,
+                ..
+            }
+            | Commands::Computer {
+                replication_metadata,
+                ..
+            } => *replication_metadata,
+            _ => false,
+        }
+    }
+
+    pub fn show_password_policy(&self) -> bool {
+        match self {
+            Commands::User {
+                password_policy, ..
+            } => *password_policy,
+            _ => false,
+        }
+    }
+
     pub fn flat_serialization(&self) -> bool {
         matches!(
             &self,
@@ -223,6 +650,15 @@ impl Commands {
             } | Commands::Group {
                 format: OutputFormat::Csv,
                 ..
+            } | Commands::Audit {
+                format: OutputFormat::Csv,
+                ..
+            } | Commands::Laps {
+                format: OutputFormat::Csv,
+                ..
+            } | Commands::Objects {
+                format: OutputFormat::Csv,
+                ..
             } | Commands::Timeline { .. }
         )
     }
@@ -233,6 +669,9 @@ impl Commands {
             Commands::Group { format, .. } => Some(*format),
             Commands::Computer { format, .. } => Some(*format),
             Commands::Types { format } => Some(*format),
+            Commands::Audit { format, .. } => Some(*format),
+            Commands::Laps { format, .. } => Some(*format),
+            Commands::Objects { format, .. } => Some(*format),
             _ => None,
         }
     }
@@ -0,0 +1,58 @@
+use strum::Display;
+
+use crate::crypto::PasswordHash;
+use crate::redact_secret;
+
+#[derive(clap::ValueEnum, Clone, Copy, Display, Default, Eq, PartialEq)]
+pub enum CrackerFormat {
+    /// hashcat, modes 1000 (NT) and 3000 (LM); use `--username` when cracking
+    #[default]
+    #[strum(serialize = "hashcat")]
+    Hashcat,
+
+    /// John the Ripper, formats `NT` and `LM`
+    #[strum(serialize = "john")]
+    John,
+}
+
+impl CrackerFormat {
+    /// name of the file which contains the NT hashes
+    pub fn nt_file_name(&self) -> &'static str {
+        match self {
+            CrackerFormat::Hashcat => "hashcat_1000.txt",
+            CrackerFormat::John => "john_nt.txt",
+        }
+    }
+
+    /// name of the file which contains the LM hashes
+    pub fn lm_file_name(&self) -> &'static str {
+        match self {
+            CrackerFormat::Hashcat => "hashcat_3000.txt",
+            CrackerFormat::John => "john_lm.txt",
+        }
+    }
+
+    pub fn nt_line(&self, user_name: &str, hash: &PasswordHash) -> String {
+        match self {
+            CrackerFormat::Hashcat => format!("{user_name}:{}", redact_secret(&hash.to_string())),
+            CrackerFormat::John => format!("{user_name}:$NT${}", redact_secret(&hash.to_string())),
+        }
+    }
+
+    /// LM hashes are cracked as two independent halves, so this returns one
+    /// line for every half which does not belong to an empty password
+    pub fn lm_lines(&self, user_name: &str, hash: &PasswordHash) -> Vec<String> {
+        let empty_half = &PasswordHash::EMPTY_LM.as_bytes()[..8];
+        hash.as_bytes()
+            .chunks_exact(8)
+            .filter(|half| *half != empty_half)
+            .map(|half| {
+                let half = redact_secret(&hex::encode(half)).to_string();
+                match self {
+                    CrackerFormat::Hashcat => format!("{user_name}:{half}"),
+                    CrackerFormat::John => format!("{user_name}:$LM${half}"),
+                }
+            })
+            .collect()
+    }
+}
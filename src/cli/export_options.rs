@@ -0,0 +1,13 @@
+use getset::{Getters, Setters};
+
+use super::CrackerFormat;
+
+#[derive(Getters, Setters, Default)]
+#[getset(get="pub", set="pub")]
+pub struct ExportOptions {
+    format: CrackerFormat,
+    include_history: bool,
+    exclude_disabled: bool,
+    exclude_machine_accounts: bool,
+    exclude_deleted: bool,
+}
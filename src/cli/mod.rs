@@ -5,10 +5,18 @@ mod output_options;
 mod entry_format;
 pub mod output;
 mod member_of_attribute;
+mod cracker_format;
+mod export_options;
+mod topology_objects;
+mod adcs_objects;
 
 pub use commands::*;
 pub use args::*;
 pub use output_format::*;
 pub use output_options::*;
 pub use entry_format::*;
-pub use member_of_attribute::*;
\ No newline at end of file
+pub use member_of_attribute::*;
+pub use cracker_format::*;
+pub use export_options::*;
+pub use topology_objects::*;
+pub use adcs_objects::*;
\ No newline at end of file
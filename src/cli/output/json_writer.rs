@@ -1,3 +1,5 @@
+use serde::Serialize;
+
 use super::Writer;
 
 #[derive(Default)]
@@ -16,4 +18,14 @@ impl Writer for JsonWriter {
                 })
         }))
     }
+
+    fn write_records<I, R>(&self, records: I) -> anyhow::Result<()>
+    where
+        I: Iterator<Item = R>,
+        R: Serialize,
+    {
+        let records: Vec<_> = records.collect();
+        println!("{}", serde_json::to_string_pretty(&records)?);
+        Ok(())
+    }
 }
@@ -1,5 +1,12 @@
+use serde::Serialize;
+
 pub trait Writer {
     fn write_typenames<I>(&self, names: I) -> anyhow::Result<()>
     where
         I: Iterator<Item = String>;
+
+    fn write_records<I, R>(&self, records: I) -> anyhow::Result<()>
+    where
+        I: Iterator<Item = R>,
+        R: Serialize;
 }
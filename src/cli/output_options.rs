@@ -8,6 +8,13 @@ pub struct OutputOptions {
     flat_serialization: bool,
     display_all_attributes: bool,
     show_all_objects: bool,
+    show_attribute_changes: bool,
+    show_membership_changes: bool,
     include_dn: bool,
+    show_password_history: bool,
+    show_laps: bool,
+    show_key_credentials: bool,
+    show_password_policy: bool,
+    show_replication_metadata: bool,
     format: Option<OutputFormat>
 }
\ No newline at end of file
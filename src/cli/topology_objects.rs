@@ -0,0 +1,21 @@
+use strum::Display;
+
+#[derive(clap::ValueEnum, Clone, Copy, Display, Hash, Eq, PartialEq)]
+pub enum TopologyObjects {
+    /// show all sites, together with their subnets, site links and servers
+    #[strum(serialize = "sites")]
+    Sites,
+
+    /// show all subnets and the sites they are assigned to
+    #[strum(serialize = "subnets")]
+    Subnets,
+
+    /// show all site links with their cost and replication interval
+    #[strum(serialize = "site-links")]
+    SiteLinks,
+
+    /// show all servers, including the settings of the domain controllers
+    /// and servers which have been deleted
+    #[strum(serialize = "servers")]
+    Servers,
+}
@@ -1,14 +1,19 @@
 use std::{collections::HashMap, ops::Index};
 
 use crate::{
-    column_information::ColumnInformation, esedb_mitigation::libesedb_count, ntds::NtdsAttributeId,
+    column_information::ColumnInformation,
+    esedb_mitigation::libesedb_count,
+    ntds::{AttributeSyntax, NtdsAttributeId},
 };
 use anyhow::Result;
+use lazy_regex::regex_captures;
 use libesedb::Table;
 
 pub struct ColumnInfoMapping {
     mapping: HashMap<NtdsAttributeId, ColumnInformation>,
     str_mapping: HashMap<String, ColumnInformation>,
+    attribute_id_mapping: HashMap<i32, ColumnInformation>,
+    attribute_syntaxes: HashMap<i32, AttributeSyntax>,
 }
 
 impl Index<NtdsAttributeId> for ColumnInfoMapping {
@@ -23,6 +28,19 @@ impl ColumnInfoMapping {
     pub fn info_by_name(&self, index: &str) -> Option<&ColumnInformation> {
         self.str_mapping.get(index)
     }
+
+    /// returns the column which stores the attribute with the given
+    /// `attributeID`. This can be used for attributes which are not known to
+    /// [`NtdsAttributeId`], e.g. because they have been added by a newer schema.
+    pub fn info_by_attribute_id(&self, attribute_id: i32) -> Option<&ColumnInformation> {
+        self.attribute_id_mapping.get(&attribute_id)
+    }
+
+    /// returns the syntax of the attribute with the given `attributeID`,
+    /// which is derived from the name of its column
+    pub fn syntax_by_attribute_id(&self, attribute_id: i32) -> Option<AttributeSyntax> {
+        self.attribute_syntaxes.get(&attribute_id).copied()
+    }
 }
 
 impl TryFrom<&Table<'_>> for ColumnInfoMapping {
@@ -30,6 +48,8 @@ impl TryFrom<&Table<'_>> for ColumnInfoMapping {
     fn try_from(data_table: &Table) -> Result<Self, Self::Error> {
         let mut mapping = HashMap::new();
         let mut str_mapping = HashMap::new();
+        let mut attribute_id_mapping = HashMap::new();
+        let mut attribute_syntaxes = HashMap::new();
 
         for index in 0..libesedb_count(|| data_table.count_columns())? {
             let column = data_table.column(index)?;
@@ -38,12 +58,28 @@ impl TryFrom<&Table<'_>> for ColumnInfoMapping {
                 mapping.insert(column_id, col_info);
             }
 
+            // attribute columns are named `ATT`, followed by a character
+            // which denotes the syntax and the attribute id
+            if let Some((_, syntax, attribute_id)) =
+                regex_captures!(r"^ATT([a-z])(-?\d+)$", &column.name()?)
+            {
+                if let Ok(attribute_id) = attribute_id.parse() {
+                    attribute_id_mapping.insert(attribute_id, col_info);
+                    let prefix = syntax.chars().next().unwrap_or_default();
+                    if let Some(syntax) = AttributeSyntax::from_column_prefix(prefix) {
+                        attribute_syntaxes.insert(attribute_id, syntax);
+                    }
+                }
+            }
+
             str_mapping.insert(column.name()?.to_string(), col_info);
         }
 
         Ok(Self {
             mapping,
             str_mapping,
+            attribute_id_mapping,
+            attribute_syntaxes,
         })
     }
 }
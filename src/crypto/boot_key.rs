@@ -0,0 +1,58 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::{Error, Result, SystemHive};
+
+/// order in which the bytes of the scrambled LSA key must be read
+const BOOT_KEY_PERMUTATION: [usize; 16] = [8, 5, 4, 2, 11, 9, 13, 3, 0, 6, 1, 12, 14, 10, 15, 7];
+
+/// names of the subkeys of `Control\Lsa` whose class names contain the scrambled boot key
+const BOOT_KEY_PARTS: [&str; 4] = ["JD", "Skew1", "GBG", "Data"];
+
+/// the boot key (also known as syskey) of a domain controller, which is
+/// required to decrypt the password encryption keys (PEK)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BootKey([u8; 16]);
+
+impl BootKey {
+    /// derives the boot key from the class names of the `Control\Lsa\{JD,Skew1,GBG,Data}`
+    /// keys of the current control set
+    pub fn from_system_hive(hive: &SystemHive) -> Result<Self> {
+        let control_set = format!("ControlSet{:03}", hive.current_control_set()?);
+        let mut scrambled = String::with_capacity(32);
+        for part in BOOT_KEY_PARTS {
+            scrambled.push_str(&hive.class_name(&format!("{control_set}\\Control\\Lsa\\{part}"))?);
+        }
+        let scrambled: [u8; 16] = hex::decode(&scrambled)
+            .map_err(|why| Error::InvalidBootKey(why.to_string()))?
+            .try_into()
+            .map_err(|_| {
+                Error::InvalidBootKey(format!("unexpected scrambled key: '{scrambled}'"))
+            })?;
+
+        Ok(Self(BOOT_KEY_PERMUTATION.map(|idx| scrambled[idx])))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+}
+
+impl FromStr for BootKey {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim().trim_start_matches("0x");
+        hex::decode(s)
+            .map_err(|why| Error::InvalidBootKey(why.to_string()))?
+            .try_into()
+            .map(Self)
+            .map_err(|_| Error::InvalidBootKey(format!("expected 32 hex digits, found '{s}'")))
+    }
+}
+
+impl Display for BootKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
@@ -0,0 +1,238 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use getset::Getters;
+use sha1::{Digest, Sha1};
+
+use super::{Error, Result};
+
+const TAG_INTEGER: u8 = 0x02;
+const TAG_OBJECT_IDENTIFIER: u8 = 0x06;
+const TAG_UTF8_STRING: u8 = 0x0c;
+const TAG_PRINTABLE_STRING: u8 = 0x13;
+const TAG_T61_STRING: u8 = 0x14;
+const TAG_IA5_STRING: u8 = 0x16;
+const TAG_UTC_TIME: u8 = 0x17;
+const TAG_GENERALIZED_TIME: u8 = 0x18;
+const TAG_BMP_STRING: u8 = 0x1e;
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_SET: u8 = 0x31;
+const TAG_VERSION: u8 = 0xa0;
+
+/// the attribute types of distinguished names which have a short name
+const NAME_ATTRIBUTES: &[(&str, &str)] = &[
+    ("2.5.4.3", "CN"),
+    ("2.5.4.6", "C"),
+    ("2.5.4.7", "L"),
+    ("2.5.4.8", "ST"),
+    ("2.5.4.10", "O"),
+    ("2.5.4.11", "OU"),
+    ("0.9.2342.19200300.100.1.25", "DC"),
+    ("1.2.840.113549.1.9.1", "E"),
+];
+
+/// the fields of a DER encoded X.509 certificate which are needed to
+/// identify it. The signature is not verified.
+#[derive(Getters)]
+#[getset(get = "pub")]
+pub struct Certificate {
+    /// the subject in the format of RFC 4514, e.g. `CN=ca,DC=contoso,DC=local`
+    subject: String,
+    issuer: String,
+
+    /// the serial number as hexadecimal string
+    serial_number: String,
+    not_before: DateTime<Utc>,
+    not_after: DateTime<Utc>,
+
+    /// the SHA1 hash of the certificate, as displayed by Windows
+    thumbprint: String,
+}
+
+impl TryFrom<&[u8]> for Certificate {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self> {
+        let (certificate, _) = read_element(value, TAG_SEQUENCE)?;
+        let (tbs_certificate, _) = read_element(certificate, TAG_SEQUENCE)?;
+
+        let mut rest = tbs_certificate;
+        if rest.first() == Some(&TAG_VERSION) {
+            rest = read_element(rest, TAG_VERSION)?.1;
+        }
+        let (serial_number, rest) = read_element(rest, TAG_INTEGER)?;
+        let (_signature, rest) = read_element(rest, TAG_SEQUENCE)?;
+        let (issuer, rest) = read_element(rest, TAG_SEQUENCE)?;
+        let (validity, rest) = read_element(rest, TAG_SEQUENCE)?;
+        let (subject, _) = read_element(rest, TAG_SEQUENCE)?;
+        let (not_before, validity) = read_time(validity)?;
+        let (not_after, _) = read_time(validity)?;
+
+        let serial_number = match serial_number {
+            [0, rest @ ..] if !rest.is_empty() => rest,
+            serial_number => serial_number,
+        };
+        Ok(Self {
+            subject: read_name(subject)?,
+            issuer: read_name(issuer)?,
+            serial_number: hex::encode_upper(serial_number),
+            not_before,
+            not_after,
+            thumbprint: hex::encode_upper(Sha1::digest(value)),
+        })
+    }
+}
+
+/// reads a DER element and returns its tag, its content and the remaining data
+fn read_tlv(data: &[u8]) -> Result<(u8, &[u8], &[u8])> {
+    let too_short = || Error::InvalidCertificate("unexpected end of data");
+    let tag = *data.first().ok_or_else(too_short)?;
+    let first_length = *data.get(1).ok_or_else(too_short)?;
+    let (length, offset) = if first_length < 0x80 {
+        (first_length as usize, 2)
+    } else {
+        let count = (first_length & 0x7f) as usize;
+        if count == 0 || count > 4 {
+            return Err(Error::InvalidCertificate("unsupported length encoding"));
+        }
+        let length = data
+            .get(2..2 + count)
+            .ok_or_else(too_short)?
+            .iter()
+            .fold(0usize, |length, byte| (length << 8) | *byte as usize);
+        (length, 2 + count)
+    };
+    let content = data.get(offset..offset + length).ok_or_else(too_short)?;
+    Ok((tag, content, &data[offset + length..]))
+}
+
+/// reads a DER element with the tag `expected_tag`
+fn read_element(data: &[u8], expected_tag: u8) -> Result<(&[u8], &[u8])> {
+    match read_tlv(data)? {
+        (tag, content, rest) if tag == expected_tag => Ok((content, rest)),
+        _ => Err(Error::InvalidCertificate("unexpected element")),
+    }
+}
+
+fn read_time(data: &[u8]) -> Result<(DateTime<Utc>, &[u8])> {
+    let (tag, content, rest) = read_tlv(data)?;
+    let value = std::str::from_utf8(content)
+        .map_err(|_| Error::InvalidCertificate("invalid time"))?;
+    let value = match tag {
+        // two-digit years from 50 to 99 are in the 20th century (RFC 5280)
+        TAG_UTC_TIME if value.as_bytes().first().is_some_and(|b| *b >= b'5') => {
+            format!("19{value}")
+        }
+        TAG_UTC_TIME => format!("20{value}"),
+        TAG_GENERALIZED_TIME => value.to_string(),
+        _ => return Err(Error::InvalidCertificate("unexpected time format")),
+    };
+    let timestamp = NaiveDateTime::parse_from_str(&value, "%Y%m%d%H%M%SZ")
+        .map_err(|_| Error::InvalidCertificate("invalid time"))?;
+    Ok((timestamp.and_utc(), rest))
+}
+
+fn read_object_identifier(data: &[u8]) -> Result<String> {
+    let (first, rest) = data
+        .split_first()
+        .ok_or(Error::InvalidCertificate("empty object identifier"))?;
+    let mut components = vec![(first / 40).to_string(), (first % 40).to_string()];
+    let mut value = 0u64;
+    for byte in rest {
+        value = (value << 7) | u64::from(byte & 0x7f);
+        if byte & 0x80 == 0 {
+            components.push(value.to_string());
+            value = 0;
+        }
+    }
+    Ok(components.join("."))
+}
+
+fn read_string(tag: u8, data: &[u8]) -> String {
+    match tag {
+        TAG_BMP_STRING => String::from_utf16_lossy(
+            &data
+                .chunks_exact(2)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                .collect::<Vec<_>>(),
+        ),
+        TAG_UTF8_STRING | TAG_PRINTABLE_STRING | TAG_T61_STRING | TAG_IA5_STRING => {
+            String::from_utf8_lossy(data).to_string()
+        }
+        _ => format!("#{}", hex::encode(data)),
+    }
+}
+
+/// converts an X.501 `Name` to the string representation of RFC 4514, which
+/// lists the most specific component first
+fn read_name(data: &[u8]) -> Result<String> {
+    let mut components = Vec::new();
+    let mut rest = data;
+    while !rest.is_empty() {
+        let (set, tail) = read_element(rest, TAG_SET)?;
+        rest = tail;
+        let mut attributes = Vec::new();
+        let mut set = set;
+        while !set.is_empty() {
+            let (attribute, tail) = read_element(set, TAG_SEQUENCE)?;
+            set = tail;
+            let (oid, attribute) = read_element(attribute, TAG_OBJECT_IDENTIFIER)?;
+            let (tag, value, _) = read_tlv(attribute)?;
+            let oid = read_object_identifier(oid)?;
+            let name = NAME_ATTRIBUTES
+                .iter()
+                .find(|(attribute_oid, _)| *attribute_oid == oid)
+                .map(|(_, name)| name.to_string())
+                .unwrap_or(oid);
+            attributes.push(format!("{name}={}", escape_value(&read_string(tag, value))));
+        }
+        components.push(attributes.join("+"));
+    }
+    components.reverse();
+    Ok(components.join(","))
+}
+
+fn escape_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, ',' | '+' | '"' | '\\' | '<' | '>' | ';') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Certificate;
+
+    #[test]
+    fn test_certificate() {
+        let value = hex::decode(concat!(
+            "308201d93082017fa00302010202041234abcd300a06082a8648ce3d040302304a31153013060a",
+            "0992268993f22c64011916056c6f63616c31173015060a0992268993f22c6401191607636f6e74",
+            "6f736f3118301606035504030c0f636f6e746f736f2d444330312d4341301e170d323631303136",
+            "3232343035345a170d3336313031333232343035345a304a31153013060a0992268993f22c6401",
+            "1916056c6f63616c31173015060a0992268993f22c6401191607636f6e746f736f311830160603",
+            "5504030c0f636f6e746f736f2d444330312d43413059301306072a8648ce3d020106082a8648ce",
+            "3d03010703420004f954ca301fe80198009e7d756745f32e838ba2063e368d85c9ce849c6e2211",
+            "075e7349c97e74c17b69868b9d9ff5a6db1f8e87297361cd80ff0647b35bcac654a3533051301d",
+            "0603551d0e0416041497e486cbb922ced374a036087f6d1ab91d5c7fd6301f0603551d23041830",
+            "16801497e486cbb922ced374a036087f6d1ab91d5c7fd6300f0603551d130101ff040530030101",
+            "ff300a06082a8648ce3d0403020348003045022100efd7bc60d61f3075e370bced088c440c33f6",
+            "74d5911beaef1c4d31fb060889fb02206349a212b1b3c1a7b7f8455793aa4d975603ca3cd13aea",
+            "da4d7dad30e22483fa"
+        ))
+        .unwrap();
+        let certificate = Certificate::try_from(&value[..]).unwrap();
+        assert_eq!(certificate.subject(), "CN=contoso-DC01-CA,DC=contoso,DC=local");
+        assert_eq!(certificate.issuer(), certificate.subject());
+        assert_eq!(certificate.serial_number(), "1234ABCD");
+        assert_eq!(certificate.not_before().to_rfc3339(), "2026-10-16T22:40:54+00:00");
+        assert_eq!(certificate.not_after().to_rfc3339(), "2036-10-13T22:40:54+00:00");
+        assert_eq!(
+            certificate.thumbprint(),
+            "111CC19F479A1B68FF6F5F53CA3AA361BFC3096B"
+        );
+        assert!(Certificate::try_from(&value[..100]).is_err());
+    }
+}
@@ -0,0 +1,238 @@
+//! parsing and decryption of DPAPI-NG blobs (CMS `EnvelopedData`), which
+//! are protected by the Group Key Distribution Service
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use aes_gcm::aead::Aead;
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
+use aes_kw::KekAes256;
+use getset::Getters;
+
+use super::{Error, GroupKeyIdentifier, KdsRootKey, Result};
+use crate::win32_types::{Guid, Sid};
+
+const DER_INTEGER: u8 = 0x02;
+const DER_OCTET_STRING: u8 = 0x04;
+const DER_OID: u8 = 0x06;
+const DER_UTF8_STRING: u8 = 0x0c;
+const DER_SEQUENCE: u8 = 0x30;
+const DER_SET: u8 = 0x31;
+const DER_CONTEXT_0: u8 = 0xa0;
+const DER_CONTEXT_0_PRIMITIVE: u8 = 0x80;
+const DER_KEK_RECIPIENT_INFO: u8 = 0xa2;
+
+/// 1.2.840.113549.1.7.3 (`envelopedData`)
+const OID_ENVELOPED_DATA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x07, 0x03];
+
+/// 2.16.840.1.101.3.4.1.46 (`aes256-GCM`)
+const OID_AES256_GCM: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x2e];
+
+/// a single DER encoded value
+struct DerValue<'a> {
+    tag: u8,
+    content: &'a [u8],
+}
+
+/// reads one DER encoded value and returns it, together with the remaining data
+fn read_der(data: &[u8]) -> Result<(DerValue<'_>, &[u8])> {
+    let invalid = || Error::InvalidDpapiNgBlob("invalid DER encoding");
+    let tag = *data.first().ok_or_else(invalid)?;
+    let first = *data.get(1).ok_or_else(invalid)?;
+    let (length, header_size) = if first & 0x80 == 0 {
+        (first as usize, 2)
+    } else {
+        let count = (first & 0x7f) as usize;
+        if count == 0 || count > 4 {
+            return Err(invalid());
+        }
+        let bytes = data.get(2..2 + count).ok_or_else(invalid)?;
+        let length = bytes.iter().fold(0, |l, b| (l << 8) | *b as usize);
+        (length, 2 + count)
+    };
+    let content = data
+        .get(header_size..header_size + length)
+        .ok_or_else(invalid)?;
+    Ok((DerValue { tag, content }, &data[header_size + length..]))
+}
+
+/// reads one DER encoded value with the expected tag
+fn expect_der(data: &[u8], tag: u8) -> Result<(&[u8], &[u8])> {
+    let (value, rest) = read_der(data)?;
+    if value.tag != tag {
+        return Err(Error::InvalidDpapiNgBlob("unexpected DER tag"));
+    }
+    Ok((value.content, rest))
+}
+
+/// collects all pairs of UTF8 strings (such as `SID`, `S-1-5-...`) from the
+/// protection descriptor
+fn collect_name_value_pairs(data: &[u8], pairs: &mut Vec<(String, String)>) -> Result<()> {
+    let mut rest = data;
+    let mut strings = Vec::new();
+    while !rest.is_empty() {
+        let (value, remaining) = read_der(rest)?;
+        match value.tag {
+            DER_UTF8_STRING => strings.push(String::from_utf8_lossy(value.content).to_string()),
+            DER_SEQUENCE | DER_SET => collect_name_value_pairs(value.content, pairs)?,
+            _ => (),
+        }
+        rest = remaining;
+    }
+    if let [name, value] = &strings[..] {
+        pairs.push((name.clone(), value.clone()));
+    }
+    Ok(())
+}
+
+/// a blob which has been encrypted using DPAPI-NG, e.g. the value of
+/// `msLAPS-EncryptedPassword`
+#[derive(Getters)]
+#[getset(get = "pub")]
+pub struct DpapiNgBlob {
+    key_id: GroupKeyIdentifier,
+
+    /// the protection descriptor, e.g. `SID=S-1-5-21-...-512`
+    protection_descriptor: Vec<(String, String)>,
+
+    #[getset(skip)]
+    encrypted_key: Vec<u8>,
+
+    #[getset(skip)]
+    nonce: Vec<u8>,
+
+    #[getset(skip)]
+    encrypted_content: Vec<u8>,
+}
+
+impl TryFrom<&[u8]> for DpapiNgBlob {
+    type Error = Error;
+
+    fn try_from(data: &[u8]) -> Result<Self> {
+        // ContentInfo
+        let (content_info, detached_content) = expect_der(data, DER_SEQUENCE)?;
+        let (content_type, rest) = expect_der(content_info, DER_OID)?;
+        if content_type != OID_ENVELOPED_DATA {
+            return Err(Error::InvalidDpapiNgBlob("this is no enveloped data"));
+        }
+        let (enveloped_data, _) = expect_der(rest, DER_CONTEXT_0)?;
+        let (enveloped_data, _) = expect_der(enveloped_data, DER_SEQUENCE)?;
+        let (_version, rest) = expect_der(enveloped_data, DER_INTEGER)?;
+
+        // RecipientInfos, which must contain a KEKRecipientInfo
+        let (recipient_infos, rest) = expect_der(rest, DER_SET)?;
+        let (kek_recipient_info, _) = expect_der(recipient_infos, DER_KEK_RECIPIENT_INFO)?;
+        let (_version, kri) = expect_der(kek_recipient_info, DER_INTEGER)?;
+        let (kek_id, kri) = expect_der(kri, DER_SEQUENCE)?;
+        let (key_identifier, kek_id) = expect_der(kek_id, DER_OCTET_STRING)?;
+        let mut protection_descriptor = Vec::new();
+        collect_name_value_pairs(kek_id, &mut protection_descriptor)?;
+        let (_key_encryption_algorithm, kri) = expect_der(kri, DER_SEQUENCE)?;
+        let (encrypted_key, _) = expect_der(kri, DER_OCTET_STRING)?;
+
+        // EncryptedContentInfo
+        let (encrypted_content_info, _) = expect_der(rest, DER_SEQUENCE)?;
+        let (_content_type, eci) = expect_der(encrypted_content_info, DER_OID)?;
+        let (algorithm, eci) = expect_der(eci, DER_SEQUENCE)?;
+        let (algorithm_oid, parameters) = expect_der(algorithm, DER_OID)?;
+        if algorithm_oid != OID_AES256_GCM {
+            return Err(Error::InvalidDpapiNgBlob(
+                "unsupported content encryption algorithm",
+            ));
+        }
+        let (parameters, _) = expect_der(parameters, DER_SEQUENCE)?;
+        let (nonce, _) = expect_der(parameters, DER_OCTET_STRING)?;
+
+        // the encrypted content might be stored outside of the structure
+        let encrypted_content = if eci.is_empty() {
+            detached_content
+        } else {
+            expect_der(eci, DER_CONTEXT_0_PRIMITIVE)?.0
+        };
+
+        Ok(Self {
+            key_id: GroupKeyIdentifier::try_from(key_identifier)?,
+            protection_descriptor,
+            encrypted_key: encrypted_key.to_vec(),
+            nonce: nonce.to_vec(),
+            encrypted_content: encrypted_content.to_vec(),
+        })
+    }
+}
+
+impl DpapiNgBlob {
+    /// returns the SID from the protection descriptor, which identifies the
+    /// principal who is authorized to decrypt this blob
+    pub fn authorized_sid(&self) -> Option<Sid> {
+        self.protection_descriptor
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("SID"))
+            .and_then(|(_, sid)| Sid::from_str(sid).ok())
+    }
+
+    /// decrypts the content of this blob, using the KDS root key
+    /// identified by [`GroupKeyIdentifier::root_key_id`]
+    pub fn decrypt(&self, root_keys: &HashMap<Guid, KdsRootKey>) -> Result<Vec<u8>> {
+        let root_key = root_keys
+            .get(self.key_id.root_key_id())
+            .ok_or_else(|| Error::MissingRootKey(self.key_id.root_key_id().to_string()))?;
+
+        // only protection descriptors with one single SID are supported
+        let sid = match &self.protection_descriptor[..] {
+            [(name, _)] if name.eq_ignore_ascii_case("SID") => self.authorized_sid(),
+            _ => None,
+        }
+        .ok_or(Error::InvalidDpapiNgBlob(
+            "unsupported protection descriptor",
+        ))?;
+
+        let kek = root_key.key_encryption_key(&self.key_id, &target_security_descriptor(&sid))?;
+        let kek: [u8; 32] = kek.try_into().unwrap();
+        let cek = KekAes256::from(kek)
+            .unwrap_vec(&self.encrypted_key)
+            .map_err(|_| Error::DecryptionFailed("unable to unwrap the content encryption key"))?;
+
+        let cipher = Aes256Gcm::new_from_slice(&cek)
+            .map_err(|_| Error::DecryptionFailed("invalid content encryption key"))?;
+        if self.nonce.len() != 12 {
+            return Err(Error::InvalidDpapiNgBlob("invalid nonce"));
+        }
+        cipher
+            .decrypt(Nonce::from_slice(&self.nonce), &self.encrypted_content[..])
+            .map_err(|_| Error::DecryptionFailed("invalid authentication tag"))
+    }
+}
+
+/// creates the security descriptor which DPAPI-NG uses to derive the key for
+/// a SID protection descriptor: `O:SYG:SYD:(A;;CCDC;;;<sid>)(A;;DC;;;WD)`
+fn target_security_descriptor(sid: &Sid) -> Vec<u8> {
+    let system = Sid::new(1, 5, vec![18]).to_bytes();
+    let everyone = Sid::new(1, 1, vec![0]).to_bytes();
+
+    let mut aces = Vec::new();
+    for (sid, access_mask) in [(sid.to_bytes(), 3u32), (everyone, 2u32)] {
+        aces.extend_from_slice(&[0, 0]);
+        aces.extend_from_slice(&(8 + sid.len() as u16).to_le_bytes());
+        aces.extend_from_slice(&access_mask.to_le_bytes());
+        aces.extend_from_slice(&sid);
+    }
+
+    let owner_offset = 20u32;
+    let group_offset = owner_offset + system.len() as u32;
+    let dacl_offset = group_offset + system.len() as u32;
+
+    // self-relative, DACL present
+    let mut sd = vec![1, 0, 0x04, 0x80];
+    sd.extend_from_slice(&owner_offset.to_le_bytes());
+    sd.extend_from_slice(&group_offset.to_le_bytes());
+    sd.extend_from_slice(&0u32.to_le_bytes());
+    sd.extend_from_slice(&dacl_offset.to_le_bytes());
+    sd.extend_from_slice(&system);
+    sd.extend_from_slice(&system);
+    sd.extend_from_slice(&[2, 0]);
+    sd.extend_from_slice(&(8 + aces.len() as u16).to_le_bytes());
+    sd.extend_from_slice(&2u16.to_le_bytes());
+    sd.extend_from_slice(&[0, 0]);
+    sd.extend(aces);
+    sd
+}
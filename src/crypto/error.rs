@@ -0,0 +1,67 @@
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("IO Error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("invalid registry hive: {0}")]
+    InvalidHive(&'static str),
+
+    #[error("missing registry key or value: '{0}'")]
+    MissingRegistryEntry(String),
+
+    #[error("invalid boot key: {0}")]
+    InvalidBootKey(String),
+
+    #[error("unsupported PEK list version: {0}")]
+    UnsupportedPekListVersion(u32),
+
+    #[error("there is no PEK with index {0}")]
+    MissingPek(usize),
+
+    #[error("encrypted value is too short ({0} bytes)")]
+    EncryptedValueTooShort(usize),
+
+    #[error("invalid supplemental credentials: {0}")]
+    InvalidSupplementalCredentials(&'static str),
+
+    #[error("invalid password hash: '{0}'")]
+    InvalidPasswordHash(String),
+
+    #[error("invalid or unsupported KDF parameters: '{0}'")]
+    InvalidKdfParameters(String),
+
+    #[error("invalid group key identifier: {0}")]
+    InvalidGroupKeyIdentifier(&'static str),
+
+    #[error("invalid public key: {0}")]
+    InvalidPublicKey(&'static str),
+
+    #[error("unsupported secret agreement algorithm: '{0}'")]
+    UnsupportedSecretAgreement(String),
+
+    #[error("invalid DPAPI-NG blob: {0}")]
+    InvalidDpapiNgBlob(&'static str),
+
+    #[error("there is no KDS root key with id {0}")]
+    MissingRootKey(String),
+
+    #[error("decryption failed: {0}")]
+    DecryptionFailed(&'static str),
+
+    #[error("invalid LAPS password: {0}")]
+    InvalidLapsPassword(String),
+
+    #[error("invalid DPAPI backup key: {0}")]
+    InvalidBackupKey(&'static str),
+
+    #[error("invalid trust authentication information: {0}")]
+    InvalidTrustAuthInformation(&'static str),
+
+    #[error("invalid key credential: {0}")]
+    InvalidKeyCredential(&'static str),
+
+    #[error("invalid certificate: {0}")]
+    InvalidCertificate(&'static str),
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
@@ -0,0 +1,370 @@
+//! key derivation of the Group Key Distribution Service (MS-GKDI), which is
+//! used to calculate the passwords of group managed service accounts
+
+use byteorder::{ByteOrder, LittleEndian};
+use getset::{Getters, Setters};
+use hmac::{Hmac, Mac};
+use num_bigint::BigUint;
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use super::{utf16_to_string, Error, Result};
+use crate::win32_types::Guid;
+
+/// label which is used for all derivations of the key hierarchy
+/// (`"KDS service"` as null-terminated UTF-16LE string)
+const KDS_SERVICE_LABEL: &[u8] = b"K\0D\0S\0 \0s\0e\0r\0v\0i\0c\0e\0\0\0";
+
+/// context which is used to derive key encryption keys from public keys
+/// (`"KDS public key"` as null-terminated UTF-16LE string)
+const KDS_PUBLIC_KEY_LABEL: &[u8] = b"K\0D\0S\0 \0p\0u\0b\0l\0i\0c\0 \0k\0e\0y\0\0\0";
+
+/// `"SHA512"` as null-terminated UTF-16LE string
+const SHA512_NAME: &[u8] = b"S\0H\0A\x005\x001\x002\0\0\0";
+
+/// magic number of a `BCRYPT_DH_KEY_BLOB` with a public key (`"DHPB"`)
+const DH_PUBLIC_KEY_MAGIC: u32 = 0x42504844;
+
+/// size of the L0, L1 and L2 seed keys
+const SEED_KEY_SIZE: usize = 64;
+
+/// magic number of a group key identifier (`"KDSK"`)
+const KEY_IDENTIFIER_MAGIC: u32 = 0x4b53444b;
+const KEY_IDENTIFIER_HEADER_SIZE: usize = 52;
+
+/// the highest index of L1 and L2 keys
+const MAX_KEY_INDEX: i32 = 31;
+
+/// security descriptor which is used to derive the L1 keys for group
+/// managed service accounts: `O:BAD:(A;;0x12019f;;;WD)`
+#[rustfmt::skip]
+const GMSA_SECURITY_DESCRIPTOR: [u8; 64] = [
+    0x01, 0x00, 0x04, 0x80, 0x30, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x14, 0x00, 0x00, 0x00, 0x02, 0x00, 0x1c, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x14, 0x00,
+    0x9f, 0x01, 0x12, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, 0x09, 0x00, 0x00, 0x00,
+    0x01, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, 0x20, 0x00, 0x00, 0x00, 0x20, 0x02, 0x00, 0x00,
+];
+
+/// hash algorithm of the SP800-108 key derivation function (in counter mode)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KdfHash {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl KdfHash {
+    /// reads the hash algorithm from the value of `msKds-KDFParam`. If
+    /// there is no value, Windows uses SHA512.
+    pub fn from_kdf_param(param: Option<&[u8]>) -> Result<Self> {
+        let param = match param {
+            None => return Ok(Self::Sha512),
+            Some(param) => param,
+        };
+        if param.len() < 16 {
+            return Err(Error::InvalidKdfParameters(hex::encode(param)));
+        }
+        let name_length = LittleEndian::read_u32(&param[8..12]) as usize;
+        let name = param
+            .get(16..16 + name_length)
+            .map(utf16_to_string)
+            .ok_or_else(|| Error::InvalidKdfParameters(hex::encode(param)))?;
+        match name.trim_end_matches('\0') {
+            "SHA1" => Ok(Self::Sha1),
+            "SHA256" => Ok(Self::Sha256),
+            "SHA384" => Ok(Self::Sha384),
+            "SHA512" => Ok(Self::Sha512),
+            name => Err(Error::InvalidKdfParameters(name.to_string())),
+        }
+    }
+
+    /// derives `length` bytes from `key`, using the SP800-108 key derivation
+    /// function in counter mode
+    pub fn derive(&self, key: &[u8], label: &[u8], context: &[u8], length: usize) -> Vec<u8> {
+        let length_in_bits = ((length * 8) as u32).to_be_bytes();
+        let mut result = Vec::with_capacity(length);
+        let mut counter = 1u32;
+        while result.len() < length {
+            let parts: [&[u8]; 5] = [
+                &counter.to_be_bytes(),
+                label,
+                &[0],
+                context,
+                &length_in_bits,
+            ];
+            result.extend(match self {
+                Self::Sha1 => hmac::<Hmac<Sha1>>(key, &parts),
+                Self::Sha256 => hmac::<Hmac<Sha256>>(key, &parts),
+                Self::Sha384 => hmac::<Hmac<Sha384>>(key, &parts),
+                Self::Sha512 => hmac::<Hmac<Sha512>>(key, &parts),
+            });
+            counter += 1;
+        }
+        result.truncate(length);
+        result
+    }
+}
+
+fn hmac<M: Mac + hmac::digest::KeyInit>(key: &[u8], parts: &[&[u8]]) -> Vec<u8> {
+    let mut mac = <M as Mac>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// a root key of the Group Key Distribution Service (`msKds-ProvRootKey`)
+#[derive(Getters, Setters)]
+#[getset(get = "pub")]
+pub struct KdsRootKey {
+    id: Guid,
+    kdf_hash: KdfHash,
+
+    #[getset(skip)]
+    key_data: Vec<u8>,
+
+    /// the secret agreement algorithm (`msKds-SecretAgreementAlgorithmID`),
+    /// which is used for public keys
+    #[getset(set = "pub")]
+    secret_agreement_algorithm: String,
+
+    /// the length of the private keys in bits (`msKds-PrivateKeyLength`)
+    #[getset(set = "pub")]
+    private_key_length: u32,
+}
+
+impl KdsRootKey {
+    pub fn new(id: Guid, kdf_hash: KdfHash, key_data: Vec<u8>) -> Self {
+        Self {
+            id,
+            kdf_hash,
+            key_data,
+            secret_agreement_algorithm: "DH".to_string(),
+            private_key_length: 512,
+        }
+    }
+
+    fn context(&self, l0: i32, l1: i32, l2: i32) -> Vec<u8> {
+        let mut context = self.id.to_bytes_le().to_vec();
+        for index in [l0, l1, l2] {
+            context.extend_from_slice(&index.to_le_bytes());
+        }
+        context
+    }
+
+    fn derive_seed_key(&self, key: &[u8], context: &[u8]) -> Vec<u8> {
+        self.kdf_hash
+            .derive(key, KDS_SERVICE_LABEL, context, SEED_KEY_SIZE)
+    }
+
+    /// calculates the L2 seed key which belongs to `key_id` and to the
+    /// (binary) security descriptor `security_descriptor`
+    fn l2_key(&self, key_id: &GroupKeyIdentifier, security_descriptor: &[u8]) -> Vec<u8> {
+        let (l0, l1, l2) = (key_id.l0_index, key_id.l1_index, key_id.l2_index);
+
+        let l0_key = self.derive_seed_key(&self.key_data, &self.context(l0, -1, -1));
+
+        let mut context = self.context(l0, MAX_KEY_INDEX, -1);
+        context.extend_from_slice(security_descriptor);
+        let mut l1_key = self.derive_seed_key(&l0_key, &context);
+        for index in (l1..MAX_KEY_INDEX).rev() {
+            l1_key = self.derive_seed_key(&l1_key, &self.context(l0, index, -1));
+        }
+
+        let mut l2_key = self.derive_seed_key(&l1_key, &self.context(l0, l1, MAX_KEY_INDEX));
+        for index in (l2..MAX_KEY_INDEX).rev() {
+            l2_key = self.derive_seed_key(&l2_key, &self.context(l0, l1, index));
+        }
+        l2_key
+    }
+
+    /// derives `length` bytes for the group managed service account with
+    /// the binary SID `sid` from the L2 seed key which belongs to `key_id`
+    pub fn derive_gmsa_key(
+        &self,
+        key_id: &GroupKeyIdentifier,
+        label: &[u8],
+        sid: &[u8],
+        length: usize,
+    ) -> Vec<u8> {
+        self.kdf_hash.derive(
+            &self.l2_key(key_id, &GMSA_SECURITY_DESCRIPTOR),
+            label,
+            sid,
+            length,
+        )
+    }
+
+    /// calculates the key encryption key of a DPAPI-NG blob, which has been
+    /// protected for the principals in `security_descriptor`
+    pub fn key_encryption_key(
+        &self,
+        key_id: &GroupKeyIdentifier,
+        security_descriptor: &[u8],
+    ) -> Result<Vec<u8>> {
+        let l2_key = self.l2_key(key_id, security_descriptor);
+        if !key_id.is_public_key() {
+            return Ok(self
+                .kdf_hash
+                .derive(&l2_key, KDS_SERVICE_LABEL, &key_id.key_info, 32));
+        }
+
+        // the blob has been encrypted using a public key, so we need to
+        // calculate the private key and the shared secret
+        let algorithm = format!("{}\0", self.secret_agreement_algorithm);
+        let algorithm: Vec<u8> = algorithm
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let private_key = self.kdf_hash.derive(
+            &l2_key,
+            KDS_SERVICE_LABEL,
+            &algorithm,
+            (self.private_key_length as usize).div_ceil(8),
+        );
+        let shared_secret = match &self.secret_agreement_algorithm[..] {
+            "DH" => dh_shared_secret(&key_id.key_info, &private_key)?,
+            algorithm => return Err(Error::UnsupportedSecretAgreement(algorithm.to_string())),
+        };
+
+        // SP800-56A concatenation KDF, with SHA256 and one single iteration
+        let mut hasher = Sha256::new();
+        hasher.update(1u32.to_be_bytes());
+        hasher.update(&shared_secret);
+        hasher.update(SHA512_NAME);
+        hasher.update(KDS_PUBLIC_KEY_LABEL);
+        hasher.update(KDS_SERVICE_LABEL);
+        let secret = hasher.finalize();
+
+        Ok(self
+            .kdf_hash
+            .derive(&secret, KDS_SERVICE_LABEL, KDS_PUBLIC_KEY_LABEL, 32))
+    }
+}
+
+/// calculates the Diffie-Hellman shared secret from a public key (a
+/// `BCRYPT_DH_KEY_BLOB`) and the private key
+fn dh_shared_secret(public_key: &[u8], private_key: &[u8]) -> Result<Vec<u8>> {
+    if public_key.len() < 8 || LittleEndian::read_u32(&public_key[0..4]) != DH_PUBLIC_KEY_MAGIC {
+        return Err(Error::InvalidPublicKey("invalid magic number"));
+    }
+    let key_length = LittleEndian::read_u32(&public_key[4..8]) as usize;
+    let values = public_key
+        .get(8..8 + 3 * key_length)
+        .ok_or(Error::InvalidPublicKey("value is too short"))?;
+
+    let field_order = BigUint::from_bytes_be(&values[..key_length]);
+    let public_key = BigUint::from_bytes_be(&values[2 * key_length..]);
+    let private_key = BigUint::from_bytes_be(private_key);
+    let shared_secret = public_key.modpow(&private_key, &field_order).to_bytes_be();
+
+    // the shared secret has the same size as the field order
+    let mut result = vec![0; key_length.saturating_sub(shared_secret.len())];
+    result.extend(shared_secret);
+    Ok(result)
+}
+
+/// identifies the root key and the L0, L1 and L2 indices of a group key
+/// (the value of `msDS-ManagedPasswordId`)
+#[derive(Getters)]
+#[getset(get = "pub")]
+pub struct GroupKeyIdentifier {
+    flags: u32,
+    l0_index: i32,
+    l1_index: i32,
+    l2_index: i32,
+    root_key_id: Guid,
+    domain_name: String,
+    forest_name: String,
+
+    /// the public key (if [`Self::is_public_key`] is `true`), or key
+    /// material which is used to derive the key encryption key
+    #[getset(skip)]
+    key_info: Vec<u8>,
+}
+
+impl GroupKeyIdentifier {
+    pub fn is_public_key(&self) -> bool {
+        self.flags & 1 != 0
+    }
+}
+
+impl TryFrom<&[u8]> for GroupKeyIdentifier {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self> {
+        if value.len() < KEY_IDENTIFIER_HEADER_SIZE {
+            return Err(Error::InvalidGroupKeyIdentifier("value is too short"));
+        }
+        if LittleEndian::read_u32(&value[4..8]) != KEY_IDENTIFIER_MAGIC {
+            return Err(Error::InvalidGroupKeyIdentifier("invalid magic number"));
+        }
+        let key_info_length = LittleEndian::read_u32(&value[40..44]) as usize;
+        let domain_length = LittleEndian::read_u32(&value[44..48]) as usize;
+        let forest_length = LittleEndian::read_u32(&value[48..52]) as usize;
+
+        let domain_start = KEY_IDENTIFIER_HEADER_SIZE + key_info_length;
+        let forest_start = domain_start + domain_length;
+        let name = |start: usize, length: usize| {
+            value
+                .get(start..start + length)
+                .map(|name| utf16_to_string(name).trim_end_matches('\0').to_string())
+                .ok_or(Error::InvalidGroupKeyIdentifier("value is too short"))
+        };
+
+        Ok(Self {
+            flags: LittleEndian::read_u32(&value[8..12]),
+            l0_index: LittleEndian::read_i32(&value[12..16]),
+            l1_index: LittleEndian::read_i32(&value[16..20]),
+            l2_index: LittleEndian::read_i32(&value[20..24]),
+            root_key_id: Guid::from_bytes_le(value[24..40].try_into().unwrap()),
+            domain_name: name(domain_start, domain_length)?,
+            forest_name: name(forest_start, forest_length)?,
+            key_info: value[KEY_IDENTIFIER_HEADER_SIZE..domain_start].to_vec(),
+        })
+    }
+}
+
+impl std::fmt::Display for GroupKeyIdentifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.l0_index, self.l1_index, self.l2_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GroupKeyIdentifier, KdfHash};
+
+    #[test]
+    fn test_key_identifier() {
+        let key_id = hex::decode(concat!(
+            "010000004b44534b02000000690100001a0000001300000095a3bbd09c7e9c04",
+            "8f2a3c1c5b7ad6df00000000180000001800000063006f006e0074006f007300",
+            "6f002e0063006f006d00000063006f006e0074006f0073006f002e0063006f00",
+            "6d000000"
+        ))
+        .unwrap();
+        let key_id = GroupKeyIdentifier::try_from(&key_id[..]).unwrap();
+        assert_eq!(*key_id.l0_index(), 361);
+        assert_eq!(*key_id.l1_index(), 26);
+        assert_eq!(*key_id.l2_index(), 19);
+        assert_eq!(
+            key_id.root_key_id().to_string(),
+            "d0bba395-7e9c-049c-8f2a-3c1c5b7ad6df"
+        );
+        assert_eq!(key_id.domain_name(), "contoso.com");
+        assert_eq!(key_id.forest_name(), "contoso.com");
+    }
+
+    #[test]
+    fn test_kdf_param() {
+        let param =
+            hex::decode("00000000010000000e000000000000005300480041003500310032000000").unwrap();
+        assert_eq!(
+            KdfHash::from_kdf_param(Some(&param)).unwrap(),
+            KdfHash::Sha512
+        );
+        assert_eq!(KdfHash::from_kdf_param(None).unwrap(), KdfHash::Sha512);
+    }
+}
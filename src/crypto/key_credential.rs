@@ -0,0 +1,251 @@
+use std::fmt::Display;
+
+use base64::prelude::*;
+use byteorder::{ByteOrder, LittleEndian};
+use getset::Getters;
+
+use crate::win32_types::Guid;
+
+use super::{Error, Result};
+
+const KEY_CREDENTIAL_LINK_VERSION_2: u32 = 0x200;
+const ENTRY_HEADER_SIZE: usize = 3;
+
+const KEY_ID: u8 = 0x01;
+const KEY_HASH: u8 = 0x02;
+const KEY_MATERIAL: u8 = 0x03;
+const KEY_USAGE: u8 = 0x04;
+const KEY_SOURCE: u8 = 0x05;
+const DEVICE_ID: u8 = 0x06;
+const KEY_APPROXIMATE_LAST_LOGON_TIME_STAMP: u8 = 0x08;
+const KEY_CREATION_TIME: u8 = 0x09;
+
+/// `BCRYPT_RSAPUBLIC_MAGIC`
+const RSA_PUBLIC_KEY_MAGIC: &[u8] = b"RSA1";
+
+/// one value of `msDS-KeyCredentialLink` (`KEYCREDENTIALLINK_BLOB`, see
+/// MS-ADTS 2.2.20), which contains a public key that can be used for
+/// Windows Hello for Business or PKINIT
+#[derive(Getters, Default)]
+#[getset(get = "pub")]
+pub struct KeyCredential {
+    /// SHA256 hash of the key material
+    key_id: Option<Vec<u8>>,
+
+    /// SHA256 hash of all entries which follow this entry
+    key_hash: Option<Vec<u8>>,
+
+    key_material: Option<Vec<u8>>,
+    key_usage: Option<KeyUsage>,
+    key_source: Option<KeySource>,
+    device_id: Option<Guid>,
+
+    /// approximate time of the last logon, as `FILETIME`
+    last_logon_time: Option<u64>,
+
+    /// creation time of the key, as `FILETIME`
+    creation_time: Option<u64>,
+}
+
+impl TryFrom<&[u8]> for KeyCredential {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self> {
+        if value.len() < 4 {
+            return Err(Error::InvalidKeyCredential("value is too short"));
+        }
+        if LittleEndian::read_u32(&value[0..4]) != KEY_CREDENTIAL_LINK_VERSION_2 {
+            return Err(Error::InvalidKeyCredential("unsupported version"));
+        }
+
+        let mut credential = Self::default();
+        let mut rest = &value[4..];
+        while !rest.is_empty() {
+            if rest.len() < ENTRY_HEADER_SIZE {
+                return Err(Error::InvalidKeyCredential("entry is too short"));
+            }
+            let length = LittleEndian::read_u16(&rest[0..2]) as usize;
+            let identifier = rest[2];
+            let entry = rest
+                .get(ENTRY_HEADER_SIZE..ENTRY_HEADER_SIZE + length)
+                .ok_or(Error::InvalidKeyCredential("entry is too short"))?;
+            rest = &rest[ENTRY_HEADER_SIZE + length..];
+
+            match identifier {
+                KEY_ID => credential.key_id = Some(entry.to_vec()),
+                KEY_HASH => credential.key_hash = Some(entry.to_vec()),
+                KEY_MATERIAL => credential.key_material = Some(entry.to_vec()),
+                KEY_USAGE => credential.key_usage = entry.first().map(|b| KeyUsage::from(*b)),
+                KEY_SOURCE => credential.key_source = entry.first().map(|b| KeySource::from(*b)),
+                DEVICE_ID => {
+                    credential.device_id = entry
+                        .try_into()
+                        .ok()
+                        .map(|bytes: [u8; 16]| Guid::from_bytes_le(bytes))
+                }
+                KEY_APPROXIMATE_LAST_LOGON_TIME_STAMP => {
+                    credential.last_logon_time = read_filetime(entry)
+                }
+                KEY_CREATION_TIME => credential.creation_time = read_filetime(entry),
+                _ => (),
+            }
+        }
+        Ok(credential)
+    }
+}
+
+impl KeyCredential {
+    /// returns the key id in the same encoding which is used by other tools
+    /// (such as DSInternals or Whisker)
+    pub fn key_id_string(&self) -> Option<String> {
+        self.key_id.as_ref().map(|id| BASE64_STANDARD.encode(id))
+    }
+
+    /// describes the type and size of the public key, if it is known
+    pub fn key_type(&self) -> Option<String> {
+        let material = self.key_material.as_ref()?;
+        if material.len() >= 8 && &material[0..4] == RSA_PUBLIC_KEY_MAGIC {
+            Some(format!(
+                "RSA {} bit",
+                LittleEndian::read_u32(&material[4..8])
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+fn read_filetime(entry: &[u8]) -> Option<u64> {
+    (entry.len() == 8).then(|| LittleEndian::read_u64(entry))
+}
+
+/// the purpose of a key credential
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum KeyUsage {
+    AdminKey,
+    /// Windows Hello for Business (next generation credentials)
+    Ngc,
+    /// transport key of a device
+    Stk,
+    BitlockerRecovery,
+    Other,
+    Fido,
+    /// file encryption key
+    Fek,
+    Dpapi,
+    Unknown(u8),
+}
+
+impl From<u8> for KeyUsage {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::AdminKey,
+            1 => Self::Ngc,
+            2 => Self::Stk,
+            3 => Self::BitlockerRecovery,
+            4 => Self::Other,
+            7 => Self::Fido,
+            8 => Self::Fek,
+            9 => Self::Dpapi,
+            value => Self::Unknown(value),
+        }
+    }
+}
+
+impl Display for KeyUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AdminKey => write!(f, "AdminKey"),
+            Self::Ngc => write!(f, "NGC"),
+            Self::Stk => write!(f, "STK"),
+            Self::BitlockerRecovery => write!(f, "BitlockerRecovery"),
+            Self::Other => write!(f, "Other"),
+            Self::Fido => write!(f, "FIDO"),
+            Self::Fek => write!(f, "FEK"),
+            Self::Dpapi => write!(f, "DPAPI"),
+            Self::Unknown(value) => write!(f, "unknown ({value})"),
+        }
+    }
+}
+
+/// the directory where a key credential has been created
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum KeySource {
+    ActiveDirectory,
+    AzureActiveDirectory,
+    Unknown(u8),
+}
+
+impl From<u8> for KeySource {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::ActiveDirectory,
+            1 => Self::AzureActiveDirectory,
+            value => Self::Unknown(value),
+        }
+    }
+}
+
+impl Display for KeySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ActiveDirectory => write!(f, "AD"),
+            Self::AzureActiveDirectory => write!(f, "AzureAD"),
+            Self::Unknown(value) => write!(f, "unknown ({value})"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeyCredential, KeySource, KeyUsage};
+
+    #[test]
+    fn test_key_credential() {
+        let value = hex::decode(concat!(
+            "00020000",
+            // key id
+            "0400",
+            "01",
+            "01020304",
+            // key material: RSA public key blob
+            "0c00",
+            "03",
+            "525341310008000003000000",
+            // key usage: NGC
+            "0100",
+            "04",
+            "01",
+            // key source: AD
+            "0100",
+            "05",
+            "00",
+            // device id
+            "1000",
+            "06",
+            "33221100554477668899aabbccddeeff",
+            // key creation time
+            "0800",
+            "09",
+            "0080d3e3a1d5d901",
+            // unknown entry, which must be ignored
+            "0200",
+            "0a",
+            "ffff",
+        ))
+        .unwrap();
+        let credential = KeyCredential::try_from(&value[..]).unwrap();
+        assert_eq!(credential.key_id_string().unwrap(), "AQIDBA==");
+        assert_eq!(credential.key_type().unwrap(), "RSA 2048 bit");
+        assert!(credential.key_usage() == &Some(KeyUsage::Ngc));
+        assert!(credential.key_source() == &Some(KeySource::ActiveDirectory));
+        assert_eq!(
+            credential.device_id().as_ref().unwrap().to_string(),
+            "00112233-4455-6677-8899-aabbccddeeff"
+        );
+        assert_eq!(credential.creation_time(), &Some(0x01d9d5a1e3d38000));
+        assert!(credential.last_logon_time().is_none());
+
+        assert!(KeyCredential::try_from(&value[..6]).is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use byteorder::{ByteOrder, LittleEndian};
+use getset::Getters;
+use serde::Deserialize;
+
+use super::{utf16_to_string, DpapiNgBlob, Error, KdsRootKey, Result};
+use crate::win32_types::{Guid, WindowsFileTime};
+
+const ENCRYPTED_PASSWORD_HEADER_SIZE: usize = 16;
+
+/// a password which has been stored by Windows LAPS, either in
+/// `msLAPS-Password` or (encrypted) in `msLAPS-EncryptedPassword` or
+/// `msLAPS-EncryptedDSRMPassword`
+#[derive(Getters, Deserialize)]
+#[getset(get = "pub")]
+pub struct LapsPassword {
+    /// name of the managed account
+    #[serde(rename = "n", default)]
+    account_name: Option<String>,
+
+    /// time of the last password update, as hexadecimal `FILETIME`
+    #[serde(rename = "t")]
+    update_time: String,
+
+    #[serde(rename = "p")]
+    password: String,
+}
+
+impl LapsPassword {
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json.trim_end_matches('\0'))
+            .map_err(|why| Error::InvalidLapsPassword(why.to_string()))
+    }
+
+    pub fn update_timestamp(&self) -> Option<WindowsFileTime> {
+        u64::from_str_radix(&self.update_time, 16)
+            .ok()
+            .map(WindowsFileTime::from)
+    }
+}
+
+/// the value of `msLAPS-EncryptedPassword` or `msLAPS-EncryptedDSRMPassword`
+#[derive(Getters)]
+#[getset(get = "pub")]
+pub struct EncryptedLapsPassword {
+    /// time of the last password update, as `FILETIME`
+    update_time: u64,
+    blob: DpapiNgBlob,
+}
+
+impl TryFrom<&[u8]> for EncryptedLapsPassword {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self> {
+        if value.len() < ENCRYPTED_PASSWORD_HEADER_SIZE {
+            return Err(Error::InvalidDpapiNgBlob("value is too short"));
+        }
+        let upper = LittleEndian::read_u32(&value[0..4]) as u64;
+        let lower = LittleEndian::read_u32(&value[4..8]) as u64;
+        let size = LittleEndian::read_u32(&value[8..12]) as usize;
+        let blob = value
+            .get(ENCRYPTED_PASSWORD_HEADER_SIZE..ENCRYPTED_PASSWORD_HEADER_SIZE + size)
+            .ok_or(Error::InvalidDpapiNgBlob("value is too short"))?;
+        Ok(Self {
+            update_time: (upper << 32) | lower,
+            blob: DpapiNgBlob::try_from(blob)?,
+        })
+    }
+}
+
+impl EncryptedLapsPassword {
+    pub fn update_timestamp(&self) -> WindowsFileTime {
+        WindowsFileTime::from(self.update_time)
+    }
+
+    pub fn decrypt(&self, root_keys: &HashMap<Guid, KdsRootKey>) -> Result<LapsPassword> {
+        let plaintext = self.blob.decrypt(root_keys)?;
+        LapsPassword::from_json(&utf16_to_string(&plaintext))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    use super::EncryptedLapsPassword;
+    use crate::crypto::{KdfHash, KdsRootKey};
+    use crate::win32_types::Guid;
+
+    /// a blob which has been created with a root key consisting of the
+    /// bytes 0 to 63, using the same key derivation as [`KdsRootKey`].
+    /// Because of this, it only detects regressions, but not deviations from
+    /// the key derivation of Windows; that still requires a known-answer
+    /// vector which has been taken from a real `msLAPS-EncryptedPassword`.
+    #[test]
+    fn test_decrypt_laps_password_roundtrip() {
+        let blob = hex::decode(concat!(
+            "a1f0d901e5d4c3b2d7010000000000003082014706092a864886f70d010703a08201383082013402",
+            "010231820100a281fd0201043081c0048184010000004b44534b02000000690100001a0000001300",
+            "000095a3bbd09c7e9c048f2a3c1c5b7ad6df200000001800000018000000a99fc4f0119f7fb40a8b",
+            "417f70566dd4e5f319db39967b3351c73bdbdb23344c63006f006e0074006f0073006f002e006300",
+            "6f006d00000063006f006e0074006f0073006f002e0063006f006d000000303706092b0601040182",
+            "374a01302a06092b0601040182374a01301d301b30190c035349440c12532d312d352d32312d312d",
+            "322d332d353132300b0609608648016503040105042850e906aa6cd3a1b3acd7e13f4ea9556cd401",
+            "b1357a1dcc5fe5290b8156328b3cd4db4efeb8aa2a21302b06092a864886f70d010701301e060960",
+            "864801650304012e3011040caea671fe2564d854e163ee090201105d85d7a70aa7d98f95f95da84f",
+            "69a16578cd55c81b8f304ca38e27c543f3019efd946eeda068f3bf150f232628b883341349f29a3b",
+            "bd284e8ae009429c2c80bb400043f4ef3a04d3c21ddd7b148b83d9b8573f170d1d667b0baabcae23",
+            "52701683ff1c8ed00bd8e512e247ce8a6f94a7848f41c032a5eba189508bdba844d402af6d990a3d",
+            "f0b3dc026d0fb7",
+        ))
+        .unwrap();
+        let encrypted = EncryptedLapsPassword::try_from(&blob[..]).unwrap();
+        assert_eq!(
+            encrypted.blob().authorized_sid().unwrap().to_string(),
+            "S-1-5-21-1-2-3-512"
+        );
+
+        let id = Guid::from_str("d0bba395-7e9c-049c-8f2a-3c1c5b7ad6df").unwrap();
+        let mut root_keys = HashMap::new();
+        root_keys.insert(
+            id.clone(),
+            KdsRootKey::new(id, KdfHash::Sha512, (0..64).collect()),
+        );
+        let password = encrypted.decrypt(&root_keys).unwrap();
+        assert_eq!(password.password(), "S3cr3t!Pass");
+        assert_eq!(password.account_name().as_deref(), Some("Administrator"));
+    }
+}
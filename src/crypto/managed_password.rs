@@ -0,0 +1,119 @@
+use aes::cipher::{BlockEncrypt, KeyInit};
+use sha1::Sha1;
+
+use super::{md4, utf16_to_string, GroupKeyIdentifier, KdsRootKey, PasswordHash};
+
+/// label which is used to derive the password of a group managed service
+/// account (`"GMSA PASSWORD"` as null-terminated UTF-16LE string)
+const GMSA_PASSWORD_LABEL: &[u8] = b"G\0M\0S\0A\0 \0P\0A\0S\0S\0W\0O\0R\0D\0\0\0";
+const GMSA_PASSWORD_SIZE: usize = 256;
+
+/// iteration count which is used by Windows to derive the AES keys
+pub(crate) const KERBEROS_ITERATION_COUNT: u32 = 4096;
+
+/// the constant `"kerberos"`, n-folded to 128 bits (see RFC 3961)
+const KERBEROS_CONSTANT: [u8; 16] = [
+    0x6b, 0x65, 0x72, 0x62, 0x65, 0x72, 0x6f, 0x73, 0x7b, 0x9b, 0x5b, 0x2b, 0x93, 0x13, 0x2b, 0x93,
+];
+
+/// the password of a group managed service account, which is derived from a
+/// KDS root key and the SID of the account
+pub struct ManagedPassword(Vec<u8>);
+
+impl ManagedPassword {
+    /// derives the password of the account with the binary SID `sid`
+    pub fn derive(root_key: &KdsRootKey, key_id: &GroupKeyIdentifier, sid: &[u8]) -> Self {
+        Self(root_key.derive_gmsa_key(key_id, GMSA_PASSWORD_LABEL, sid, GMSA_PASSWORD_SIZE))
+    }
+
+    pub fn nt_hash(&self) -> PasswordHash {
+        md4(&self.0).into()
+    }
+
+    /// calculates the AES128 key (`aes128-cts-hmac-sha1-96`) with the given salt
+    pub fn aes128_key(&self, salt: &str) -> Vec<u8> {
+        kerberos_aes_key(
+            &self.utf8_password(),
+            salt.as_bytes(),
+            KERBEROS_ITERATION_COUNT,
+            16,
+        )
+    }
+
+    /// calculates the AES256 key (`aes256-cts-hmac-sha1-96`) with the given salt
+    pub fn aes256_key(&self, salt: &str) -> Vec<u8> {
+        kerberos_aes_key(
+            &self.utf8_password(),
+            salt.as_bytes(),
+            KERBEROS_ITERATION_COUNT,
+            32,
+        )
+    }
+
+    /// the password is random binary data, which Windows interprets as
+    /// UTF-16 and converts to UTF-8 (replacing invalid characters) before
+    /// deriving the Kerberos keys
+    fn utf8_password(&self) -> Vec<u8> {
+        utf16_to_string(&self.0).into_bytes()
+    }
+}
+
+/// returns the salt which is used for the Kerberos keys of machine accounts,
+/// including group managed service accounts
+pub fn machine_account_salt(realm: &str, sam_account_name: &str) -> String {
+    let host = sam_account_name
+        .strip_suffix('$')
+        .unwrap_or(sam_account_name)
+        .to_lowercase();
+    format!(
+        "{}host{host}.{}",
+        realm.to_uppercase(),
+        realm.to_lowercase()
+    )
+}
+
+/// derives an AES key from a password, as specified in RFC 3962
+pub(crate) fn kerberos_aes_key(
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+    key_size: usize,
+) -> Vec<u8> {
+    let mut key = vec![0; key_size];
+    pbkdf2::pbkdf2_hmac::<Sha1>(password, salt, iterations, &mut key);
+
+    let mut result = Vec::with_capacity(key_size);
+    let mut block = aes::Block::from(KERBEROS_CONSTANT);
+    while result.len() < key_size {
+        match key_size {
+            16 => aes::Aes128::new_from_slice(&key)
+                .unwrap()
+                .encrypt_block(&mut block),
+            _ => aes::Aes256::new_from_slice(&key)
+                .unwrap()
+                .encrypt_block(&mut block),
+        }
+        result.extend_from_slice(&block);
+    }
+    result.truncate(key_size);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::kerberos_aes_key;
+
+    /// test vectors from RFC 3962, appendix B
+    #[test]
+    fn test_kerberos_aes_key() {
+        let salt = b"ATHENA.MIT.EDUraeburn";
+        assert_eq!(
+            hex::encode(kerberos_aes_key(b"password", salt, 1200, 16)),
+            "4c01cd46d632d01e6dbe230a01ed642a"
+        );
+        assert_eq!(
+            hex::encode(kerberos_aes_key(b"password", salt, 1200, 32)),
+            "55a6ac740ad17b4846941051e1e8b0a7548d93b0ab30a8bc3ff16280382b8c2a"
+        );
+    }
+}
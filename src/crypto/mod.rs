@@ -0,0 +1,36 @@
+//! decryption of secrets which are stored in the `ntds.dit` file
+
+mod backup_key;
+mod boot_key;
+mod certificate;
+mod dpapi_ng;
+mod error;
+mod gkdi;
+mod key_credential;
+mod laps_password;
+mod managed_password;
+mod password_hash;
+mod pek_list;
+mod primitives;
+mod rid_encryption;
+mod supplemental_credentials;
+mod system_hive;
+mod trust_auth;
+
+pub use backup_key::*;
+pub use boot_key::*;
+pub use certificate::*;
+pub use dpapi_ng::*;
+pub use error::*;
+pub use gkdi::*;
+pub use key_credential::*;
+pub use laps_password::*;
+pub use managed_password::*;
+pub use password_hash::*;
+pub use pek_list::*;
+pub use rid_encryption::*;
+pub use supplemental_credentials::*;
+pub use system_hive::*;
+pub use trust_auth::*;
+
+pub(crate) use primitives::*;
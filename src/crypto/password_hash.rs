@@ -0,0 +1,84 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use super::{md4, Error};
+use crate::redact_secret;
+
+/// a NT or LM password hash
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct PasswordHash([u8; 16]);
+
+impl PasswordHash {
+    /// LM hash of an empty password, which is also used if no LM hash is stored
+    pub const EMPTY_LM: Self = Self([
+        0xaa, 0xd3, 0xb4, 0x35, 0xb5, 0x14, 0x04, 0xee, 0xaa, 0xd3, 0xb4, 0x35, 0xb5, 0x14, 0x04,
+        0xee,
+    ]);
+
+    /// NT hash of an empty password
+    pub const EMPTY_NT: Self = Self([
+        0x31, 0xd6, 0xcf, 0xe0, 0xd1, 0x6a, 0xe9, 0x31, 0xb7, 0x3c, 0x59, 0xd7, 0xe0, 0xc0, 0x89,
+        0xc0,
+    ]);
+
+    /// calculates the NT hash of a password, which is the MD4 hash of its UTF-16LE representation
+    pub fn nt_hash_of(password: &str) -> Self {
+        let data: Vec<u8> = password.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        Self(md4(&data))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+}
+
+impl FromStr for PasswordHash {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        hex::decode(s.trim())
+            .ok()
+            .and_then(|hash| hash.try_into().ok())
+            .map(Self)
+            .ok_or_else(|| Error::InvalidPasswordHash(s.to_string()))
+    }
+}
+
+impl From<[u8; 16]> for PasswordHash {
+    fn from(value: [u8; 16]) -> Self {
+        Self(value)
+    }
+}
+
+impl Display for PasswordHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+impl Serialize for PasswordHash {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&redact_secret(&self.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::PasswordHash;
+
+    #[test]
+    fn test_nt_hash() {
+        assert_eq!(PasswordHash::nt_hash_of(""), PasswordHash::EMPTY_NT);
+        assert_eq!(
+            PasswordHash::nt_hash_of("password"),
+            PasswordHash::from_str("8846f7eaee8fb117ad06bdd830b7586c").unwrap()
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use std::fmt::Display;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use super::{
+    aes_cbc_decrypt, md5, rc4_decrypt, remove_rid_encryption, BootKey, Error, PasswordHash, Result,
+    SupplementalCredentials,
+};
+
+/// PEK lists up to Windows Server 2012 R2 are encrypted using RC4
+const PEK_LIST_VERSION_RC4: u32 = 2;
+
+/// PEK lists since Windows Server 2016 are encrypted using AES
+const PEK_LIST_VERSION_AES: u32 = 3;
+
+const HEADER_SIZE: usize = 8;
+const KEY_MATERIAL_SIZE: usize = 16;
+const PLAIN_HEADER_SIZE: usize = 32;
+const PEK_ENTRY_SIZE: usize = 20;
+
+/// secret attributes which are encrypted using AES (instead of RC4) have this
+/// value in the first four bytes of their header
+const SECRET_ALGORITHM_AES: u32 = 0x13;
+
+/// a single password encryption key
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Pek([u8; 16]);
+
+impl Pek {
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+}
+
+impl Display for Pek {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+/// the list of password encryption keys (PEK), which is stored in the
+/// `pekList` attribute of the domain object. The PEKs are used to encrypt
+/// all secret attributes, such as password hashes.
+#[derive(Clone, Debug)]
+pub struct PekList {
+    keys: Vec<Pek>,
+}
+
+impl PekList {
+    /// decrypts the value of the `pekList` attribute using the boot key of the domain controller
+    pub fn decrypt(encrypted: &[u8], boot_key: &BootKey) -> Result<Self> {
+        if encrypted.len() < HEADER_SIZE + KEY_MATERIAL_SIZE + PLAIN_HEADER_SIZE {
+            return Err(Error::EncryptedValueTooShort(encrypted.len()));
+        }
+        let version = LittleEndian::read_u32(&encrypted[0..4]);
+        let key_material: &[u8; KEY_MATERIAL_SIZE] = encrypted
+            [HEADER_SIZE..HEADER_SIZE + KEY_MATERIAL_SIZE]
+            .try_into()
+            .unwrap();
+        let encrypted_peks = &encrypted[HEADER_SIZE + KEY_MATERIAL_SIZE..];
+
+        match version {
+            PEK_LIST_VERSION_RC4 => {
+                let mut parts: Vec<&[u8]> = vec![boot_key.as_bytes()];
+                parts.extend(std::iter::repeat(&key_material[..]).take(1000));
+                let mut plain = encrypted_peks.to_vec();
+                rc4_decrypt(&md5(&parts), &mut plain);
+
+                // every entry consists of a one byte header, three bytes padding and the key
+                let keys = plain[PLAIN_HEADER_SIZE..]
+                    .chunks_exact(PEK_ENTRY_SIZE)
+                    .map(|entry| Pek(entry[4..].try_into().unwrap()))
+                    .collect();
+                Ok(Self { keys })
+            }
+            PEK_LIST_VERSION_AES => {
+                let plain = aes_cbc_decrypt(boot_key.as_bytes(), key_material, encrypted_peks);
+
+                // every entry consists of its index (as u32) and the key. The list
+                // is terminated by the first entry with an unexpected index.
+                let keys = plain[PLAIN_HEADER_SIZE..]
+                    .chunks_exact(PEK_ENTRY_SIZE)
+                    .enumerate()
+                    .take_while(|(idx, entry)| {
+                        u32::try_from(*idx) == Ok(LittleEndian::read_u32(&entry[0..4]))
+                    })
+                    .map(|(_, entry)| Pek(entry[4..].try_into().unwrap()))
+                    .collect();
+                Ok(Self { keys })
+            }
+            version => Err(Error::UnsupportedPekListVersion(version)),
+        }
+    }
+
+    pub fn keys(&self) -> &[Pek] {
+        &self.keys
+    }
+
+    pub fn get(&self, index: usize) -> Result<&Pek> {
+        self.keys.get(index).ok_or(Error::MissingPek(index))
+    }
+
+    /// removes the PEK encryption from the value of a secret attribute.
+    ///
+    /// Every secret attribute starts with an eight byte header, which
+    /// contains the encryption algorithm and the index of the PEK, followed
+    /// by 16 bytes of key material. AES encrypted values store the length of
+    /// the plaintext before the encrypted data.
+    pub fn decrypt_secret(&self, encrypted: &[u8]) -> Result<Vec<u8>> {
+        if encrypted.len() < HEADER_SIZE + KEY_MATERIAL_SIZE {
+            return Err(Error::EncryptedValueTooShort(encrypted.len()));
+        }
+        let algorithm = LittleEndian::read_u32(&encrypted[0..4]);
+        let pek = self.get(usize::from(encrypted[4]))?;
+        let key_material: &[u8; KEY_MATERIAL_SIZE] = encrypted
+            [HEADER_SIZE..HEADER_SIZE + KEY_MATERIAL_SIZE]
+            .try_into()
+            .unwrap();
+        let data = &encrypted[HEADER_SIZE + KEY_MATERIAL_SIZE..];
+
+        if algorithm == SECRET_ALGORITHM_AES {
+            if data.len() < 4 {
+                return Err(Error::EncryptedValueTooShort(encrypted.len()));
+            }
+            let length = LittleEndian::read_u32(&data[0..4]) as usize;
+            let mut plain = aes_cbc_decrypt(pek.as_bytes(), key_material, &data[4..]);
+            plain.truncate(length);
+            Ok(plain)
+        } else {
+            let mut plain = data.to_vec();
+            rc4_decrypt(&md5(&[pek.as_bytes(), key_material]), &mut plain);
+            Ok(plain)
+        }
+    }
+
+    /// decrypts a NT or LM hash (from `unicodePwd` or `dBCSPwd`), which
+    /// belongs to the account with the relative identifier `rid`
+    pub fn decrypt_hash(&self, encrypted: &[u8], rid: u32) -> Result<PasswordHash> {
+        let plain = self.decrypt_secret(encrypted)?;
+        let hash: &[u8; 16] = plain
+            .get(..16)
+            .and_then(|hash| hash.try_into().ok())
+            .ok_or(Error::EncryptedValueTooShort(encrypted.len()))?;
+        Ok(remove_rid_encryption(rid, hash).into())
+    }
+
+    /// decrypts a password history (from `ntPwdHistory` or `lmPwdHistory`),
+    /// which belongs to the account with the relative identifier `rid`
+    pub fn decrypt_hash_history(&self, encrypted: &[u8], rid: u32) -> Result<Vec<PasswordHash>> {
+        Ok(self
+            .decrypt_secret(encrypted)?
+            .chunks_exact(16)
+            .map(|hash| remove_rid_encryption(rid, hash.try_into().unwrap()).into())
+            .collect())
+    }
+
+    /// decrypts and parses the value of the `supplementalCredentials` attribute
+    pub fn decrypt_supplemental_credentials(
+        &self,
+        encrypted: &[u8],
+    ) -> Result<SupplementalCredentials> {
+        // values without any content consist only of header and key material
+        if encrypted.len() <= HEADER_SIZE + KEY_MATERIAL_SIZE {
+            return Ok(SupplementalCredentials::default());
+        }
+        SupplementalCredentials::try_from(&self.decrypt_secret(encrypted)?[..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::crypto::{BootKey, PekList};
+
+    const BOOT_KEY: &str = "88554422bb99dd33006611cceeaaff77";
+    const PEK_LIST_RC4: &str = "0200000000000000000102030405060708090a0b0c0d0e0f1054a12263d0e5689cae442d368f10c64770ccfc09caae46dce3172da0d692a4e0d50a2ffdfdce42f413fcd82bff40a66a9670aff5048a24f0ac7ddf6ee1e7ebfb747f0e196db142";
+    const PEK_LIST_AES: &str = "0300000000000000000102030405060708090a0b0c0d0e0f53680602cce0df29135b9926cd5ce6a767c716c04d7274bc5aa717f431f397c3f26181f5a9772f65c6ccc09a5a41f3d78b3fd5e9ee88051044aab697e3a8e697ae8f7cbc8fb94cf9d45a67456b719af5";
+
+    fn decrypt(pek_list: &str) -> Vec<String> {
+        let boot_key = BootKey::from_str(BOOT_KEY).unwrap();
+        PekList::decrypt(&hex::decode(pek_list).unwrap(), &boot_key)
+            .unwrap()
+            .keys()
+            .iter()
+            .map(|pek| pek.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_rc4_pek_list() {
+        assert_eq!(
+            decrypt(PEK_LIST_RC4),
+            vec![
+                "01010101010101010101010101010101",
+                "02020202020202020202020202020202"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hash_decryption() {
+        let boot_key = BootKey::from_str(BOOT_KEY).unwrap();
+        let pek_list = PekList::decrypt(&hex::decode(PEK_LIST_AES).unwrap(), &boot_key).unwrap();
+        for encrypted in [
+            "1100000001000000101112131415161718191a1b1c1d1e1f7ee1f2b032f79c23f53f087105ff0e7a",
+            "1300000001000000101112131415161718191a1b1c1d1e1f1000000002308ed70ff2bd9a80696368536292f9da983cc95cf28ae7824011560b61a07e",
+        ] {
+            let hash = pek_list
+                .decrypt_hash(&hex::decode(encrypted).unwrap(), 1105)
+                .unwrap();
+            assert_eq!(hash.to_string(), "8846f7eaee8fb117ad06bdd830b7586c");
+        }
+    }
+
+    #[test]
+    fn test_aes_pek_list() {
+        assert_eq!(
+            decrypt(PEK_LIST_AES),
+            vec![
+                "01010101010101010101010101010101",
+                "02020202020202020202020202020202"
+            ]
+        );
+    }
+}
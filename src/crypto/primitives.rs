@@ -0,0 +1,39 @@
+use aes::cipher::{block_padding::NoPadding, BlockDecryptMut, KeyInit, KeyIvInit, StreamCipher};
+use byteorder::{ByteOrder, LittleEndian};
+use md5::{Digest, Md5};
+use rc4::{consts::U16, Rc4};
+
+/// decrypts `data` in place using RC4 with a 128 bit key
+pub(crate) fn rc4_decrypt(key: &[u8; 16], data: &mut [u8]) {
+    let mut cipher = Rc4::<U16>::new(key.into());
+    cipher.apply_keystream(data);
+}
+
+/// decrypts `data` using AES-128 in CBC mode. Incomplete blocks are padded
+/// with zeros, so the result might be longer than the input.
+pub(crate) fn aes_cbc_decrypt(key: &[u8; 16], iv: &[u8; 16], data: &[u8]) -> Vec<u8> {
+    let mut buffer = data.to_vec();
+    buffer.resize(data.len().div_ceil(16) * 16, 0);
+    cbc::Decryptor::<aes::Aes128>::new(key.into(), iv.into())
+        .decrypt_padded_mut::<NoPadding>(&mut buffer)
+        .expect("the buffer is a multiple of the block size");
+    buffer
+}
+
+pub(crate) fn md5(parts: &[&[u8]]) -> [u8; 16] {
+    let mut hasher = Md5::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+pub(crate) fn md4(data: &[u8]) -> [u8; 16] {
+    md4::Md4::digest(data).into()
+}
+
+/// decodes UTF-16LE, replacing invalid characters
+pub(crate) fn utf16_to_string(data: &[u8]) -> String {
+    let chars: Vec<u16> = data.chunks_exact(2).map(LittleEndian::read_u16).collect();
+    String::from_utf16_lossy(&chars)
+}
@@ -0,0 +1,31 @@
+use des::cipher::{BlockDecrypt, KeyInit};
+use des::Des;
+
+/// password hashes are encrypted with DES before they are encrypted with the
+/// PEK. The two DES keys are derived from the RID of the account.
+pub fn remove_rid_encryption(rid: u32, encrypted: &[u8; 16]) -> [u8; 16] {
+    let k = rid.to_le_bytes();
+    let key1 = expand_des_key(&[k[0], k[1], k[2], k[3], k[0], k[1], k[2]]);
+    let key2 = expand_des_key(&[k[3], k[0], k[1], k[2], k[3], k[0], k[1]]);
+
+    let mut decrypted = *encrypted;
+    let (block1, block2) = decrypted.split_at_mut(8);
+    Des::new(&key1.into()).decrypt_block(block1.into());
+    Des::new(&key2.into()).decrypt_block(block2.into());
+    decrypted
+}
+
+/// converts a 56 bit key into a 64 bit DES key (the parity bits are left empty)
+fn expand_des_key(key: &[u8; 7]) -> [u8; 8] {
+    [
+        key[0] >> 1,
+        ((key[0] & 0x01) << 6) | (key[1] >> 2),
+        ((key[1] & 0x03) << 5) | (key[2] >> 3),
+        ((key[2] & 0x07) << 4) | (key[3] >> 4),
+        ((key[3] & 0x0f) << 3) | (key[4] >> 5),
+        ((key[4] & 0x1f) << 2) | (key[5] >> 6),
+        ((key[5] & 0x3f) << 1) | (key[6] >> 7),
+        key[6] & 0x7f,
+    ]
+    .map(|b| b << 1)
+}
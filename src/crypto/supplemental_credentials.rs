@@ -0,0 +1,265 @@
+use std::fmt::Display;
+
+use byteorder::{ByteOrder, LittleEndian};
+use getset::Getters;
+use serde::Serialize;
+
+use super::{utf16_to_string, Error, Result};
+
+const USER_PROPERTIES_HEADER_SIZE: usize = 112;
+const USER_PROPERTIES_SIGNATURE: u16 = 0x50;
+
+const KERB_STORED_CREDENTIAL_HEADER_SIZE: usize = 16;
+const KERB_STORED_CREDENTIAL_NEW_HEADER_SIZE: usize = 24;
+const KERB_KEY_DATA_SIZE: usize = 20;
+const KERB_KEY_DATA_NEW_SIZE: usize = 24;
+
+const WDIGEST_HEADER_SIZE: usize = 16;
+const WDIGEST_HASH_SIZE: usize = 16;
+
+/// encryption type of a Kerberos key, as defined in RFC 3961 and RFC 3962
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct KerberosKeyType(u32);
+
+impl KerberosKeyType {
+    pub const DES_CBC_CRC: Self = Self(1);
+    pub const DES_CBC_MD5: Self = Self(3);
+    pub const AES128_CTS_HMAC_SHA1_96: Self = Self(17);
+    pub const AES256_CTS_HMAC_SHA1_96: Self = Self(18);
+    pub const RC4_HMAC: Self = Self(23);
+}
+
+impl Display for KerberosKeyType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::DES_CBC_CRC => write!(f, "des-cbc-crc"),
+            Self::DES_CBC_MD5 => write!(f, "des-cbc-md5"),
+            Self::AES128_CTS_HMAC_SHA1_96 => write!(f, "aes128-cts-hmac-sha1-96"),
+            Self::AES256_CTS_HMAC_SHA1_96 => write!(f, "aes256-cts-hmac-sha1-96"),
+            Self::RC4_HMAC => write!(f, "rc4-hmac"),
+            Self(key_type) => write!(f, "unknown-{key_type}"),
+        }
+    }
+}
+
+impl Serialize for KerberosKeyType {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// a single Kerberos key
+#[derive(Getters, Clone, Debug)]
+#[getset(get = "pub")]
+pub struct KerberosKey {
+    key_type: KerberosKeyType,
+
+    /// only keys from `Primary:Kerberos-Newer-Keys` have an iteration count
+    iteration_count: Option<u32>,
+    key: Vec<u8>,
+}
+
+/// the contents of `Primary:Kerberos-Newer-Keys` (`KERB_STORED_CREDENTIAL_NEW`)
+/// or `Primary:Kerberos` (`KERB_STORED_CREDENTIAL`)
+#[derive(Getters, Clone, Debug)]
+#[getset(get = "pub")]
+pub struct KerberosCredentials {
+    default_salt: String,
+    default_iteration_count: Option<u32>,
+    credentials: Vec<KerberosKey>,
+    old_credentials: Vec<KerberosKey>,
+    older_credentials: Vec<KerberosKey>,
+}
+
+/// the decrypted and parsed contents of the `supplementalCredentials`
+/// attribute (`USER_PROPERTIES`, see MS-SAMR 2.2.10.1)
+#[derive(Getters, Clone, Debug, Default)]
+#[getset(get = "pub")]
+pub struct SupplementalCredentials {
+    kerberos_newer_keys: Option<KerberosCredentials>,
+    kerberos: Option<KerberosCredentials>,
+    wdigest: Option<Vec<[u8; WDIGEST_HASH_SIZE]>>,
+
+    /// the cleartext password, which is only stored if reversible encryption is enabled
+    cleartext: Option<String>,
+}
+
+impl TryFrom<&[u8]> for SupplementalCredentials {
+    type Error = Error;
+
+    fn try_from(data: &[u8]) -> Result<Self> {
+        let mut me = Self::default();
+
+        // accounts which never had a password set have an empty property list
+        if data.len() < USER_PROPERTIES_HEADER_SIZE
+            || LittleEndian::read_u16(&data[108..110]) != USER_PROPERTIES_SIGNATURE
+        {
+            return Ok(me);
+        }
+
+        let property_count = LittleEndian::read_u16(&data[110..112]);
+        let mut offset = USER_PROPERTIES_HEADER_SIZE;
+        for _ in 0..property_count {
+            let header = slice(data, offset, 6)?;
+            let name_length = LittleEndian::read_u16(&header[0..2]) as usize;
+            let value_length = LittleEndian::read_u16(&header[2..4]) as usize;
+            let name = utf16_to_string(slice(data, offset + 6, name_length)?);
+            let value = slice(data, offset + 6 + name_length, value_length)?;
+            offset += 6 + name_length + value_length;
+
+            // property values are stored as hex encoded ASCII strings
+            let value = hex::decode(value).map_err(|_| {
+                Error::InvalidSupplementalCredentials("property is not hex encoded")
+            })?;
+
+            match &name[..] {
+                "Primary:Kerberos-Newer-Keys" => {
+                    me.kerberos_newer_keys = Some(KerberosCredentials::parse_new(&value)?)
+                }
+                "Primary:Kerberos" => me.kerberos = Some(KerberosCredentials::parse(&value)?),
+                "Primary:WDigest" => me.wdigest = Some(parse_wdigest(&value)?),
+                "Primary:CLEARTEXT" => me.cleartext = Some(utf16_to_string(&value)),
+                _ => log::debug!("ignoring supplemental credentials package '{name}'"),
+            }
+        }
+        Ok(me)
+    }
+}
+
+impl KerberosCredentials {
+    /// parses `KERB_STORED_CREDENTIAL_NEW` (MS-SAMR 2.2.10.5)
+    fn parse_new(data: &[u8]) -> Result<Self> {
+        let header = slice(data, 0, KERB_STORED_CREDENTIAL_NEW_HEADER_SIZE)?;
+        let counts = [
+            LittleEndian::read_u16(&header[4..6]) as usize,
+            LittleEndian::read_u16(&header[6..8]) as usize,
+            LittleEndian::read_u16(&header[8..10]) as usize,
+            LittleEndian::read_u16(&header[10..12]) as usize,
+        ];
+        let default_salt = utf16_to_string(slice(
+            data,
+            LittleEndian::read_u32(&header[16..20]) as usize,
+            LittleEndian::read_u16(&header[12..14]) as usize,
+        )?);
+        let default_iteration_count = Some(LittleEndian::read_u32(&header[20..24]));
+
+        let mut key_lists = Vec::new();
+        let mut offset = KERB_STORED_CREDENTIAL_NEW_HEADER_SIZE;
+        for count in counts {
+            let mut keys = Vec::with_capacity(count);
+            for _ in 0..count {
+                let entry = slice(data, offset, KERB_KEY_DATA_NEW_SIZE)?;
+                keys.push(KerberosKey::parse(
+                    data,
+                    Some(LittleEndian::read_u32(&entry[8..12])),
+                    &entry[12..24],
+                )?);
+                offset += KERB_KEY_DATA_NEW_SIZE;
+            }
+            key_lists.push(keys);
+        }
+
+        // the service credentials (key_lists[1]) are not used
+        let older_credentials = key_lists.pop().unwrap();
+        let old_credentials = key_lists.pop().unwrap();
+        let credentials = key_lists.remove(0);
+        Ok(Self {
+            default_salt,
+            default_iteration_count,
+            credentials,
+            old_credentials,
+            older_credentials,
+        })
+    }
+
+    /// parses `KERB_STORED_CREDENTIAL` (MS-SAMR 2.2.10.4)
+    fn parse(data: &[u8]) -> Result<Self> {
+        let header = slice(data, 0, KERB_STORED_CREDENTIAL_HEADER_SIZE)?;
+        let credential_count = LittleEndian::read_u16(&header[4..6]) as usize;
+        let old_credential_count = LittleEndian::read_u16(&header[6..8]) as usize;
+        let default_salt = utf16_to_string(slice(
+            data,
+            LittleEndian::read_u32(&header[12..16]) as usize,
+            LittleEndian::read_u16(&header[8..10]) as usize,
+        )?);
+
+        let mut keys = Vec::with_capacity(credential_count + old_credential_count);
+        for idx in 0..credential_count + old_credential_count {
+            let entry = slice(
+                data,
+                KERB_STORED_CREDENTIAL_HEADER_SIZE + idx * KERB_KEY_DATA_SIZE,
+                KERB_KEY_DATA_SIZE,
+            )?;
+            keys.push(KerberosKey::parse(data, None, &entry[8..20])?);
+        }
+        let old_credentials = keys.split_off(credential_count);
+        Ok(Self {
+            default_salt,
+            default_iteration_count: None,
+            credentials: keys,
+            old_credentials,
+            older_credentials: Vec::new(),
+        })
+    }
+}
+
+impl KerberosKey {
+    /// `key_info` contains key type, key length and key offset
+    fn parse(data: &[u8], iteration_count: Option<u32>, key_info: &[u8]) -> Result<Self> {
+        let key_type = KerberosKeyType(LittleEndian::read_u32(&key_info[0..4]));
+        let key_length = LittleEndian::read_u32(&key_info[4..8]) as usize;
+        let key_offset = LittleEndian::read_u32(&key_info[8..12]) as usize;
+        Ok(Self {
+            key_type,
+            iteration_count,
+            key: slice(data, key_offset, key_length)?.to_vec(),
+        })
+    }
+}
+
+/// parses `WDIGEST_CREDENTIALS` (MS-SAMR 2.2.10.2)
+fn parse_wdigest(data: &[u8]) -> Result<Vec<[u8; WDIGEST_HASH_SIZE]>> {
+    let header = slice(data, 0, WDIGEST_HEADER_SIZE)?;
+    let count = header[3] as usize;
+    let hashes = slice(data, WDIGEST_HEADER_SIZE, count * WDIGEST_HASH_SIZE)?;
+    Ok(hashes
+        .chunks_exact(WDIGEST_HASH_SIZE)
+        .map(|hash| hash.try_into().unwrap())
+        .collect())
+}
+
+fn slice(data: &[u8], offset: usize, length: usize) -> Result<&[u8]> {
+    data.get(offset..offset + length)
+        .ok_or(Error::InvalidSupplementalCredentials(
+            "unexpected end of data",
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KerberosKeyType, SupplementalCredentials};
+
+    const USER_PROPERTIES: &str = "00000000d701000000000000200020002000200020002000200020002000200020002000200020002000200020002000200020002000200020002000200020002000200020002000200020002000200020002000200020002000200020002000200020002000200020002000500002003600e00001005000720069006d006100720079003a004b00650072006200650072006f0073002d004e0065007700650072002d004b0065007900730030343030303030303031303030303030303030303030303032303030323030303330303030303030303031303030303030303030303030303030303030303030303031303030303031323030303030303230303030303030353030303030303034353030353830303431303034643030353030303463303034353030326530303433303034663030346430303631303036633030363930303633303036353030303030313032303330343035303630373038303930613062306330643065306631303131313231333134313531363137313831393161316231633164316531662200240001005000720069006d006100720079003a0043004c00450041005200540045005800540035303030363130303733303037333030373730303330303037323030363430303231303000";
+
+    #[test]
+    fn test_user_properties() {
+        let data = hex::decode(USER_PROPERTIES).unwrap();
+        let credentials = SupplementalCredentials::try_from(&data[..]).unwrap();
+        assert_eq!(credentials.cleartext().as_deref(), Some("Passw0rd!"));
+        assert!(credentials.kerberos().is_none());
+        assert!(credentials.wdigest().is_none());
+
+        let kerberos = credentials.kerberos_newer_keys().as_ref().unwrap();
+        assert_eq!(kerberos.default_salt(), "EXAMPLE.COMalice");
+        assert_eq!(*kerberos.default_iteration_count(), Some(4096));
+        assert_eq!(kerberos.credentials().len(), 1);
+        assert!(kerberos.old_credentials().is_empty());
+
+        let key = &kerberos.credentials()[0];
+        assert_eq!(*key.key_type(), KerberosKeyType::AES256_CTS_HMAC_SHA1_96);
+        assert_eq!(*key.key(), (0..32).collect::<Vec<u8>>());
+    }
+}
@@ -0,0 +1,234 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use super::{utf16_to_string, Error, Result};
+
+const BASE_BLOCK_SIZE: usize = 4096;
+const KEY_COMP_NAME: u16 = 0x0020;
+const VALUE_COMP_NAME: u16 = 0x0001;
+const DATA_IS_RESIDENT: u32 = 0x8000_0000;
+
+/// minimal reader for offline registry hives (`regf` format).
+///
+/// Only those parts of the format are supported which are required to
+/// obtain the boot key from a `SYSTEM` hive: key nodes, subkey lists,
+/// values with small data and the class names of keys.
+pub struct SystemHive {
+    data: Vec<u8>,
+    root_cell: usize,
+}
+
+/// a key node (`nk`) inside of a registry hive
+#[derive(Clone, Copy)]
+struct KeyNode<'h> {
+    hive: &'h SystemHive,
+    offset: usize,
+}
+
+impl TryFrom<Vec<u8>> for SystemHive {
+    type Error = Error;
+
+    fn try_from(data: Vec<u8>) -> Result<Self> {
+        if data.len() < BASE_BLOCK_SIZE || &data[0..4] != b"regf" {
+            return Err(Error::InvalidHive("missing 'regf' signature"));
+        }
+        let root_cell = LittleEndian::read_u32(&data[0x24..0x28]) as usize;
+        let me = Self { data, root_cell };
+        me.key_node(root_cell)?;
+        Ok(me)
+    }
+}
+
+impl SystemHive {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let mut data = Vec::new();
+        File::open(path)?.read_to_end(&mut data)?;
+        Self::try_from(data)
+    }
+
+    /// returns the number of the current control set, as stored in `Select\Current`
+    pub fn current_control_set(&self) -> Result<u32> {
+        let select = self.open_key("Select")?;
+        let current = select
+            .value_data("Current")?
+            .ok_or_else(|| Error::MissingRegistryEntry("Select\\Current".into()))?;
+        if current.len() < 4 {
+            return Err(Error::InvalidHive("Select\\Current is not a DWORD"));
+        }
+        Ok(LittleEndian::read_u32(current))
+    }
+
+    /// returns the class name of the key which is specified by `path`. Path
+    /// components must be separated by a backslash and are relative to the
+    /// root key of the hive.
+    pub fn class_name(&self, path: &str) -> Result<String> {
+        self.open_key(path)?
+            .class_name()?
+            .ok_or_else(|| Error::MissingRegistryEntry(format!("class name of {path}")))
+    }
+
+    fn open_key(&self, path: &str) -> Result<KeyNode<'_>> {
+        let mut key = self.key_node(self.root_cell)?;
+        for name in path.split('\\').filter(|n| !n.is_empty()) {
+            key = key
+                .subkey(name)?
+                .ok_or_else(|| Error::MissingRegistryEntry(path.to_string()))?;
+        }
+        Ok(key)
+    }
+
+    fn key_node(&self, offset: usize) -> Result<KeyNode<'_>> {
+        let cell = self.cell(offset)?;
+        if cell.len() < 76 || &cell[0..2] != b"nk" {
+            return Err(Error::InvalidHive("expected a key node"));
+        }
+        Ok(KeyNode { hive: self, offset })
+    }
+
+    /// returns the contents of the cell at `offset` (which is relative to
+    /// the first hive bin), without the cell size
+    fn cell(&self, offset: usize) -> Result<&[u8]> {
+        let start = BASE_BLOCK_SIZE
+            .checked_add(offset)
+            .ok_or(Error::InvalidHive("invalid cell offset"))?;
+        let header = self
+            .data
+            .get(start..start + 4)
+            .ok_or(Error::InvalidHive("cell offset is out of bounds"))?;
+        let size = LittleEndian::read_i32(header).unsigned_abs() as usize;
+        if size < 4 {
+            return Err(Error::InvalidHive("invalid cell size"));
+        }
+        self.data
+            .get(start + 4..start + size)
+            .ok_or(Error::InvalidHive("cell exceeds the hive size"))
+    }
+
+    /// returns the offsets of all key nodes which are referenced by the subkey list at `offset`
+    fn subkey_offsets(&self, offset: usize, offsets: &mut Vec<usize>) -> Result<()> {
+        let list = self.cell(offset)?;
+        if list.len() < 4 {
+            return Err(Error::InvalidHive("subkey list is too short"));
+        }
+        let count = LittleEndian::read_u16(&list[2..4]) as usize;
+        let (entry_size, is_index_root) = match &list[0..2] {
+            b"lf" | b"lh" => (8, false),
+            b"li" => (4, false),
+            b"ri" => (4, true),
+            _ => return Err(Error::InvalidHive("unknown subkey list type")),
+        };
+        for idx in 0..count {
+            let start = 4 + idx * entry_size;
+            let entry = list
+                .get(start..start + 4)
+                .ok_or(Error::InvalidHive("subkey list is too short"))?;
+            let entry = LittleEndian::read_u32(entry) as usize;
+            if is_index_root {
+                self.subkey_offsets(entry, offsets)?;
+            } else {
+                offsets.push(entry);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'h> KeyNode<'h> {
+    fn cell(&self) -> &'h [u8] {
+        // the cell has been validated when the key node was created
+        self.hive.cell(self.offset).unwrap()
+    }
+
+    fn u32_at(&self, offset: usize) -> usize {
+        LittleEndian::read_u32(&self.cell()[offset..offset + 4]) as usize
+    }
+
+    fn name(&self) -> Result<String> {
+        let cell = self.cell();
+        let flags = LittleEndian::read_u16(&cell[2..4]);
+        let length = LittleEndian::read_u16(&cell[72..74]) as usize;
+        let name = cell
+            .get(76..76 + length)
+            .ok_or(Error::InvalidHive("key name exceeds the key node"))?;
+        Ok(decode_name(name, flags & KEY_COMP_NAME != 0))
+    }
+
+    fn class_name(&self) -> Result<Option<String>> {
+        let offset = self.u32_at(48);
+        let length = LittleEndian::read_u16(&self.cell()[74..76]) as usize;
+        if offset == 0xffff_ffff || length == 0 {
+            return Ok(None);
+        }
+        let class_name = self
+            .hive
+            .cell(offset)?
+            .get(..length)
+            .ok_or(Error::InvalidHive("class name exceeds its cell"))?;
+        Ok(Some(decode_name(class_name, false)))
+    }
+
+    fn subkey(&self, name: &str) -> Result<Option<KeyNode<'h>>> {
+        if self.u32_at(20) == 0 {
+            return Ok(None);
+        }
+        let mut offsets = Vec::new();
+        self.hive.subkey_offsets(self.u32_at(28), &mut offsets)?;
+        for offset in offsets {
+            let subkey = self.hive.key_node(offset)?;
+            if subkey.name()?.eq_ignore_ascii_case(name) {
+                return Ok(Some(subkey));
+            }
+        }
+        Ok(None)
+    }
+
+    fn value_data(&self, name: &str) -> Result<Option<&'h [u8]>> {
+        let count = self.u32_at(36);
+        if count == 0 {
+            return Ok(None);
+        }
+        let list = self.hive.cell(self.u32_at(40))?;
+        for idx in 0..count {
+            let entry = list
+                .get(idx * 4..idx * 4 + 4)
+                .ok_or(Error::InvalidHive("value list is too short"))?;
+            let value = self.hive.cell(LittleEndian::read_u32(entry) as usize)?;
+            if value.len() < 20 || &value[0..2] != b"vk" {
+                return Err(Error::InvalidHive("expected a key value"));
+            }
+            let name_length = LittleEndian::read_u16(&value[2..4]) as usize;
+            let flags = LittleEndian::read_u16(&value[16..18]);
+            let value_name = value
+                .get(20..20 + name_length)
+                .ok_or(Error::InvalidHive("value name exceeds the key value"))?;
+            if !decode_name(value_name, flags & VALUE_COMP_NAME != 0).eq_ignore_ascii_case(name) {
+                continue;
+            }
+
+            let size = LittleEndian::read_u32(&value[4..8]);
+            return if size & DATA_IS_RESIDENT != 0 {
+                let size = (size & !DATA_IS_RESIDENT) as usize;
+                Ok(Some(&value[8..8 + size.min(4)]))
+            } else {
+                let data = self
+                    .hive
+                    .cell(LittleEndian::read_u32(&value[8..12]) as usize)?;
+                data.get(..size as usize)
+                    .map(Some)
+                    .ok_or(Error::InvalidHive("value data exceeds its cell"))
+            };
+        }
+        Ok(None)
+    }
+}
+
+fn decode_name(name: &[u8], is_compressed: bool) -> String {
+    if is_compressed {
+        name.iter().map(|c| char::from(*c)).collect()
+    } else {
+        utf16_to_string(name)
+    }
+}
@@ -0,0 +1,171 @@
+use byteorder::{ByteOrder, LittleEndian};
+use getset::Getters;
+
+use super::{
+    kerberos_aes_key, md4, utf16_to_string, Error, PasswordHash, Result, KERBEROS_ITERATION_COUNT,
+};
+
+const TRUST_AUTH_HEADER_SIZE: usize = 12;
+const AUTH_INFORMATION_HEADER_SIZE: usize = 16;
+
+const TRUST_AUTH_TYPE_NT4OWF: u32 = 1;
+const TRUST_AUTH_TYPE_CLEAR: u32 = 2;
+
+/// the decrypted value of `trustAuthIncoming` or `trustAuthOutgoing`
+/// (`trustAuthInOutBlob`, see MS-ADTS 6.1.6.9.1)
+#[derive(Getters)]
+#[getset(get = "pub")]
+pub struct TrustAuthInformation {
+    current: Vec<AuthInformation>,
+    previous: Vec<AuthInformation>,
+}
+
+impl TryFrom<&[u8]> for TrustAuthInformation {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self> {
+        if value.len() < TRUST_AUTH_HEADER_SIZE {
+            return Err(Error::InvalidTrustAuthInformation("value is too short"));
+        }
+        let count = LittleEndian::read_u32(&value[0..4]) as usize;
+        let current_offset = LittleEndian::read_u32(&value[4..8]) as usize;
+        let previous_offset = LittleEndian::read_u32(&value[8..12]) as usize;
+        Ok(Self {
+            current: AuthInformation::read_all(value, current_offset, count)?,
+            previous: AuthInformation::read_all(value, previous_offset, count)?,
+        })
+    }
+}
+
+impl TrustAuthInformation {
+    /// returns the current password, or its NT hash
+    pub fn current_password(&self) -> Option<&AuthInformation> {
+        self.current.iter().find(|info| info.is_password())
+    }
+
+    /// returns the previous password, or its NT hash
+    pub fn previous_password(&self) -> Option<&AuthInformation> {
+        self.previous.iter().find(|info| info.is_password())
+    }
+}
+
+/// one `LSAPR_AUTH_INFORMATION` structure
+#[derive(Getters)]
+#[getset(get = "pub")]
+pub struct AuthInformation {
+    /// time of the last password change, as `FILETIME`
+    last_update_time: u64,
+    auth_type: u32,
+
+    #[getset(skip)]
+    auth_info: Vec<u8>,
+}
+
+impl AuthInformation {
+    fn read_all(value: &[u8], offset: usize, count: usize) -> Result<Vec<Self>> {
+        let mut infos = Vec::with_capacity(count);
+        let mut offset = offset;
+        for _ in 0..count {
+            let header = value
+                .get(offset..offset + AUTH_INFORMATION_HEADER_SIZE)
+                .ok_or(Error::InvalidTrustAuthInformation("value is too short"))?;
+            let length = LittleEndian::read_u32(&header[12..16]) as usize;
+            let start = offset + AUTH_INFORMATION_HEADER_SIZE;
+            let auth_info = value
+                .get(start..start + length)
+                .ok_or(Error::InvalidTrustAuthInformation("value is too short"))?;
+            infos.push(Self {
+                last_update_time: LittleEndian::read_u64(&header[0..8]),
+                auth_type: LittleEndian::read_u32(&header[8..12]),
+                auth_info: auth_info.to_vec(),
+            });
+
+            // every structure is aligned to four bytes
+            offset = (start + length).next_multiple_of(4);
+        }
+        Ok(infos)
+    }
+
+    fn is_password(&self) -> bool {
+        matches!(
+            self.auth_type,
+            TRUST_AUTH_TYPE_CLEAR | TRUST_AUTH_TYPE_NT4OWF
+        )
+    }
+
+    pub fn nt_hash(&self) -> Option<PasswordHash> {
+        match self.auth_type {
+            TRUST_AUTH_TYPE_CLEAR => Some(md4(&self.auth_info).into()),
+            TRUST_AUTH_TYPE_NT4OWF => {
+                let hash: [u8; 16] = self.auth_info.get(..16)?.try_into().ok()?;
+                Some(hash.into())
+            }
+            _ => None,
+        }
+    }
+
+    /// calculates the AES key with `key_size` bytes, which is only possible
+    /// if the cleartext password is available
+    pub fn aes_key(&self, salt: &str, key_size: usize) -> Option<Vec<u8>> {
+        (self.auth_type == TRUST_AUTH_TYPE_CLEAR).then(|| {
+            kerberos_aes_key(
+                utf16_to_string(&self.auth_info).as_bytes(),
+                salt.as_bytes(),
+                KERBEROS_ITERATION_COUNT,
+                key_size,
+            )
+        })
+    }
+}
+
+/// returns the salt of the inter-realm key `krbtgt/<target_realm>@<issuing_realm>`
+pub fn inter_realm_salt(issuing_realm: &str, target_realm: &str) -> String {
+    format!(
+        "{}krbtgt{}",
+        issuing_realm.to_uppercase(),
+        target_realm.to_uppercase()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TrustAuthInformation;
+
+    #[test]
+    fn test_trust_auth_information() {
+        let value = hex::decode(concat!(
+            "01000000",
+            "0c000000",
+            "2c000000",
+            // current: cleartext password "password"
+            "0080d3e3a1d5d901",
+            "02000000",
+            "10000000",
+            "700061007300730077006f0072006400",
+            // previous: NT hash
+            "0080d3e3a1d5d901",
+            "01000000",
+            "10000000",
+            "31d6cfe0d16ae931b73c59d7e0c089c0",
+        ))
+        .unwrap();
+        let info = TrustAuthInformation::try_from(&value[..]).unwrap();
+        assert_eq!(
+            info.current_password()
+                .unwrap()
+                .nt_hash()
+                .unwrap()
+                .to_string(),
+            "8846f7eaee8fb117ad06bdd830b7586c"
+        );
+        assert_eq!(
+            info.previous_password()
+                .unwrap()
+                .nt_hash()
+                .unwrap()
+                .to_string(),
+            "31d6cfe0d16ae931b73c59d7e0c089c0"
+        );
+        assert!(info.previous_password().unwrap().aes_key("", 16).is_none());
+    }
+}
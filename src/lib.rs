@@ -14,6 +14,8 @@ pub mod cli;
 pub mod ntds;
 pub mod value;
 pub mod cache;
+pub mod crypto;
+mod redaction;
 mod progress_bar;
 pub use c_database::*;
 pub use column_information::*;
@@ -22,5 +24,6 @@ pub use column_info_mapping::*;
 pub use record_predicate::*;
 pub use esedbinfo::*;
 pub use membership_serialization::*;
+pub use redaction::*;
 use progress_bar::*;
 pub use formatted_value::*;
\ No newline at end of file
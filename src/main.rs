@@ -3,8 +3,12 @@ use std::path::Path;
 use anyhow::Result;
 use clap::Parser;
 use libesedb::EseDb;
-use libntdsextract2::cli::{Args, Commands, OutputOptions};
-use libntdsextract2::{use_member_of_attribute, CDatabase, CsvSerialization, EntryId, EsedbInfo, JsonSerialization};
+use libntdsextract2::cli::{Args, Commands, ExportOptions, OutputOptions};
+use libntdsextract2::ntds::KnownPasswords;
+use libntdsextract2::{
+    enable_redaction, load_pseudonyms, redact_secret, save_pseudonyms, use_member_of_attribute,
+    CDatabase, CsvSerialization, EntryId, EsedbInfo, JsonSerialization,
+};
 use simplelog::{Config, TermLogger};
 
 mod progress_bar;
@@ -42,19 +46,43 @@ fn main() -> Result<()> {
         std::process::exit(-1);
     }
 
+    if *cli.redact() {
+        enable_redaction(*cli.pseudonymize());
+        if let Some(pseudonym_map) = cli.pseudonym_map() {
+            if pseudonym_map.exists() {
+                load_pseudonyms(pseudonym_map)?;
+            }
+        }
+
+        // those commands display raw attribute values, which cannot be redacted
+        if matches!(cli.command(), Commands::Entry { .. } | Commands::Search { .. }) {
+            eprintln!("this command is not supported in combination with `--redact`");
+            std::process::exit(-1);
+        }
+    }
+
     let esedb = EseDb::open(cli.ntds_file())?;
     let info = EsedbInfo::try_from(&esedb)?;
-    let database = CDatabase::new(&info)?;
+    let mut database = CDatabase::new(&info)?;
+
+    if let Some(boot_key) = cli.read_boot_key()? {
+        log::info!("using boot key {}", redact_secret(&boot_key.to_string()));
+        database.set_boot_key(&boot_key)?;
+    }
 
     let mut options = OutputOptions::default();
     options.set_display_all_attributes(cli.command().display_all_attributes());
     options.set_flat_serialization(cli.command().flat_serialization());
     options.set_format(cli.command().format());
     options.set_include_dn(cli.command().include_dn());
+    options.set_show_password_history(cli.command().show_password_history());
+    options.set_show_key_credentials(cli.command().show_key_credentials());
+    options.set_show_password_policy(cli.command().show_password_policy());
+    options.set_show_replication_metadata(cli.command().show_replication_metadata());
 
     use_member_of_attribute(cli.command().member_of_attribute());
 
-    match cli.command() {
+    let result = match cli.command() {
         Commands::Group { .. } => {
             do_with_serialization!(cli.command(), database, show_groups, &options)
         }
@@ -64,15 +92,27 @@ fn main() -> Result<()> {
         Commands::Computer { .. } => {
             do_with_serialization!(cli.command(), database, show_computers, &options)
         }
+        Commands::Objects { class, .. } => {
+            if cli.command().flat_serialization() {
+                database.show_objects_of_class::<CsvSerialization>(&options, class)
+            } else {
+                database.show_objects_of_class::<JsonSerialization>(&options, class)
+            }
+        }
         Commands::Types { .. } => {
             do_with_serialization!(cli.command(), database, show_type_names, &options)
         }
         Commands::Timeline {
             all_objects,
             include_deleted,
+            attribute_changes,
+            membership_changes,
             format
         } => {
             options.set_show_all_objects(*all_objects);
+            options.set_show_attribute_changes(*attribute_changes);
+            options.set_show_membership_changes(*membership_changes);
+            options.set_show_key_credentials(true);
             database.show_timeline(&options, *include_deleted, format)
         }
         Commands::Tree { max_depth } => Ok(database.show_tree(*max_depth)?),
@@ -96,5 +136,81 @@ fn main() -> Result<()> {
             };
             database.search_entries(&regex)
         }
+        Commands::Pek => database.show_pek_list(),
+        Commands::Hashes { format } => database.show_hashes(*format),
+        Commands::Credentials {
+            format,
+            cleartext_only,
+        } => database.show_supplemental_credentials(*format, *cleartext_only),
+        Commands::Export {
+            output_dir,
+            format,
+            include_history,
+            exclude_disabled,
+            exclude_machine_accounts,
+            exclude_deleted,
+        } => {
+            let mut export_options = ExportOptions::default();
+            export_options.set_format(*format);
+            export_options.set_include_history(*include_history);
+            export_options.set_exclude_disabled(*exclude_disabled);
+            export_options.set_exclude_machine_accounts(*exclude_machine_accounts);
+            export_options.set_exclude_deleted(*exclude_deleted);
+            database.export_hashes(&export_options, output_dir)
+        }
+        Commands::Audit {
+            format,
+            known_hashes,
+            wordlist,
+            member_of_attribute: _,
+        } => {
+            let mut known_passwords = KnownPasswords::default();
+            if let Some(known_hashes) = known_hashes {
+                known_passwords.load_hashes(known_hashes)?;
+            }
+            if let Some(wordlist) = wordlist {
+                known_passwords.load_wordlist(wordlist)?;
+            }
+            if cli.command().flat_serialization() {
+                database.show_password_audit::<CsvSerialization>(*format, &known_passwords)
+            } else {
+                database.show_password_audit::<JsonSerialization>(*format, &known_passwords)
+            }
+        }
+        Commands::Gmsa { format } => database.show_gmsa_passwords(*format),
+        Commands::Laps { .. } => {
+            options.set_show_laps(true);
+            do_with_serialization!(cli.command(), database, show_laps, &options)
+        }
+        Commands::Bitlocker { format } => database.show_bitlocker_recovery_information(*format),
+        Commands::Trusts { format } => database.show_trusted_domains(*format),
+        Commands::ShadowCredentials { format } => database.show_shadow_credentials(*format),
+        Commands::Memberships { format, history } => {
+            database.show_group_memberships(*format, *history)
+        }
+        Commands::Anomalies { format } => database.show_anomalies(*format),
+        Commands::Ou { format } => database.show_organizational_units(*format),
+        Commands::Gpo { format } => database.show_group_policy_objects(*format),
+        Commands::Domain { format } => database.show_domain_summary(*format),
+        Commands::PasswordPolicies { format } => database.show_password_policies(*format),
+        Commands::Dns { format, zone_dir } => match zone_dir {
+            Some(zone_dir) => database.export_zone_files(zone_dir),
+            None => database.show_dns_records(*format),
+        },
+        Commands::Topology { format, objects } => database.show_topology(*objects, *format),
+        Commands::Adcs { format, objects } => {
+            database.show_certificate_services(*objects, *format)
+        }
+        Commands::Secrets { format, export_dir } => {
+            database.show_lsa_secrets(*format).and_then(|_| match export_dir {
+                Some(export_dir) => database.export_backup_keys(export_dir),
+                None => Ok(()),
+            })
+        }
+    };
+
+    if let Some(pseudonym_map) = cli.pseudonym_map() {
+        save_pseudonyms(pseudonym_map)?;
     }
+    result
 }
@@ -6,6 +6,7 @@ use crate::{
     cache::RecordPointer,
     cli::MemberOfAttribute,
     object_tree::ObjectTree,
+    pseudonymize_dn, pseudonymize_name,
     win32_types::{Rdn, Sid},
     SerializationType,
 };
@@ -106,22 +107,27 @@ where
     {
         match member_of_attribute() {
             MemberOfAttribute::Sid => {
-                T::serialize(self.sid.as_ref().map(|s| s.to_string()), serializer)
+                T::serialize(self.sid.as_ref().map(Sid::to_pseudonymized_string), serializer)
+            }
+            MemberOfAttribute::Rdn => {
+                T::serialize(Some(self.rdn.to_pseudonymized_string()), serializer)
             }
-            MemberOfAttribute::Rdn => T::serialize(Some(self.rdn.to_string()), serializer),
             MemberOfAttribute::Dn => T::serialize(
                 match &self.dn {
                     PointerOrString::Pointer(_ptr) => None,
-                    PointerOrString::String(dn) => Some(dn.clone()),
+                    PointerOrString::String(dn) => Some(pseudonymize_dn(dn).to_string()),
                     PointerOrString::None => {
                         panic!("it is not expected to serialize a previously deserialized value")
                     }
                 },
                 serializer,
             ),
-            MemberOfAttribute::SamAccountName => {
-                T::serialize(self.sam_account_name.clone(), serializer)
-            }
+            MemberOfAttribute::SamAccountName => T::serialize(
+                self.sam_account_name
+                    .as_ref()
+                    .map(|name| pseudonymize_name(name).to_string()),
+                serializer,
+            ),
         }
     }
 }
@@ -160,16 +166,19 @@ where
     {
         match member_of_attribute() {
             MemberOfAttribute::Sid => T::serialize_list(
-                self.0.iter().map(|m| m.sid.as_ref().map(|s| s.to_string())),
+                self.0.iter().map(|m| m.sid.as_ref().map(Sid::to_pseudonymized_string)),
                 serializer,
             ),
             MemberOfAttribute::Rdn => {
-                T::serialize_list(self.0.iter().map(|m| Some(m.rdn.to_string())), serializer)
+                T::serialize_list(
+                    self.0.iter().map(|m| Some(m.rdn.to_pseudonymized_string())),
+                    serializer,
+                )
             }
             MemberOfAttribute::Dn => T::serialize_list(
                 self.0.iter().map(|m| match &m.dn {
                     PointerOrString::Pointer(ptr) => Some(format!("MISSING ENTRY FOR REFERENCE {ptr}")),
-                    PointerOrString::String(dn) => Some(dn.clone()),
+                    PointerOrString::String(dn) => Some(pseudonymize_dn(dn).to_string()),
                     PointerOrString::None => {
                         panic!("it is not expected to serialize a previously deserialized value")
                     }
@@ -177,7 +186,11 @@ where
                 serializer,
             ),
             MemberOfAttribute::SamAccountName => T::serialize_list(
-                self.0.iter().map(|m| m.sam_account_name.clone()),
+                self.0.iter().map(|m| {
+                    m.sam_account_name
+                        .as_ref()
+                        .map(|name| pseudonymize_name(name).to_string())
+                }),
                 serializer,
             ),
         }
@@ -0,0 +1,198 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+
+use getset::Getters;
+use serde::Serialize;
+
+use crate::crypto::{KerberosCredentials, PekList, SupplementalCredentials};
+use crate::{
+    pseudonymize_dn, pseudonymize_dns_name, pseudonymize_name, redact_free_text, redact_secret,
+};
+
+use super::DataTableRecord;
+
+/// the decrypted `supplementalCredentials` of an account
+#[derive(Getters)]
+#[getset(get = "pub")]
+pub struct AccountCredentials {
+    sam_account_name: String,
+    domain: Option<String>,
+    rid: u32,
+    is_deleted: bool,
+    distinguished_name: Option<String>,
+    credentials: SupplementalCredentials,
+}
+
+/// a single secret from the `supplementalCredentials`. This is a flat
+/// structure, so that it can be used for CSV output as well.
+#[derive(Serialize)]
+pub struct SupplementalCredentialEntry<'a> {
+    sam_account_name: &'a str,
+    domain: &'a Option<String>,
+    rid: u32,
+    is_deleted: bool,
+    distinguished_name: &'a Option<String>,
+    package: &'static str,
+    generation: Option<&'static str>,
+    credential_type: String,
+    salt: Option<Cow<'a, str>>,
+    iteration_count: Option<u32>,
+    value: String,
+}
+
+impl SupplementalCredentialEntry<'_> {
+    pub fn is_cleartext(&self) -> bool {
+        self.package == "Primary:CLEARTEXT"
+    }
+}
+
+impl AccountCredentials {
+    pub fn from_record(
+        record: &DataTableRecord,
+        pek_list: &PekList,
+        distinguished_name: Option<String>,
+    ) -> anyhow::Result<Self> {
+        let credentials = match record.att_supplemental_credentials_opt()? {
+            Some(encrypted) => pek_list.decrypt_supplemental_credentials(&encrypted)?,
+            None => SupplementalCredentials::default(),
+        };
+
+        Ok(Self {
+            sam_account_name: pseudonymize_name(&record.att_sam_account_name()?).to_string(),
+            domain: record
+                .upn_suffix()?
+                .map(|domain| pseudonymize_dns_name(&domain).to_string()),
+            rid: *record.att_object_sid()?.get_rid(),
+            is_deleted: record.att_is_deleted_opt()?.unwrap_or(false),
+            distinguished_name: distinguished_name.map(|dn| pseudonymize_dn(&dn).to_string()),
+            credentials,
+        })
+    }
+
+    /// returns the account name, prefixed with the domain name if it is known
+    pub fn user_name(&self) -> String {
+        match &self.domain {
+            Some(domain) => format!("{domain}\\{}", self.sam_account_name),
+            None => self.sam_account_name.clone(),
+        }
+    }
+
+    /// returns all secrets of this account as a list of flat entries
+    pub fn entries(&self) -> Vec<SupplementalCredentialEntry<'_>> {
+        let mut entries = Vec::new();
+        let entry = |package, generation, credential_type, salt, iteration_count, value| {
+            SupplementalCredentialEntry {
+                sam_account_name: &self.sam_account_name,
+                domain: &self.domain,
+                rid: self.rid,
+                is_deleted: self.is_deleted,
+                distinguished_name: &self.distinguished_name,
+                package,
+                generation,
+                credential_type,
+                salt,
+                iteration_count,
+                value,
+            }
+        };
+
+        for (package, kerberos) in [
+            (
+                "Primary:Kerberos-Newer-Keys",
+                self.credentials.kerberos_newer_keys(),
+            ),
+            ("Primary:Kerberos", self.credentials.kerberos()),
+        ] {
+            if let Some(kerberos) = kerberos {
+                for (generation, keys) in [
+                    ("current", kerberos.credentials()),
+                    ("old", kerberos.old_credentials()),
+                    ("older", kerberos.older_credentials()),
+                ] {
+                    for key in keys {
+                        entries.push(entry(
+                            package,
+                            Some(generation),
+                            key.key_type().to_string(),
+                            Some(redact_free_text(kerberos.default_salt())),
+                            key.iteration_count()
+                                .or(*kerberos.default_iteration_count()),
+                            redact_secret(&hex::encode(key.key())).to_string(),
+                        ));
+                    }
+                }
+            }
+        }
+
+        if let Some(hashes) = self.credentials.wdigest() {
+            for (index, hash) in hashes.iter().enumerate() {
+                entries.push(entry(
+                    "Primary:WDigest",
+                    None,
+                    format!("wdigest-{}", index + 1),
+                    None,
+                    None,
+                    redact_secret(&hex::encode(hash)).to_string(),
+                ));
+            }
+        }
+
+        if let Some(cleartext) = self.credentials.cleartext() {
+            entries.push(entry(
+                "Primary:CLEARTEXT",
+                None,
+                "cleartext".to_string(),
+                None,
+                None,
+                redact_secret(cleartext).to_string(),
+            ));
+        }
+        entries
+    }
+
+    /// returns the cleartext password in the format used by secretsdump, if there is one
+    pub fn cleartext_line(&self) -> Option<String> {
+        let suffix = if self.is_deleted { " (deleted)" } else { "" };
+        self.credentials
+            .cleartext()
+            .as_ref()
+            .map(|cleartext| {
+                format!(
+                    "{}:CLEARTEXT:{}{suffix}",
+                    self.user_name(),
+                    redact_secret(cleartext)
+                )
+            })
+    }
+
+    /// returns the current Kerberos keys, preferring the newer keys
+    fn current_kerberos_credentials(&self) -> Option<&KerberosCredentials> {
+        self.credentials
+            .kerberos_newer_keys()
+            .as_ref()
+            .or(self.credentials.kerberos().as_ref())
+    }
+}
+
+/// displays the current Kerberos keys and the cleartext password in the
+/// format used by secretsdump
+impl Display for AccountCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let user_name = self.user_name();
+        let suffix = if self.is_deleted { " (deleted)" } else { "" };
+        if let Some(kerberos) = self.current_kerberos_credentials() {
+            for key in kerberos.credentials() {
+                writeln!(
+                    f,
+                    "{user_name}:{}:{}{suffix}",
+                    key.key_type(),
+                    redact_secret(&hex::encode(key.key()))
+                )?;
+            }
+        }
+        if let Some(cleartext) = self.cleartext_line() {
+            writeln!(f, "{cleartext}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,291 @@
+use std::fmt::Display;
+
+use getset::Getters;
+use serde::Serialize;
+
+use crate::cli::ExportOptions;
+use crate::crypto::{PasswordHash, PekList};
+use crate::{pseudonymize_dn, pseudonymize_dns_name, pseudonymize_name, redact_secret};
+use crate::win32_types::{SamAccountType, Sid, UserAccountControl};
+
+use super::DataTableRecord;
+
+/// the NT and LM hashes of a user, computer or trust account
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct AccountHashes {
+    sam_account_name: String,
+    domain: Option<String>,
+    rid: u32,
+    sid: Sid,
+    lm_hash: PasswordHash,
+    nt_hash: PasswordHash,
+    is_deleted: bool,
+    distinguished_name: Option<String>,
+
+    #[serde(skip)]
+    is_disabled: bool,
+
+    #[serde(skip)]
+    is_machine_account: bool,
+
+    #[serde(skip)]
+    nt_history: Vec<PasswordHash>,
+
+    #[serde(skip)]
+    lm_history: Vec<PasswordHash>,
+}
+
+impl AccountHashes {
+    pub fn from_record(
+        record: &DataTableRecord,
+        pek_list: &PekList,
+        distinguished_name: Option<String>,
+    ) -> anyhow::Result<Self> {
+        let sid = record.att_object_sid()?;
+        let rid = *sid.get_rid();
+
+        let nt_hash = match record.att_unicode_pwd_opt()? {
+            Some(encrypted) => pek_list.decrypt_hash(&encrypted, rid)?,
+            None => PasswordHash::EMPTY_NT,
+        };
+        let lm_hash = match record.att_dbcs_pwd_opt()? {
+            Some(encrypted) => pek_list.decrypt_hash(&encrypted, rid)?,
+            None => PasswordHash::EMPTY_LM,
+        };
+
+        // the histories are optional, so a corrupt history must not hide the
+        // current hashes
+        let decrypt_history = |encrypted: anyhow::Result<Option<Vec<u8>>>, kind: &str| {
+            let history = encrypted.and_then(|encrypted| match encrypted {
+                Some(encrypted) => Ok(pek_list.decrypt_hash_history(&encrypted, rid)?),
+                None => Ok(Vec::new()),
+            });
+            history.unwrap_or_else(|why| {
+                log::warn!("unable to decrypt the {kind} password history of {sid}: {why}");
+                Vec::new()
+            })
+        };
+        let nt_history = decrypt_history(record.att_nt_pwd_history_opt(), "NT");
+        let lm_history = decrypt_history(record.att_lm_pwd_history_opt(), "LM");
+
+        Ok(Self {
+            sam_account_name: pseudonymize_name(&record.att_sam_account_name()?).to_string(),
+            domain: record
+                .upn_suffix()?
+                .map(|domain| pseudonymize_dns_name(&domain).to_string()),
+            rid,
+            sid,
+            lm_hash,
+            nt_hash,
+            is_deleted: record.att_is_deleted_opt()?.unwrap_or(false),
+            distinguished_name: distinguished_name.map(|dn| pseudonymize_dn(&dn).to_string()),
+            is_disabled: record
+                .att_user_account_control_opt()?
+                .is_some_and(|uac| uac.contains(UserAccountControl::ADS_UF_ACCOUNTDISABLE)),
+            is_machine_account: record.att_sam_account_type_opt()?
+                == Some(SamAccountType::SAM_MACHINE_ACCOUNT),
+            nt_history,
+            lm_history,
+        })
+    }
+
+    /// returns the account name, prefixed with the domain name if it is known
+    pub fn user_name(&self) -> String {
+        match &self.domain {
+            Some(domain) => format!("{domain}\\{}", self.sam_account_name),
+            None => self.sam_account_name.clone(),
+        }
+    }
+
+    /// checks if this account is excluded by one of the `--exclude-*` options
+    pub fn is_excluded(&self, options: &ExportOptions) -> bool {
+        (*options.exclude_disabled() && self.is_disabled)
+            || (*options.exclude_machine_accounts() && self.is_machine_account)
+            || (*options.exclude_deleted() && self.is_deleted)
+    }
+
+    /// returns the lines which are exported for the NT hashes of this account
+    pub fn nt_lines(&self, options: &ExportOptions) -> Vec<String> {
+        self.with_history(&self.nt_hash, &self.nt_history, *options.include_history())
+            .into_iter()
+            .filter(|(_, hash)| *hash != PasswordHash::EMPTY_NT)
+            .map(|(user_name, hash)| options.format().nt_line(&user_name, &hash))
+            .collect()
+    }
+
+    /// returns the lines which are exported for the LM hashes of this account
+    pub fn lm_lines(&self, options: &ExportOptions) -> Vec<String> {
+        self.with_history(&self.lm_hash, &self.lm_history, *options.include_history())
+            .into_iter()
+            .flat_map(|(user_name, hash)| options.format().lm_lines(&user_name, &hash))
+            .collect()
+    }
+
+    fn with_history(
+        &self,
+        current: &PasswordHash,
+        history: &[PasswordHash],
+        include_history: bool,
+    ) -> Vec<(String, PasswordHash)> {
+        let user_name = self.user_name();
+        let mut hashes = vec![(user_name.clone(), *current)];
+
+        // the first entry of the history is the current hash, so we number
+        // the remaining entries the same way secretsdump does
+        if include_history {
+            for (index, hash) in history.iter().enumerate().skip(1) {
+                hashes.push((format!("{user_name}_history{}", index - 1), *hash));
+            }
+        }
+        hashes
+    }
+}
+
+/// displays the hashes in the format used by secretsdump
+impl Display for AccountHashes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}:{}:::",
+            self.user_name(),
+            self.rid,
+            redact_secret(&self.lm_hash.to_string()),
+            redact_secret(&self.nt_hash.to_string())
+        )?;
+        if self.is_deleted {
+            write!(f, " (deleted)")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::cli::{CrackerFormat, ExportOptions};
+    use crate::crypto::PasswordHash;
+    use crate::win32_types::Sid;
+
+    use super::AccountHashes;
+
+    fn hash(hex: &str) -> PasswordHash {
+        PasswordHash::from_str(hex).unwrap()
+    }
+
+    fn account(name: &str) -> AccountHashes {
+        AccountHashes {
+            sam_account_name: name.to_string(),
+            domain: Some("example.com".to_string()),
+            rid: 1104,
+            sid: Sid::from_str("S-1-5-21-1-2-3-1104").unwrap(),
+            lm_hash: hash("e52cac67419a9a224a3b108f3fa6cb6d"),
+            nt_hash: hash("64f12cddaa88057e06a81b54e73b949b"),
+            is_deleted: false,
+            distinguished_name: None,
+            is_disabled: false,
+            is_machine_account: false,
+            nt_history: vec![
+                hash("64f12cddaa88057e06a81b54e73b949b"),
+                hash("a4f49c406510bdcab6824ee7c30fd852"),
+                PasswordHash::EMPTY_NT,
+                hash("0cb6948805f797bf2a82807973b89537"),
+            ],
+            lm_history: vec![
+                hash("e52cac67419a9a224a3b108f3fa6cb6d"),
+                hash("e52cac67419a9a22aad3b435b51404ee"),
+            ],
+        }
+    }
+
+    fn options(format: CrackerFormat, include_history: bool) -> ExportOptions {
+        let mut options = ExportOptions::default();
+        options.set_format(format);
+        options.set_include_history(include_history);
+        options
+    }
+
+    #[test]
+    fn test_hashcat_lines() {
+        let account = account("alice");
+        let options = options(CrackerFormat::Hashcat, false);
+        assert_eq!(
+            account.nt_lines(&options),
+            ["example.com\\alice:64f12cddaa88057e06a81b54e73b949b"]
+        );
+        assert_eq!(
+            account.lm_lines(&options),
+            ["example.com\\alice:e52cac67419a9a22", "example.com\\alice:4a3b108f3fa6cb6d"]
+        );
+    }
+
+    #[test]
+    fn test_john_lines() {
+        let account = account("alice");
+        let options = options(CrackerFormat::John, false);
+        assert_eq!(
+            account.nt_lines(&options),
+            ["example.com\\alice:$NT$64f12cddaa88057e06a81b54e73b949b"]
+        );
+        assert_eq!(
+            account.lm_lines(&options),
+            [
+                "example.com\\alice:$LM$e52cac67419a9a22",
+                "example.com\\alice:$LM$4a3b108f3fa6cb6d"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_history_lines() {
+        let mut account = account("alice");
+        account.domain = None;
+        let options = options(CrackerFormat::Hashcat, true);
+
+        // the empty hash in the history is skipped, but still counted
+        assert_eq!(
+            account.nt_lines(&options),
+            [
+                "alice:64f12cddaa88057e06a81b54e73b949b",
+                "alice_history0:a4f49c406510bdcab6824ee7c30fd852",
+                "alice_history2:0cb6948805f797bf2a82807973b89537",
+            ]
+        );
+        assert_eq!(
+            account.lm_lines(&options),
+            ["alice:e52cac67419a9a22", "alice:4a3b108f3fa6cb6d", "alice_history0:e52cac67419a9a22"]
+        );
+    }
+
+    #[test]
+    fn test_exclusion() {
+        let active = account("alice");
+        let mut disabled = account("bob");
+        disabled.is_disabled = true;
+        let mut machine_account = account("WS01$");
+        machine_account.is_machine_account = true;
+        let mut deleted = account("carol");
+        deleted.is_deleted = true;
+
+        let excluded = |options: &ExportOptions| {
+            [&active, &disabled, &machine_account, &deleted]
+                .into_iter()
+                .filter(|account| account.is_excluded(options))
+                .map(|account| account.sam_account_name().as_str())
+                .collect::<Vec<_>>()
+        };
+
+        let mut options = ExportOptions::default();
+        assert!(excluded(&options).is_empty());
+
+        options.set_exclude_disabled(true);
+        assert_eq!(excluded(&options), ["bob"]);
+
+        options.set_exclude_machine_accounts(true);
+        assert_eq!(excluded(&options), ["bob", "WS01$"]);
+
+        options.set_exclude_deleted(true);
+        assert_eq!(excluded(&options), ["bob", "WS01$", "carol"]);
+    }
+}
@@ -0,0 +1,361 @@
+use chrono::{DateTime, Duration, Utc};
+use getset::Getters;
+use serde::Serialize;
+
+use crate::win32_types::{AttributeMetadata, ReplicationMetadata, TruncatedWindowsFileTime};
+
+use super::NtdsAttributeId;
+
+/// the maximum clock skew between two domain controllers, which is allowed
+/// before the timestamps of an object are considered to be inconsistent
+const CLOCK_SKEW_TOLERANCE_MINUTES: i64 = 5;
+
+/// the amount by which an authoritative restore (`ntdsutil`) increases the
+/// version of every attribute, per day since the backup has been taken
+const AUTHORITATIVE_RESTORE_VERSION_INCREMENT: u32 = 100_000;
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyKind {
+    /// `whenChanged` is older than the last originating change of an attribute
+    WhenChangedBeforeChange,
+
+    /// `whenCreated` is newer than `whenChanged`
+    CreatedAfterChanged,
+
+    /// `whenCreated` differs from the originating change time of `whenCreated`
+    /// in the replication metadata
+    WhenCreatedMismatch,
+
+    /// an attribute has been changed before the object has been created
+    ChangeBeforeCreation,
+
+    /// the record has been written before the object has been created
+    RecordTimeBeforeCreation,
+
+    /// `uSNChanged` is lower than `uSNCreated` or lower than the local USN of
+    /// an attribute change
+    UsnOutOfOrder,
+
+    /// the object has been created on this DC with a higher USN than another
+    /// object, but its creation time is older
+    UsnRollback,
+
+    /// an attribute has a version of 0, or `whenCreated` (which is written
+    /// exactly once) has a version other than 1, without the object having
+    /// been restored authoritatively
+    VersionReset,
+}
+
+/// an inconsistency between the timestamps, USNs and the replication
+/// metadata of an object
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct Anomaly {
+    anomaly: AnomalyKind,
+    record_id: i32,
+    name: Option<String>,
+    distinguished_name: Option<String>,
+    details: String,
+}
+
+impl Anomaly {
+    /// `name` and `distinguished_name` must already be pseudonymized
+    pub fn new(
+        anomaly: AnomalyKind,
+        record_id: i32,
+        name: Option<String>,
+        distinguished_name: Option<String>,
+        details: String,
+    ) -> Self {
+        Self {
+            anomaly,
+            record_id,
+            name,
+            distinguished_name,
+            details,
+        }
+    }
+}
+
+/// the timestamps and update sequence numbers of a single object
+#[derive(Default)]
+pub struct ObjectTimestamps {
+    pub record_time: Option<DateTime<Utc>>,
+    pub when_created: Option<DateTime<Utc>>,
+    pub when_changed: Option<DateTime<Utc>>,
+    pub usn_created: Option<i64>,
+    pub usn_changed: Option<i64>,
+    pub replication_metadata: Option<ReplicationMetadata>,
+}
+
+fn display(ts: DateTime<Utc>) -> TruncatedWindowsFileTime {
+    TruncatedWindowsFileTime::from(ts)
+}
+
+impl ObjectTimestamps {
+    /// checks the timestamps and USNs of this object for consistency
+    pub fn check(&self) -> Vec<(AnomalyKind, String)> {
+        let tolerance = Duration::minutes(CLOCK_SKEW_TOLERANCE_MINUTES);
+        let mut anomalies = Vec::new();
+
+        if let (Some(when_created), Some(when_changed)) = (self.when_created, self.when_changed) {
+            if when_created > when_changed + tolerance {
+                anomalies.push((
+                    AnomalyKind::CreatedAfterChanged,
+                    format!(
+                        "whenCreated ({}) is newer than whenChanged ({})",
+                        display(when_created),
+                        display(when_changed)
+                    ),
+                ));
+            }
+        }
+
+        if let (Some(record_time), Some(when_created)) = (self.record_time, self.when_created) {
+            if record_time + tolerance < when_created {
+                anomalies.push((
+                    AnomalyKind::RecordTimeBeforeCreation,
+                    format!(
+                        "the record has been written ({}) before the object has been created ({})",
+                        display(record_time),
+                        display(when_created)
+                    ),
+                ));
+            }
+        }
+
+        if let (Some(usn_created), Some(usn_changed)) = (self.usn_created, self.usn_changed) {
+            if usn_changed < usn_created {
+                anomalies.push((
+                    AnomalyKind::UsnOutOfOrder,
+                    format!("uSNChanged ({usn_changed}) is lower than uSNCreated ({usn_created})"),
+                ));
+            }
+        }
+
+        if let Some(metadata) = &self.replication_metadata {
+            self.check_replication_metadata(metadata, tolerance, &mut anomalies);
+        }
+        anomalies
+    }
+
+    fn check_replication_metadata(
+        &self,
+        metadata: &ReplicationMetadata,
+        tolerance: Duration,
+        anomalies: &mut Vec<(AnomalyKind, String)>,
+    ) {
+        let attribute_name = |entry: &AttributeMetadata| {
+            entry
+                .attribute()
+                .clone()
+                .unwrap_or_else(|| entry.attribute_id().to_string())
+        };
+
+        if let Some(last_change) = metadata
+            .entries()
+            .iter()
+            .max_by_key(|entry| DateTime::<Utc>::from(entry.originating_change_time()))
+        {
+            let change_time = DateTime::<Utc>::from(last_change.originating_change_time());
+            if let Some(when_changed) = self.when_changed {
+                if when_changed + tolerance < change_time {
+                    anomalies.push((
+                        AnomalyKind::WhenChangedBeforeChange,
+                        format!(
+                            "whenChanged ({}) is older than the last change of {} ({})",
+                            display(when_changed),
+                            attribute_name(last_change),
+                            display(change_time)
+                        ),
+                    ));
+                }
+            }
+        }
+
+        // an authoritative restore rewrites all attributes, including `whenCreated`
+        let is_restored = is_authoritatively_restored(metadata);
+
+        for entry in metadata.entries() {
+            let change_time = DateTime::<Utc>::from(entry.originating_change_time());
+            let is_when_created = *entry.attribute_id() == NtdsAttributeId::AttWhenCreated as u32;
+
+            if *entry.version() == 0 {
+                anomalies.push((
+                    AnomalyKind::VersionReset,
+                    format!("{} has version 0", attribute_name(entry)),
+                ));
+            } else if is_when_created && *entry.version() != 1 && !is_restored {
+                anomalies.push((
+                    AnomalyKind::VersionReset,
+                    format!("whenCreated has version {}", entry.version()),
+                ));
+            }
+
+            if let Some(when_created) = self.when_created {
+                if is_when_created {
+                    if !is_restored && (change_time - when_created).num_seconds().abs() > 1 {
+                        anomalies.push((
+                            AnomalyKind::WhenCreatedMismatch,
+                            format!(
+                                "whenCreated is {}, but has been written at {}",
+                                display(when_created),
+                                display(change_time)
+                            ),
+                        ));
+                    }
+                } else if change_time + tolerance < when_created {
+                    anomalies.push((
+                        AnomalyKind::ChangeBeforeCreation,
+                        format!(
+                            "{} has been changed ({}) before the object has been created ({})",
+                            attribute_name(entry),
+                            display(change_time),
+                            display(when_created)
+                        ),
+                    ));
+                }
+            }
+
+            if let Some(usn_changed) = self.usn_changed {
+                if *entry.local_usn() > usn_changed {
+                    anomalies.push((
+                        AnomalyKind::UsnOutOfOrder,
+                        format!(
+                            "the local USN of {} ({}) is higher than uSNChanged ({usn_changed})",
+                            attribute_name(entry),
+                            entry.local_usn()
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// returns `uSNCreated` and `whenCreated`, if the object has been
+    /// created on this DC (and not been replicated from another DC)
+    pub fn local_creation(&self) -> Option<(i64, DateTime<Utc>)> {
+        let entry = self
+            .replication_metadata
+            .as_ref()?
+            .entries()
+            .iter()
+            .find(|entry| *entry.attribute_id() == NtdsAttributeId::AttWhenCreated as u32)?;
+        if entry.local_usn() == entry.originating_usn() {
+            Some((self.usn_created?, self.when_created?))
+        } else {
+            None
+        }
+    }
+}
+
+/// returns `true` if the object has been restored authoritatively, which
+/// increases the version of every attribute by at least
+/// [`AUTHORITATIVE_RESTORE_VERSION_INCREMENT`]
+fn is_authoritatively_restored(metadata: &ReplicationMetadata) -> bool {
+    !metadata.entries().is_empty()
+        && metadata
+            .entries()
+            .iter()
+            .all(|entry| *entry.version() > AUTHORITATIVE_RESTORE_VERSION_INCREMENT)
+}
+
+/// checks that objects which have been created on this DC have been created in
+/// the order of their USNs. Returns the indices of all objects which have a
+/// higher USN than an object which has been created later, together with the
+/// USN and creation time of that object.
+pub fn find_usn_rollbacks(creations: &[(i64, DateTime<Utc>)]) -> Vec<(usize, String)> {
+    let tolerance = Duration::minutes(CLOCK_SKEW_TOLERANCE_MINUTES);
+    let mut order: Vec<_> = (0..creations.len()).collect();
+    order.sort_by_key(|idx| creations[*idx].0);
+
+    let mut rollbacks = Vec::new();
+    let mut latest: Option<(i64, DateTime<Utc>)> = None;
+    for idx in order {
+        let (usn, when_created) = creations[idx];
+        match latest {
+            Some((latest_usn, latest_created)) if when_created + tolerance < latest_created => {
+                rollbacks.push((
+                    idx,
+                    format!(
+                        "created at {} with USN {usn}, but an object with the lower USN {latest_usn} has been created at {}",
+                        display(when_created),
+                        display(latest_created)
+                    ),
+                ));
+            }
+            Some((_, latest_created)) if when_created <= latest_created => (),
+            _ => latest = Some((usn, when_created)),
+        }
+    }
+    rollbacks
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::win32_types::ReplicationMetadata;
+
+    use super::{find_usn_rollbacks, AnomalyKind, ObjectTimestamps};
+
+    /// returns the replication metadata of `whenCreated` and `cn`, with the
+    /// given versions
+    fn replication_metadata(when_created: u32, cn: u32) -> ReplicationMetadata {
+        let mut value = hex::decode("01000000000000000200000000000000").unwrap();
+        for (attribute_id, version) in [(0x20002u32, when_created), (3, cn)] {
+            value.extend_from_slice(&attribute_id.to_le_bytes());
+            value.extend_from_slice(&version.to_le_bytes());
+            value.extend_from_slice(&0x3_cdc5_8000u64.to_le_bytes());
+            value.extend_from_slice(&[0x11; 16]);
+            value.extend_from_slice(&0x2010i64.to_le_bytes());
+            value.extend_from_slice(&0x2010i64.to_le_bytes());
+        }
+        ReplicationMetadata::try_from(&value[..]).unwrap()
+    }
+
+    #[test]
+    fn test_usn_rollback() {
+        let ts = |hour| Utc.with_ymd_and_hms(2024, 6, 1, hour, 0, 0).unwrap();
+        let creations = [(100, ts(8)), (300, ts(10)), (200, ts(9)), (400, ts(7))];
+        let rollbacks = find_usn_rollbacks(&creations);
+        assert_eq!(rollbacks.len(), 1);
+        assert_eq!(rollbacks[0].0, 3);
+    }
+
+    #[test]
+    fn test_timestamps() {
+        let ts = |hour| Utc.with_ymd_and_hms(2024, 6, 1, hour, 0, 0).unwrap();
+        let timestamps = ObjectTimestamps {
+            record_time: Some(ts(12)),
+            when_created: Some(ts(12)),
+            when_changed: Some(ts(10)),
+            usn_created: Some(200),
+            usn_changed: Some(100),
+            replication_metadata: None,
+        };
+        let anomalies: Vec<_> = timestamps.check().into_iter().map(|(a, _)| a).collect();
+        assert_eq!(
+            anomalies,
+            vec![AnomalyKind::CreatedAfterChanged, AnomalyKind::UsnOutOfOrder]
+        );
+    }
+
+    #[test]
+    fn test_version_reset() {
+        let check = |when_created, cn| {
+            let timestamps = ObjectTimestamps {
+                replication_metadata: Some(replication_metadata(when_created, cn)),
+                ..Default::default()
+            };
+            timestamps.check().into_iter().map(|(a, _)| a).collect::<Vec<_>>()
+        };
+        assert_eq!(check(1, 5), vec![]);
+        assert_eq!(check(3, 5), vec![AnomalyKind::VersionReset]);
+
+        // an authoritative restore increases the version of all attributes
+        assert_eq!(check(100_001, 100_005), vec![]);
+        assert_eq!(check(100_001, 5), vec![AnomalyKind::VersionReset]);
+    }
+}
@@ -0,0 +1,129 @@
+use bodyfile::Bodyfile3Line;
+use chrono::{DateTime, Utc};
+use flow_record::derive::*;
+use flow_record::prelude::*;
+
+use crate::win32_types::{AttributeMetadata, TimelineEntry};
+
+/// a single attribute change, which has been taken from the replication metadata
+#[derive(FlowRecord)]
+#[flow_record(version = 1, source = "ntdsextract2", classification = "ntds")]
+pub struct NtdsAttributeChange {
+    pub(crate) name: String,
+    pub(crate) object_type: String,
+    pub(crate) record_id: i32,
+    pub(crate) attribute: String,
+    pub(crate) version: u32,
+    pub(crate) change_time: DateTime<Utc>,
+    pub(crate) originating_invocation_id: String,
+    pub(crate) originating_dsa: String,
+    pub(crate) originating_usn: i64,
+    pub(crate) local_usn: i64,
+}
+
+/// creates the bodyfile line of a single attribute change of the object
+/// `name`, whose record id is `inode`
+pub fn attribute_change_to_bodyfile(
+    entry: &AttributeMetadata,
+    name: &str,
+    object_type: &str,
+    inode: &str,
+) -> Bodyfile3Line {
+    let caption = format!(
+        "{} changed on DC {}, version {}",
+        entry
+            .attribute()
+            .clone()
+            .unwrap_or_else(|| entry.attribute_id().to_string()),
+        entry.originating_dsa_name(),
+        entry.version()
+    );
+    entry
+        .originating_change_time()
+        .c_entry(name, &caption, object_type)
+        .with_inode(inode)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cache::{RecordId, RecordPointer};
+    use crate::ntds::{GroupMembership, MemberLink};
+    use crate::win32_types::{LinkValueMetadata, ReplicationMetadata, TruncatedWindowsFileTime};
+
+    use super::attribute_change_to_bodyfile;
+
+    /// 2024-06-01T12:00:00Z
+    const CREATED: i64 = 1717243200;
+
+    /// 2024-06-01T14:00:15Z
+    const CHANGED: i64 = 1717250415;
+
+    #[test]
+    fn test_attribute_change_event() {
+        let value = hex::decode(concat!(
+            "01000000",
+            "00000000",
+            "01000000",
+            "00000000",
+            // an attribute whose name has not been resolved
+            "1d059000",
+            "07000000",
+            "6fba6b1c03000000",
+            "33221100554477668899aabbccddeeff",
+            "1020000000000000",
+            "2030000000000000",
+        ))
+        .unwrap();
+        let metadata = ReplicationMetadata::try_from(&value[..]).unwrap();
+        let line = attribute_change_to_bodyfile(&metadata.entries()[0], "alice", "Person", "4711");
+        assert_eq!(
+            line.get_name(),
+            "alice (Person, 9438493 changed on DC 00112233-4455-6677-8899-aabbccddeeff, version 7)"
+        );
+        assert_eq!(line.get_inode(), "4711");
+        assert_eq!(line.get_ctime(), CHANGED);
+
+        let metadata = metadata.with_dsa_names(|_| Some("DC01".to_string()));
+        let line = attribute_change_to_bodyfile(&metadata.entries()[0], "alice", "Person", "4711");
+        assert_eq!(line.get_name(), "alice (Person, 9438493 changed on DC DC01, version 7)");
+    }
+
+    #[test]
+    fn test_absent_member_event() {
+        let value = hex::decode(concat!(
+            "409e6b1c03000000",
+            "02000000",
+            "00000000",
+            "6fba6b1c03000000",
+            "33221100554477668899aabbccddeeff",
+            "1020000000000000",
+        ))
+        .unwrap();
+        let link = MemberLink {
+            group: RecordPointer::new(RecordId::from(100), 1.into()),
+            member: RecordPointer::new(RecordId::from(200), 2.into()),
+            deletion_time: Some(TruncatedWindowsFileTime::from(
+                chrono::DateTime::from_timestamp(CHANGED, 0).unwrap(),
+            )),
+            usn_changed: Some(0x2010),
+            metadata: Some(LinkValueMetadata::try_from(&value[..]).unwrap()),
+        };
+        let membership = GroupMembership::from_link(
+            &link,
+            "Domain Admins".to_string(),
+            "CN=alice,CN=Users,DC=example,DC=com".to_string(),
+            Some("alice".to_string()),
+            |_| Some("DC01".to_string()),
+        );
+
+        let lines = membership.to_bodyfile();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].get_name(), "alice (group membership, added to Domain Admins)");
+        assert_eq!(lines[0].get_ctime(), CREATED);
+        assert_eq!(
+            lines[1].get_name(),
+            "alice (group membership, removed from Domain Admins on DC DC01)"
+        );
+        assert_eq!(lines[1].get_ctime(), CHANGED);
+    }
+}
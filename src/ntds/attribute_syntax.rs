@@ -0,0 +1,70 @@
+/// the syntax of an attribute (`attributeSyntax`), which determines how
+/// its values are stored.
+///
+/// The syntax is also encoded in the name of the column which stores the
+/// attribute: `ATT`, followed by a character from `b` (`2.5.5.1`) to `r`
+/// (`2.5.5.17`) and the `attributeID`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AttributeSyntax {
+    /// `2.5.5.1`, stored as the record id (DNT) of the referenced object
+    DistinguishedName,
+    /// `2.5.5.2`
+    ObjectIdentifier,
+    /// `2.5.5.3`
+    CaseExactString,
+    /// `2.5.5.4`
+    CaseIgnoreString,
+    /// `2.5.5.5`
+    PrintableString,
+    /// `2.5.5.6`
+    NumericString,
+    /// `2.5.5.7`
+    DnWithBinary,
+    /// `2.5.5.8`
+    Boolean,
+    /// `2.5.5.9`
+    Integer,
+    /// `2.5.5.10`
+    OctetString,
+    /// `2.5.5.11`, stored as seconds since 1601-01-01
+    UtcTime,
+    /// `2.5.5.12`
+    UnicodeString,
+    /// `2.5.5.13`
+    PresentationAddress,
+    /// `2.5.5.14`
+    DnWithString,
+    /// `2.5.5.15`, stored as the id of the security descriptor in the `sd_table`
+    SecurityDescriptor,
+    /// `2.5.5.16`, which is also used for `FILETIME`s
+    LargeInteger,
+    /// `2.5.5.17`
+    Sid,
+}
+
+impl AttributeSyntax {
+    /// returns the syntax which is denoted by the fourth character of an
+    /// attribute column name (e.g. `m` in `ATTm3`)
+    pub fn from_column_prefix(prefix: char) -> Option<Self> {
+        Some(match prefix {
+            'b' => Self::DistinguishedName,
+            'c' => Self::ObjectIdentifier,
+            'd' => Self::CaseExactString,
+            'e' => Self::CaseIgnoreString,
+            'f' => Self::PrintableString,
+            'g' => Self::NumericString,
+            'h' => Self::DnWithBinary,
+            'i' => Self::Boolean,
+            'j' => Self::Integer,
+            'k' => Self::OctetString,
+            'l' => Self::UtcTime,
+            'm' => Self::UnicodeString,
+            'n' => Self::PresentationAddress,
+            'o' => Self::DnWithString,
+            'p' => Self::SecurityDescriptor,
+            'q' => Self::LargeInteger,
+            'r' => Self::Sid,
+            _ => return None,
+        })
+    }
+}
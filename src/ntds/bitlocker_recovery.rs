@@ -0,0 +1,99 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use getset::Getters;
+use serde::Serialize;
+
+use crate::win32_types::{Guid, WindowsFileTime};
+use crate::{pseudonymize_dn, pseudonymize_name, redact_secret};
+
+use super::DataTableRecord;
+
+/// a BitLocker recovery information object (`msFVE-RecoveryInformation`),
+/// which is stored as child of the computer whose volume it belongs to
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct BitlockerRecovery {
+    computer: Option<String>,
+    sam_account_name: Option<String>,
+
+    /// the creation time of the recovery password, taken from the RDN
+    created: Option<WindowsFileTime>,
+
+    /// identifies the recovery password; this is the password ID which
+    /// BitLocker displays on the recovery screen
+    recovery_guid: Option<Guid>,
+    volume_guid: Option<Guid>,
+    recovery_password: Option<String>,
+
+    /// the key package, which can be used to repair damaged volumes
+    key_package: Option<String>,
+}
+
+impl BitlockerRecovery {
+    pub fn from_record(
+        record: &DataTableRecord,
+        computer: Option<&DataTableRecord>,
+        computer_dn: Option<String>,
+        attribute_ids: &BitlockerAttributeIds,
+    ) -> anyhow::Result<Self> {
+        let rdn = record.att_object_name()?;
+        let (created, recovery_guid) = match parse_recovery_rdn(rdn.name()) {
+            Some((created, recovery_guid)) => (Some(created.into()), Some(recovery_guid)),
+            None => {
+                log::warn!("unexpected name of BitLocker recovery information: '{rdn}'");
+                (None, None)
+            }
+        };
+        let sam_account_name = match computer {
+            Some(computer) => computer.att_sam_account_name_opt()?,
+            None => None,
+        };
+
+        Ok(Self {
+            computer: computer_dn.map(|dn| pseudonymize_dn(&dn).to_string()),
+            sam_account_name: sam_account_name.map(|name| pseudonymize_name(&name).to_string()),
+            created,
+            recovery_guid,
+            volume_guid: record.get_value_by_attribute_id_opt(attribute_ids.volume_guid)?,
+            recovery_password: record
+                .get_value_by_attribute_id_opt::<String>(attribute_ids.recovery_password)?
+                .map(|password| redact_secret(&password).to_string()),
+            key_package: record
+                .get_value_by_attribute_id_opt::<Vec<u8>>(attribute_ids.key_package)?
+                .map(|key_package| redact_secret(&hex::encode(key_package)).to_string()),
+        })
+    }
+}
+
+/// the ids of the BitLocker attributes, which must be looked up in the schema
+pub struct BitlockerAttributeIds {
+    pub recovery_password: i32,
+    pub volume_guid: i32,
+    pub key_package: i32,
+}
+
+/// the RDN of a recovery information object consists of its creation time
+/// and the recovery GUID, e.g. `2023-03-14T10:25:23-08:00{063EA4E1-220C-4293-BA01-4754620A96E7}`
+fn parse_recovery_rdn(rdn: &str) -> Option<(DateTime<Utc>, Guid)> {
+    let (created, guid) = rdn.split_once('{')?;
+    let created = DateTime::parse_from_rfc3339(created).ok()?;
+    let guid = Guid::from_str(guid.strip_suffix('}')?).ok()?;
+    Some((created.with_timezone(&Utc), guid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_recovery_rdn;
+
+    #[test]
+    fn test_parse_recovery_rdn() {
+        let (created, guid) =
+            parse_recovery_rdn("2023-03-14T10:25:23-08:00{063EA4E1-220C-4293-BA01-4754620A96E7}")
+                .unwrap();
+        assert_eq!(created.to_rfc3339(), "2023-03-14T18:25:23+00:00");
+        assert_eq!(guid.to_string(), "063ea4e1-220c-4293-ba01-4754620a96e7");
+
+        assert!(parse_recovery_rdn("Computers").is_none());
+    }
+}
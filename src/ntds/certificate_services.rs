@@ -0,0 +1,607 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use getset::Getters;
+use sddl::{AccessMask, Ace, AceHeaderFlags};
+use serde::Serialize;
+
+use crate::crypto::Certificate;
+use crate::win32_types::{
+    CertificateNameFlags, EnrollmentFlags, Sid, TimeInterval, TruncatedWindowsFileTime,
+    WindowsFileTime,
+};
+use crate::{pseudonymize_dns_name, pseudonymize_name, redact_free_text};
+
+use crate::object_tree_entry::ObjectTreeEntry;
+
+use super::{DataTable, DataTableRecord};
+
+/// the extended rights which allow to request certificates
+const ENROLLMENT_RIGHTS: &[&str] = &[
+    "0e10c968-78fb-11d2-90d4-00c04f79dc55", // Certificate-Enrollment
+    "a05b8cc2-17bc-4802-a710-e7c15ab866a2", // Certificate-AutoEnrollment
+];
+
+/// the attributes of a certificate template which can be changed to make it
+/// vulnerable to ESC1 to ESC3 (`schemaIDGUID`s)
+const ABUSABLE_TEMPLATE_ATTRIBUTES: &[&str] = &[
+    "18976af6-3b9e-11d2-90cc-00c04fd91ab1", // pKIExtendedKeyUsage
+    "ea1dddc4-60ff-416e-8cc0-17cee534bce7", // msPKI-Certificate-Name-Flag
+    "d15ef7d8-f226-46db-ae79-b34e560bd12c", // msPKI-Enrollment-Flag
+    "fe17e04b-937d-4f7e-8e0e-9292c8d5683e", // msPKI-RA-Signature
+    "dbd90548-aa37-4202-9966-8c537ba5ce32", // msPKI-Certificate-Application-Policy
+    "3c91fbbf-4773-4ccd-a87b-85d53e7bcf6a", // msPKI-RA-Application-Policies
+];
+
+const EKU_ANY_PURPOSE: &str = "2.5.29.37.0";
+const EKU_CERTIFICATE_REQUEST_AGENT: &str = "1.3.6.1.4.1.311.20.2.1";
+
+/// the extended key usages which allow to authenticate using Kerberos
+/// (PKINIT) or Schannel
+const AUTHENTICATION_EKUS: &[&str] = &[
+    "1.3.6.1.5.5.7.3.2",      // Client Authentication
+    "1.3.6.1.5.2.3.4",        // PKINIT Client Authentication
+    "1.3.6.1.4.1.311.20.2.2", // Smart Card Logon
+    EKU_ANY_PURPOSE,
+];
+
+const EKU_NAMES: &[(&str, &str)] = &[
+    ("1.3.6.1.5.5.7.3.1", "Server Authentication"),
+    ("1.3.6.1.5.5.7.3.2", "Client Authentication"),
+    ("1.3.6.1.5.5.7.3.3", "Code Signing"),
+    ("1.3.6.1.5.5.7.3.4", "Secure Email"),
+    ("1.3.6.1.5.5.7.3.8", "Time Stamping"),
+    ("1.3.6.1.5.5.7.3.9", "OCSP Signing"),
+    ("1.3.6.1.5.2.3.4", "PKINIT Client Authentication"),
+    ("1.3.6.1.5.2.3.5", "KDC Authentication"),
+    ("1.3.6.1.4.1.311.10.3.4", "Encrypting File System"),
+    ("1.3.6.1.4.1.311.10.3.4.1", "File Recovery"),
+    ("1.3.6.1.4.1.311.20.2.1", "Certificate Request Agent"),
+    ("1.3.6.1.4.1.311.20.2.2", "Smart Card Logon"),
+    ("1.3.6.1.4.1.311.21.5", "Private Key Archival"),
+    ("1.3.6.1.4.1.311.21.6", "Key Recovery Agent"),
+    ("2.5.29.37.0", "Any Purpose"),
+];
+
+/// names of well-known principals, which have no object in the database
+const WELL_KNOWN_PRINCIPALS: &[(&str, &str)] = &[
+    ("S-1-1-0", "Everyone"),
+    ("S-1-3-0", "Creator Owner"),
+    ("S-1-5-7", "Anonymous Logon"),
+    ("S-1-5-9", "Enterprise Domain Controllers"),
+    ("S-1-5-10", "Self"),
+    ("S-1-5-11", "Authenticated Users"),
+    ("S-1-5-18", "Local System"),
+];
+
+/// RIDs of domain groups which contain all users or computers
+const LOW_PRIVILEGED_RIDS: &[u32] = &[
+    513, // Domain Users
+    514, // Domain Guests
+    515, // Domain Computers
+];
+
+/// returns `true` if (nearly) every account is a member of `sid`, such as
+/// `Authenticated Users` or `Domain Users`
+pub fn is_low_privileged(sid: &Sid) -> bool {
+    if sid.is_domain_sid() {
+        return LOW_PRIVILEGED_RIDS.contains(sid.get_rid());
+    }
+    ["S-1-1-0", "S-1-5-7", "S-1-5-11", "S-1-5-32-545", "S-1-5-32-546"]
+        .iter()
+        .any(|low_privileged| sid.to_string() == *low_privileged)
+}
+
+/// returns the name of a well-known principal, such as `Everyone`
+pub fn well_known_principal(sid: &Sid) -> Option<&'static str> {
+    let sid = sid.to_string();
+    WELL_KNOWN_PRINCIPALS
+        .iter()
+        .find(|(well_known, _)| *well_known == sid)
+        .map(|(_, name)| *name)
+}
+
+/// maps the SIDs of all security principals to their (pseudonymized)
+/// names
+fn principal_names(data_table: &DataTable) -> HashMap<Sid, String> {
+    data_table
+        .data_table()
+        .metadata()
+        .iter()
+        .filter_map(|entry| {
+            let sid = entry.sid().as_ref()?;
+            let name = entry
+                .sam_account_name()
+                .clone()
+                .unwrap_or_else(|| entry.rdn().name().to_string());
+            Some((sid.clone(), pseudonymize_name(&name).to_string()))
+        })
+        .collect()
+}
+
+/// returns the `CN=Public Key Services` container of the configuration
+/// partition
+fn public_key_services(data_table: &DataTable) -> anyhow::Result<Rc<ObjectTreeEntry>> {
+    DataTable::find_descendant(data_table.configuration()?, &["Services", "Public Key Services"])
+        .ok_or(anyhow::anyhow!("db has no `Public Key Services` entry"))
+}
+
+fn describe_eku(oid: &str) -> String {
+    EKU_NAMES
+        .iter()
+        .find(|(eku, _)| *eku == oid)
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| oid.to_string())
+}
+
+/// the principals which are allowed to enroll for or to modify a
+/// certificate template, according to its security descriptor. Only
+/// allowing ACEs are considered, so denying ACEs might further restrict the
+/// access.
+#[derive(Default)]
+pub struct TemplateAccess {
+    pub owner: Option<Sid>,
+    pub enrollment: Vec<Sid>,
+
+    /// principals which can change the settings or the permissions of the
+    /// template
+    pub write: Vec<Sid>,
+}
+
+impl From<&sddl::SecurityDescriptor> for TemplateAccess {
+    fn from(descriptor: &sddl::SecurityDescriptor) -> Self {
+        let mut access = Self {
+            owner: descriptor.owner().as_ref().map(Sid::from),
+            ..Default::default()
+        };
+        let aces = descriptor.dacl().iter().flat_map(|dacl| dacl.ace_list());
+        for ace in aces {
+            let (header, object_type, sid) = match ace {
+                Ace::ACCESS_ALLOWED_ACE { header, sid, .. } => (header, None, sid),
+                Ace::ACCESS_ALLOWED_OBJECT_ACE {
+                    header,
+                    object_type,
+                    sid,
+                    ..
+                } => (header, object_type.as_ref(), sid),
+                _ => continue,
+            };
+            if header.ace_flags().contains(AceHeaderFlags::INHERIT_ONLY_ACE) {
+                continue;
+            }
+
+            let mask = *header.mask();
+            let sid = Sid::from(sid);
+            let can_enroll = mask.contains(AccessMask::GENERIC_ALL)
+                || (mask.contains(AccessMask::CONTROL_ACCESS)
+                    && object_type.map_or(true, |object_type| {
+                        ENROLLMENT_RIGHTS.contains(&object_type.to_string().as_str())
+                    }));
+            let can_write = mask.intersects(
+                AccessMask::GENERIC_ALL
+                    | AccessMask::GENERIC_WRITE
+                    | AccessMask::WRITE_DACL
+                    | AccessMask::WRITE_OWNER,
+            ) || (mask.contains(AccessMask::WRITE_PROPERTY)
+                && object_type.map_or(true, |object_type| {
+                    ABUSABLE_TEMPLATE_ATTRIBUTES.contains(&object_type.to_string().as_str())
+                }));
+            if can_enroll && !access.enrollment.contains(&sid) {
+                access.enrollment.push(sid.clone());
+            }
+            if can_write && !access.write.contains(&sid) {
+                access.write.push(sid);
+            }
+        }
+        access
+    }
+}
+
+/// the settings of a certificate template which are relevant to find
+/// misconfigurations
+#[derive(Default)]
+pub struct TemplateSettings {
+    pub enrollment_flag: EnrollmentFlags,
+    pub certificate_name_flag: CertificateNameFlags,
+    pub ra_signature: i32,
+
+    /// the application policies, or the extended key usages if the template
+    /// has no application policies
+    pub extended_key_usages: Vec<String>,
+}
+
+impl TemplateSettings {
+    /// returns the names of the well-known misconfigurations (ESC1 to ESC4)
+    /// which allow low privileged users to escalate their privileges.
+    /// Templates which are not published by any enrollment service cannot
+    /// be used to request certificates, but they can still be modified.
+    pub fn vulnerabilities(
+        &self,
+        access: &TemplateAccess,
+        is_published: bool,
+    ) -> Vec<&'static str> {
+        let mut vulnerabilities = Vec::new();
+        let ekus = &self.extended_key_usages;
+        let can_enroll = is_published
+            && access.enrollment.iter().any(is_low_privileged)
+            && !self
+                .enrollment_flag
+                .contains(EnrollmentFlags::CT_FLAG_PEND_ALL_REQUESTS)
+            && self.ra_signature <= 0;
+
+        // templates without EKUs can be used for any purpose
+        let allows_authentication =
+            ekus.is_empty() || ekus.iter().any(|eku| AUTHENTICATION_EKUS.contains(&eku.as_str()));
+        let allows_any_purpose = ekus.is_empty() || ekus.iter().any(|eku| eku == EKU_ANY_PURPOSE);
+
+        if can_enroll
+            && allows_authentication
+            && self
+                .certificate_name_flag
+                .contains(CertificateNameFlags::CT_FLAG_ENROLLEE_SUPPLIES_SUBJECT)
+        {
+            vulnerabilities.push("ESC1");
+        }
+        if can_enroll && allows_any_purpose {
+            vulnerabilities.push("ESC2");
+        }
+        if can_enroll && ekus.iter().any(|eku| eku == EKU_CERTIFICATE_REQUEST_AGENT) {
+            vulnerabilities.push("ESC3");
+        }
+        if access.write.iter().any(is_low_privileged)
+            || access.owner.as_ref().is_some_and(is_low_privileged)
+        {
+            vulnerabilities.push("ESC4");
+        }
+        vulnerabilities
+    }
+}
+
+/// a certificate template (`pKICertificateTemplate` object)
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct CertificateTemplate {
+    name: String,
+    display_name: Option<String>,
+    oid: Option<String>,
+    schema_version: Option<i32>,
+
+    /// the enrollment services which publish this template
+    published_by: String,
+    enrollment_flag: Option<EnrollmentFlags>,
+    certificate_name_flag: Option<CertificateNameFlags>,
+    extended_key_usages: String,
+
+    /// the number of signatures of enrollment agents which are required
+    ra_signature: Option<i32>,
+    ra_application_policies: String,
+    minimal_key_size: Option<i32>,
+    validity_period: Option<TimeInterval>,
+    renewal_period: Option<TimeInterval>,
+    owner: Option<String>,
+    enrollment_principals: String,
+    write_principals: String,
+    vulnerabilities: String,
+    when_created: Option<TruncatedWindowsFileTime>,
+    when_changed: Option<TruncatedWindowsFileTime>,
+    is_deleted: bool,
+}
+
+impl CertificateTemplate {
+    /// reads all certificate templates, together with the names of the
+    /// enrollment services which publish them
+    pub fn read_all(data_table: &DataTable) -> anyhow::Result<Vec<Self>> {
+        let metadata = data_table.data_table().metadata();
+        let mut published_by: HashMap<String, Vec<String>> = HashMap::new();
+        for service in data_table.records_of_class("PKI-Enrollment-Service") {
+            if service.att_is_deleted_opt()?.unwrap_or(false) {
+                continue;
+            }
+            for template in service.att_certificate_templates()? {
+                published_by
+                    .entry(template)
+                    .or_default()
+                    .push(metadata[service.ptr()].rdn().name().to_string());
+            }
+        }
+
+        let principal_names = principal_names(data_table);
+        let principal_name = |sid: &Sid| match well_known_principal(sid) {
+            Some(name) => name.to_string(),
+            None => principal_names
+                .get(sid)
+                .cloned()
+                .unwrap_or_else(|| sid.to_pseudonymized_string()),
+        };
+        data_table
+            .records_of_class("PKI-Certificate-Template")
+            .map(|template| {
+                let name = metadata[template.ptr()].rdn().name();
+                let access = template
+                    .att_nt_security_descriptor_opt()?
+                    .and_then(|sd_id| data_table.sd_table().descriptor(&sd_id))
+                    .transpose()?
+                    .map(|sd| TemplateAccess::from(sd.as_ref()))
+                    .unwrap_or_default();
+                Self::from_record(
+                    &template,
+                    name,
+                    published_by.get(name).map(Vec::as_slice).unwrap_or_default(),
+                    access,
+                    principal_name,
+                )
+            })
+            .collect()
+    }
+
+    /// `principal_name` returns the (already pseudonymized) name of a
+    /// principal
+    pub fn from_record(
+        record: &DataTableRecord,
+        name: &str,
+        published_by: &[String],
+        access: TemplateAccess,
+        principal_name: impl Fn(&Sid) -> String,
+    ) -> anyhow::Result<Self> {
+        let application_policies = record.att_certificate_application_policies()?;
+        let settings = TemplateSettings {
+            enrollment_flag: record.att_enrollment_flag_opt()?.unwrap_or_default(),
+            certificate_name_flag: record.att_certificate_name_flag_opt()?.unwrap_or_default(),
+            ra_signature: record.att_ra_signature_opt()?.unwrap_or(0),
+            extended_key_usages: if application_policies.is_empty() {
+                record.att_extended_key_usages()?
+            } else {
+                application_policies
+            },
+        };
+        let is_deleted = record.att_is_deleted_opt()?.unwrap_or(false);
+        let vulnerabilities = if is_deleted {
+            Vec::new()
+        } else {
+            settings.vulnerabilities(&access, !published_by.is_empty())
+        };
+        let principals = |sids: &[Sid]| {
+            sids.iter()
+                .map(&principal_name)
+                .collect::<Vec<_>>()
+                .join("; ")
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            display_name: record.att_display_name_opt()?,
+            oid: record.att_cert_template_oid_opt()?,
+            schema_version: record.att_template_schema_version_opt()?,
+            published_by: published_by
+                .iter()
+                .map(|ca| pseudonymize_name(ca).to_string())
+                .collect::<Vec<_>>()
+                .join("; "),
+            enrollment_flag: record.att_enrollment_flag_opt()?,
+            certificate_name_flag: record.att_certificate_name_flag_opt()?,
+            extended_key_usages: settings
+                .extended_key_usages
+                .iter()
+                .map(|eku| describe_eku(eku))
+                .collect::<Vec<_>>()
+                .join("; "),
+            ra_signature: record.att_ra_signature_opt()?,
+            ra_application_policies: record
+                .att_ra_application_policies()?
+                .iter()
+                .map(|eku| describe_eku(eku))
+                .collect::<Vec<_>>()
+                .join("; "),
+            minimal_key_size: record.att_minimal_key_size_opt()?,
+            validity_period: record.att_expiration_period_opt()?,
+            renewal_period: record.att_overlap_period_opt()?,
+            owner: access.owner.as_ref().map(&principal_name),
+            enrollment_principals: principals(&access.enrollment),
+            write_principals: principals(&access.write),
+            vulnerabilities: vulnerabilities.join("; "),
+            when_created: record.att_when_created_opt()?,
+            when_changed: record.att_when_changed_opt()?,
+            is_deleted,
+        })
+    }
+}
+
+/// a certification authority which issues certificates
+/// (`pKIEnrollmentService` object)
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct EnrollmentService {
+    name: String,
+    dns_host_name: Option<String>,
+    certificate_subject: Option<String>,
+    certificate_thumbprint: Option<String>,
+    certificate_not_after: Option<WindowsFileTime>,
+    certificate_templates: String,
+    when_created: Option<TruncatedWindowsFileTime>,
+    when_changed: Option<TruncatedWindowsFileTime>,
+    is_deleted: bool,
+}
+
+impl EnrollmentService {
+    /// reads all enrollment services, which are the certification
+    /// authorities of the forest
+    pub fn read_all(data_table: &DataTable) -> anyhow::Result<Vec<Self>> {
+        let metadata = data_table.data_table().metadata();
+        data_table
+            .records_of_class("PKI-Enrollment-Service")
+            .map(|service| Self::from_record(&service, metadata[service.ptr()].rdn().name()))
+            .collect()
+    }
+
+    pub fn from_record(record: &DataTableRecord, name: &str) -> anyhow::Result<Self> {
+        let certificate = record
+            .att_ca_certificates()?
+            .first()
+            .and_then(|value| match Certificate::try_from(&value[..]) {
+                Ok(certificate) => Some(certificate),
+                Err(why) => {
+                    log::warn!("invalid certificate of the enrollment service {name}: {why}");
+                    None
+                }
+            });
+
+        Ok(Self {
+            name: pseudonymize_name(name).to_string(),
+            dns_host_name: record
+                .att_dns_host_name_opt()?
+                .map(|name| pseudonymize_dns_name(&name).to_string()),
+            certificate_subject: certificate
+                .as_ref()
+                .map(|certificate| redact_free_text(certificate.subject()).to_string()),
+            certificate_thumbprint: certificate
+                .as_ref()
+                .map(|certificate| certificate.thumbprint().to_string()),
+            certificate_not_after: certificate
+                .as_ref()
+                .map(|certificate| WindowsFileTime::from(*certificate.not_after())),
+            certificate_templates: record.att_certificate_templates()?.join("; "),
+            when_created: record.att_when_created_opt()?,
+            when_changed: record.att_when_changed_opt()?,
+            is_deleted: record.att_is_deleted_opt()?.unwrap_or(false),
+        })
+    }
+}
+
+/// a certificate which is stored in the Public Key Services container
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct PkiCertificate {
+    /// the container of the object which contains this certificate, such
+    /// as `NTAuthCertificates` or `Certification Authorities`
+    store: &'static str,
+    object: String,
+    subject: String,
+    issuer: String,
+    serial_number: String,
+    not_before: WindowsFileTime,
+    not_after: WindowsFileTime,
+    thumbprint: String,
+}
+
+impl PkiCertificate {
+    /// reads the certificates which are stored in the Public Key Services
+    /// container: the certificates of root CAs, of intermediate CAs (`AIA`),
+    /// of enrollment services and of CAs which are allowed to issue
+    /// certificates for logon (`NTAuthCertificates`)
+    pub fn read_all(data_table: &DataTable) -> anyhow::Result<Vec<Self>> {
+        let public_key_services = public_key_services(data_table)?;
+        let mut certificates = Vec::new();
+        if let Some(nt_auth) = public_key_services.find_child_by_name("NTAuthCertificates") {
+            let record = data_table.data_table().data_table_record_from(*nt_auth.record_ptr())?;
+            certificates.extend(Self::from_record(
+                &record,
+                "NTAuthCertificates",
+                nt_auth.name().name(),
+            )?);
+        }
+        for store in ["Certification Authorities", "AIA", "Enrollment Services"] {
+            let Some(container) = public_key_services.find_child_by_name(store) else {
+                continue;
+            };
+            for object in container.children().borrow().iter() {
+                let record = data_table.data_table().data_table_record_from(*object.record_ptr())?;
+                certificates.extend(Self::from_record(&record, store, object.name().name())?);
+            }
+        }
+        Ok(certificates)
+    }
+
+    /// reads all certificates of `record`, which is stored in `store`
+    pub fn from_record(
+        record: &DataTableRecord,
+        store: &'static str,
+        object: &str,
+    ) -> anyhow::Result<Vec<Self>> {
+        let mut certificates = Vec::new();
+        for value in record.att_ca_certificates()? {
+            match Certificate::try_from(&value[..]) {
+                Ok(certificate) => certificates.push(Self {
+                    store,
+                    object: pseudonymize_name(object).to_string(),
+                    subject: redact_free_text(certificate.subject()).to_string(),
+                    issuer: redact_free_text(certificate.issuer()).to_string(),
+                    serial_number: certificate.serial_number().to_string(),
+                    not_before: WindowsFileTime::from(*certificate.not_before()),
+                    not_after: WindowsFileTime::from(*certificate.not_after()),
+                    thumbprint: certificate.thumbprint().to_string(),
+                }),
+                Err(why) => log::warn!("invalid certificate in {store}/{object}: {why}"),
+            }
+        }
+        Ok(certificates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::win32_types::{CertificateNameFlags, EnrollmentFlags, Sid};
+
+    use super::{TemplateAccess, TemplateSettings};
+
+    fn sid(sid: &str) -> Sid {
+        Sid::from_str(sid).unwrap()
+    }
+
+    #[test]
+    fn test_vulnerabilities() {
+        let domain_users = sid("S-1-5-21-1004336348-1177238915-682003330-513");
+        let domain_admins = sid("S-1-5-21-1004336348-1177238915-682003330-512");
+        let access = TemplateAccess {
+            owner: Some(domain_admins.clone()),
+            enrollment: vec![domain_users.clone()],
+            write: vec![domain_admins.clone()],
+        };
+        let mut settings = TemplateSettings {
+            certificate_name_flag: CertificateNameFlags::CT_FLAG_ENROLLEE_SUPPLIES_SUBJECT,
+            extended_key_usages: vec!["1.3.6.1.5.5.7.3.2".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(settings.vulnerabilities(&access, true), vec!["ESC1"]);
+        assert!(settings.vulnerabilities(&access, false).is_empty());
+
+        settings.enrollment_flag = EnrollmentFlags::CT_FLAG_PEND_ALL_REQUESTS;
+        assert!(settings.vulnerabilities(&access, true).is_empty());
+
+        settings.enrollment_flag = EnrollmentFlags::empty();
+        settings.extended_key_usages = Vec::new();
+        assert_eq!(settings.vulnerabilities(&access, true), vec!["ESC1", "ESC2"]);
+
+        settings.certificate_name_flag = CertificateNameFlags::empty();
+        settings.extended_key_usages = vec!["1.3.6.1.4.1.311.20.2.1".to_string()];
+        assert_eq!(settings.vulnerabilities(&access, true), vec!["ESC3"]);
+
+        let access = TemplateAccess {
+            owner: Some(domain_admins),
+            enrollment: Vec::new(),
+            write: vec![sid("S-1-5-11")],
+        };
+        assert_eq!(settings.vulnerabilities(&access, false), vec!["ESC4"]);
+    }
+
+    #[test]
+    fn test_template_access() {
+        // only writing one of the attributes which control the template
+        // settings allows to make the template vulnerable
+        let dacl = sddl::Acl::from_sddl(
+            "D:P\
+             (OA;CI;WP;bf967950-0de6-11d0-a285-00aa003049e2;\
+             e5209ca2-3bba-11d2-90cc-00c04fd91ab1;S-1-5-11)\
+             (OA;CI;WP;ea1dddc4-60ff-416e-8cc0-17cee534bce7;\
+             e5209ca2-3bba-11d2-90cc-00c04fd91ab1;S-1-1-0)\
+             (A;CI;WP;;;S-1-5-32-545)(A;CI;WD;;;S-1-5-32-546)",
+            None,
+        )
+        .unwrap();
+        let descriptor = sddl::SecurityDescriptor::new(None, None, Some(dacl), None);
+        let access = TemplateAccess::from(&descriptor);
+        let write: Vec<_> = access.write.iter().map(|sid| sid.to_string()).collect();
+        assert_eq!(write, vec!["S-1-1-0", "S-1-5-32-545", "S-1-5-32-546"]);
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::{HashMap, HashSet};
+
+use getset::Getters;
+
+/// a `classSchema` object, which defines an object class
+#[derive(Getters)]
+#[getset(get = "pub")]
+pub struct ClassSchema {
+    ldap_display_name: String,
+
+    /// the `governsID` of this class, which is used as value of `objectClass`
+    governs_id: i32,
+
+    /// the `governsID` of the superclass
+    sub_class_of: Option<i32>,
+
+    /// the `attributeID`s of all attributes which may or must be set
+    /// (`mustContain`, `mayContain`, `systemMustContain` and `systemMayContain`)
+    attributes: Vec<i32>,
+
+    /// the `governsID`s of all auxiliary classes, whose attributes can be
+    /// used by this class as well
+    auxiliary_classes: Vec<i32>,
+}
+
+impl ClassSchema {
+    pub fn new(
+        ldap_display_name: String,
+        governs_id: i32,
+        sub_class_of: Option<i32>,
+        attributes: Vec<i32>,
+        auxiliary_classes: Vec<i32>,
+    ) -> Self {
+        Self {
+            ldap_display_name,
+            governs_id,
+            sub_class_of,
+            attributes,
+            auxiliary_classes,
+        }
+    }
+}
+
+/// all classes of the schema, which allows to resolve the class hierarchy
+#[derive(Default)]
+pub struct ClassHierarchy {
+    classes: HashMap<i32, ClassSchema>,
+}
+
+impl ClassHierarchy {
+    pub fn insert(&mut self, class: ClassSchema) {
+        self.classes.insert(class.governs_id, class);
+    }
+
+    pub fn len(&self) -> usize {
+        self.classes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+
+    /// searches for a class by its `lDAPDisplayName` (case insensitive)
+    pub fn find(&self, ldap_display_name: &str) -> Option<&ClassSchema> {
+        self.classes.values().find(|class| {
+            class
+                .ldap_display_name
+                .eq_ignore_ascii_case(ldap_display_name)
+        })
+    }
+
+    /// returns the `governsID`s of the class and of all of its subclasses
+    pub fn subclasses_of(&self, governs_id: i32) -> HashSet<i32> {
+        let mut subclasses = HashSet::from([governs_id]);
+        loop {
+            let new_subclasses: Vec<_> = self
+                .classes
+                .values()
+                .filter(|class| !subclasses.contains(&class.governs_id))
+                .filter(|class| {
+                    class
+                        .sub_class_of
+                        .is_some_and(|superclass| subclasses.contains(&superclass))
+                })
+                .map(|class| class.governs_id)
+                .collect();
+            if new_subclasses.is_empty() {
+                return subclasses;
+            }
+            subclasses.extend(new_subclasses);
+        }
+    }
+
+    /// returns the `attributeID`s of all attributes which can be used by
+    /// objects of this class, including the attributes of its superclasses
+    /// and auxiliary classes
+    pub fn attributes_of(&self, governs_id: i32) -> Vec<i32> {
+        let mut attributes = Vec::new();
+        let mut visited = HashSet::new();
+        let mut pending = vec![governs_id];
+        while let Some(governs_id) = pending.pop() {
+            if !visited.insert(governs_id) {
+                continue;
+            }
+            if let Some(class) = self.classes.get(&governs_id) {
+                attributes.extend(class.attributes.iter().copied());
+                pending.extend(class.sub_class_of);
+                pending.extend(class.auxiliary_classes.iter().copied());
+            }
+        }
+        attributes.sort_unstable();
+        attributes.dedup();
+        attributes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClassHierarchy, ClassSchema};
+
+    #[test]
+    fn test_class_hierarchy() {
+        let mut hierarchy = ClassHierarchy::default();
+        // `top` is a subclass of itself
+        hierarchy.insert(ClassSchema::new("top".into(), 1, Some(1), vec![10], vec![]));
+        hierarchy.insert(ClassSchema::new(
+            "person".into(),
+            2,
+            Some(1),
+            vec![20],
+            vec![],
+        ));
+        hierarchy.insert(ClassSchema::new(
+            "user".into(),
+            3,
+            Some(2),
+            vec![30],
+            vec![5],
+        ));
+        hierarchy.insert(ClassSchema::new(
+            "computer".into(),
+            4,
+            Some(3),
+            vec![40],
+            vec![],
+        ));
+        hierarchy.insert(ClassSchema::new(
+            "mailRecipient".into(),
+            5,
+            Some(1),
+            vec![50, 20],
+            vec![],
+        ));
+
+        let user = hierarchy.find("User").unwrap();
+        assert_eq!(*user.governs_id(), 3);
+        assert_eq!(hierarchy.subclasses_of(3).len(), 2);
+        assert_eq!(hierarchy.subclasses_of(1).len(), 5);
+        assert_eq!(hierarchy.attributes_of(3), vec![10, 20, 30, 50]);
+        assert_eq!(hierarchy.attributes_of(4), vec![10, 20, 30, 40, 50]);
+    }
+}
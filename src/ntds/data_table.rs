@@ -1,10 +1,18 @@
+use std::cell::OnceCell;
 use std::collections::{HashMap, HashSet};
-use std::io::{stdout, Write};
+use std::fs::File;
+use std::io::{stdout, BufWriter, Write};
+use std::path::Path;
 use std::rc::Rc;
+use std::str::FromStr;
 
-use crate::cache::{RecordPointer, SpecialRecords};
+use crate::cache::{RecordId, RecordPointer, SpecialRecords};
 use crate::cli::output::Writer;
-use crate::cli::{EntryFormat, MemberOfAttribute, OutputFormat, OutputOptions, TimelineFormat};
+use crate::cli::{
+    AdcsObjects, EntryFormat, ExportOptions, MemberOfAttribute, OutputFormat, OutputOptions,
+    TimelineFormat, TopologyObjects,
+};
+use crate::crypto::{BackupKey, BootKey, GroupKeyIdentifier, KdfHash, KdsRootKey, PekList};
 use crate::membership_serialization::{CsvSerialization, SerializationType};
 use crate::ntds::DataTableRecord;
 use crate::ntds::FromDataTable;
@@ -12,21 +20,35 @@ use crate::ntds::LinkTable;
 use crate::ntds::NtdsAttributeId;
 use crate::ntds::Result;
 use crate::object_tree::ObjectTree;
+use crate::object_tree_entry::ObjectTreeEntry;
 use crate::progress_bar::create_progressbar;
-use crate::{cache, member_of_attribute, EntryId};
-use crate::{ntds, FormattedValue};
+use crate::win32_types::{DnsRecord, GpLink, GpLinkEntry, Guid, ReplicationMetadata};
+use crate::{
+    cache, is_redaction_enabled, member_of_attribute, pseudonymize_dn, pseudonymize_name, EntryId,
+};
+use crate::{ntds, redact_secret, FormattedValue};
 use bodyfile::Bodyfile3Line;
 use flow_record::prelude::Serializer;
-use getset::Getters;
+use getset::{Getters, Setters};
 use maplit::hashset;
 use regex::Regex;
 use serde_json::json;
 
-use super::{Computer, Group, ObjectType, Person, Schema, SdTable};
+use super::{
+    audit_passwords, blocks_gpo_inheritance, describe_gp_link, dns_partition_of, find_usn_rollbacks,
+    global_security_groups_of, gpo_guid, site_of_server, write_zone_file, zone_file_name,
+    AccountCredentials, AccountHashes, Anomaly, AnomalyKind, AuditedAccount, BitlockerAttributeIds,
+    BitlockerRecovery, CertificateTemplate, ClassAttribute, ClassHierarchy, ClassObject,
+    ClassSchema, Computer, DnsResourceRecord, DomainController, DomainNames, DomainSummary,
+    DsaResolver, EnrollmentService, GenericObject, GmsaPassword, Group, GroupMembership,
+    GroupPolicyObject, KnownPasswords, LapsCredentials, LsaSecret, ObjectTimestamps, ObjectType,
+    OrganizationalUnit, PasswordPolicies, PasswordPolicy, Person, PkiCertificate, Schema, SdTable,
+    ShadowCredential, Site, SiteLink, SiteServer, Subnet, TrustedDomain, PRIVILEGED_GROUP_RIDS,
+};
 
 /// wraps a ESEDB Table.
 /// This class assumes the a NTDS datatable is being wrapped
-#[derive(Getters)]
+#[derive(Getters, Setters)]
 #[getset(get = "pub")]
 pub struct DataTable<'info, 'db> {
     data_table: cache::DataTable<'info, 'db>,
@@ -37,6 +59,19 @@ pub struct DataTable<'info, 'db> {
     sd_table: Rc<SdTable>,
     schema: Schema,
     special_records: SpecialRecords,
+
+    /// the decrypted password encryption keys, if a boot key has been provided
+    #[getset(get = "pub", set = "pub")]
+    pek_list: Option<PekList>,
+
+    #[getset(skip)]
+    kds_root_keys: OnceCell<HashMap<Guid, KdsRootKey>>,
+
+    #[getset(skip)]
+    dsa_resolver: OnceCell<DsaResolver>,
+
+    #[getset(skip)]
+    password_policies: OnceCell<PasswordPolicies>,
 }
 
 impl<'info, 'db> DataTable<'info, 'db> {
@@ -58,9 +93,51 @@ impl<'info, 'db> DataTable<'info, 'db> {
             sd_table,
             schema,
             special_records,
+            pek_list: None,
+            kds_root_keys: OnceCell::new(),
+            dsa_resolver: OnceCell::new(),
+            password_policies: OnceCell::new(),
         })
     }
 
+    /// reads the encrypted PEK list from the domain object and decrypts it
+    /// using the boot key of the domain controller
+    pub fn decrypt_pek_list(&self, boot_key: &BootKey) -> anyhow::Result<PekList> {
+        let domain_root = self
+            .data_table()
+            .data_table_record_from(*self.special_records().domain_root().record_ptr())?;
+
+        let encrypted = match domain_root.att_pek_list_opt()? {
+            Some(encrypted) => encrypted,
+            None => {
+                log::warn!("the domain object has no PEK list, searching all records");
+                self.data_table()
+                    .iter()
+                    .find_map(|record| record.att_pek_list_opt().ok().flatten())
+                    .ok_or(anyhow::anyhow!("this database contains no PEK list"))?
+            }
+        };
+
+        let pek_list = PekList::decrypt(&encrypted, boot_key)?;
+        log::info!("decrypted {} password encryption keys", pek_list.keys().len());
+        Ok(pek_list)
+    }
+
+    /// returns the decrypted PEK list, or an error if no boot key has been specified
+    pub fn require_pek_list(&self) -> anyhow::Result<&PekList> {
+        self.pek_list().as_ref().ok_or(anyhow::anyhow!(
+            "this requires the boot key; please specify `--system` or `--bootkey`"
+        ))
+    }
+
+    pub fn show_pek_list(&self) -> anyhow::Result<()> {
+        let pek_list = self.require_pek_list()?;
+        for (index, pek) in pek_list.keys().iter().enumerate() {
+            println!("{index}: {}", redact_secret(&pek.to_string()));
+        }
+        Ok(())
+    }
+
     fn find_type_record(
         &'db self,
         object_type: ObjectType,
@@ -109,6 +186,1069 @@ impl<'info, 'db> DataTable<'info, 'db> {
         Ok(type_records)
     }
 
+    /// iterates over all user, computer and trust accounts, including deleted accounts
+    pub fn account_records(&self) -> impl Iterator<Item = DataTableRecord<'info, 'db>> + '_ {
+        self.data_table()
+            .metadata()
+            .iter()
+            .filter(|e| e.sid().is_some() && e.sam_account_name().is_some())
+            .filter_map(|e| self.data_table().data_table_record_from(*e.record_ptr()).ok())
+            .filter(|record| record.is_account().unwrap_or(false))
+    }
+
+    /// returns the password hashes of all user, computer and trust accounts
+    pub fn account_hashes(&self) -> anyhow::Result<Vec<AccountHashes>> {
+        let pek_list = self.require_pek_list()?;
+        let mut accounts = Vec::new();
+        for record in self.account_records() {
+            let dn = self.object_tree().dn_of(record.ptr());
+            match AccountHashes::from_record(&record, pek_list, dn) {
+                Ok(hashes) => accounts.push(hashes),
+                Err(why) => log::warn!("unable to decrypt the hashes of {}: {why}", record.ptr()),
+            }
+        }
+        Ok(accounts)
+    }
+
+    pub fn show_hashes(&self, format: Option<OutputFormat>) -> anyhow::Result<()> {
+        let accounts = self.account_hashes()?;
+        match format {
+            None => {
+                for account in accounts {
+                    println!("{account}");
+                }
+                Ok(())
+            }
+            Some(format) => format.write_records(accounts.into_iter()),
+        }
+    }
+
+    /// writes the NT and LM hashes into `output_dir`, using one file per hash type
+    pub fn export_hashes(&self, options: &ExportOptions, output_dir: &Path) -> anyhow::Result<()> {
+        let format = options.format();
+        let nt_path = output_dir.join(format.nt_file_name());
+        let lm_path = output_dir.join(format.lm_file_name());
+        let mut nt_file = BufWriter::new(File::create(&nt_path)?);
+        let mut lm_file = BufWriter::new(File::create(&lm_path)?);
+        let mut nt_count = 0;
+        let mut lm_count = 0;
+
+        for account in self.account_hashes()? {
+            if account.is_excluded(options) {
+                continue;
+            }
+            for line in account.nt_lines(options) {
+                writeln!(nt_file, "{line}")?;
+                nt_count += 1;
+            }
+            for line in account.lm_lines(options) {
+                writeln!(lm_file, "{line}")?;
+                lm_count += 1;
+            }
+        }
+
+        nt_file.flush()?;
+        lm_file.flush()?;
+        log::info!("wrote {nt_count} NT hashes to {}", nt_path.display());
+        log::info!("wrote {lm_count} LM hashes to {}", lm_path.display());
+        Ok(())
+    }
+
+    /// checks if an account is privileged, which is the case if it has
+    /// `adminCount` set or if it is a (possibly indirect) member of a
+    /// privileged group
+    fn is_privileged(&self, record: &DataTableRecord) -> anyhow::Result<bool> {
+        if record.att_admin_count_opt()?.unwrap_or(0) > 0
+            || *record.att_object_sid()?.get_rid() == 500
+        {
+            return Ok(true);
+        }
+        if let Some(primary_group_id) = record.att_primary_group_id_opt()? {
+            if PRIVILEGED_GROUP_RIDS.contains(&u32::try_from(primary_group_id)?) {
+                return Ok(true);
+            }
+        }
+
+        let mut visited = HashSet::new();
+        let mut pending = vec![*record.ptr().ds_record_id()];
+        while let Some(object_id) = pending.pop() {
+            if !visited.insert(object_id) {
+                continue;
+            }
+            if let Some(groups) = self.link_table.member_of(&object_id) {
+                for group in groups {
+                    let entry = &self.data_table().metadata()[group];
+                    if entry
+                        .sid()
+                        .as_ref()
+                        .is_some_and(|sid| PRIVILEGED_GROUP_RIDS.contains(sid.get_rid()))
+                    {
+                        return Ok(true);
+                    }
+                    pending.push(*group.ds_record_id());
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    pub fn show_password_audit<T: SerializationType>(
+        &self,
+        format: OutputFormat,
+        known_passwords: &KnownPasswords,
+    ) -> anyhow::Result<()> {
+        let pek_list = self.require_pek_list()?;
+        let mut accounts = Vec::new();
+        for record in self.account_records() {
+            let dn = self.object_tree().dn_of(record.ptr());
+            let hashes = match AccountHashes::from_record(&record, pek_list, dn) {
+                Ok(hashes) => hashes,
+                Err(why) => {
+                    log::warn!("unable to decrypt the hashes of {}: {why}", record.ptr());
+                    continue;
+                }
+            };
+            let mut member_of = self
+                .link_table
+                .member_refs_of::<T>(*record.ptr().ds_record_id(), self);
+            member_of.update_dn(self.object_tree());
+            let is_privileged = self.is_privileged(&record)?;
+            accounts.push(AuditedAccount::new(hashes, member_of, is_privileged));
+        }
+
+        format.write_records(audit_passwords(&accounts, known_passwords).into_iter())
+    }
+
+    /// returns the decrypted `supplementalCredentials` of all user, computer and trust accounts
+    pub fn account_credentials(&self) -> anyhow::Result<Vec<AccountCredentials>> {
+        let pek_list = self.require_pek_list()?;
+        let mut accounts = Vec::new();
+        for record in self.account_records() {
+            let dn = self.object_tree().dn_of(record.ptr());
+            match AccountCredentials::from_record(&record, pek_list, dn) {
+                Ok(credentials) => accounts.push(credentials),
+                Err(why) => log::warn!(
+                    "unable to decrypt the supplemental credentials of {}: {why}",
+                    record.ptr()
+                ),
+            }
+        }
+        Ok(accounts)
+    }
+
+    pub fn show_supplemental_credentials(
+        &self,
+        format: Option<OutputFormat>,
+        cleartext_only: bool,
+    ) -> anyhow::Result<()> {
+        let accounts = self.account_credentials()?;
+        match format {
+            None => {
+                for account in accounts {
+                    if !cleartext_only {
+                        print!("{account}");
+                    } else if let Some(cleartext) = account.cleartext_line() {
+                        println!("{cleartext}");
+                    }
+                }
+                Ok(())
+            }
+            Some(format) => format.write_records(
+                accounts
+                    .iter()
+                    .flat_map(|account| account.entries())
+                    .filter(|entry| !cleartext_only || entry.is_cleartext()),
+            ),
+        }
+    }
+
+    /// returns the id of the schema record of the class with the common
+    /// name `class_name` (e.g. `ms-Kds-Prov-RootKey`)
+    fn class_id(&self, class_name: &str) -> Option<RecordId> {
+        let class_id = self
+            .data_table()
+            .metadata()
+            .children_of(&self.schema_record_id)
+            .find(|entry| entry.rdn().name().eq_ignore_ascii_case(class_name))
+            .map(|entry| *entry.record_ptr().ds_record_id());
+        if class_id.is_none() {
+            log::warn!("the schema contains no class named '{class_name}'");
+        }
+        class_id
+    }
+
+    /// iterates over all objects whose object category is the class with
+    /// the common name `class_name` (e.g. `ms-Kds-Prov-RootKey`)
+    pub(crate) fn records_of_class<'s>(
+        &'s self,
+        class_name: &str,
+    ) -> impl Iterator<Item = DataTableRecord<'info, 'db>> + 's {
+        let metadata = self.data_table().metadata();
+        self.class_id(class_name)
+            .into_iter()
+            .flat_map(|class_id| metadata.entries_of_type(&class_id))
+            .filter_map(|entry| self.data_table().data_table_record_from(*entry.record_ptr()).ok())
+    }
+
+    /// returns the `attributeID` of an attribute which is not known to [`NtdsAttributeId`]
+    pub(crate) fn attribute_id(&self, ldap_display_name: &str) -> anyhow::Result<i32> {
+        self.data_table()
+            .metadata()
+            .attribute_id(ldap_display_name)
+            .ok_or(anyhow::anyhow!(
+                "the schema contains no attribute named '{ldap_display_name}'"
+            ))
+    }
+
+    /// returns all (decrypted) root keys of the Group Key Distribution Service
+    pub fn kds_root_keys(&self) -> anyhow::Result<&HashMap<Guid, KdsRootKey>> {
+        if let Some(root_keys) = self.kds_root_keys.get() {
+            return Ok(root_keys);
+        }
+        let root_keys = self.read_kds_root_keys()?;
+        Ok(self.kds_root_keys.get_or_init(|| root_keys))
+    }
+
+    fn read_kds_root_keys(&self) -> anyhow::Result<HashMap<Guid, KdsRootKey>> {
+        // the KDS attributes are only available after a schema extension
+        // (Windows Server 2012), so older schemas do not contain them
+        let records: Vec<_> = self.records_of_class("ms-Kds-Prov-RootKey").collect();
+        if records.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let pek_list = self.require_pek_list()?;
+        let kdf_param = self.attribute_id("msKds-KDFParam")?;
+        let root_key_data = self.attribute_id("msKds-RootKeyData")?;
+        let secret_agreement_algorithm = self.attribute_id("msKds-SecretAgreementAlgorithmID")?;
+        let private_key_length = self.attribute_id("msKds-PrivateKeyLength")?;
+
+        // a single corrupt root key must not prevent using the other ones
+        let read_root_key = |record: &DataTableRecord, id: Guid| -> anyhow::Result<_> {
+            let kdf_hash = KdfHash::from_kdf_param(
+                record
+                    .get_value_by_attribute_id_opt::<Vec<u8>>(kdf_param)?
+                    .as_deref(),
+            )?;
+            let key_data = match record.get_value_by_attribute_id_opt::<Vec<u8>>(root_key_data)? {
+                Some(encrypted) => pek_list.decrypt_secret(&encrypted)?,
+                None => anyhow::bail!("the root key has no key data"),
+            };
+            let mut root_key = KdsRootKey::new(id, kdf_hash, key_data);
+            if let Some(algorithm) =
+                record.get_value_by_attribute_id_opt::<String>(secret_agreement_algorithm)?
+            {
+                root_key.set_secret_agreement_algorithm(algorithm);
+            }
+            if let Some(length) = record.get_value_by_attribute_id_opt::<i32>(private_key_length)? {
+                root_key.set_private_key_length(u32::try_from(length)?);
+            }
+            Ok(root_key)
+        };
+
+        let mut root_keys = HashMap::new();
+        for record in records {
+            let name = match record.att_object_name() {
+                Ok(name) => name,
+                Err(why) => {
+                    log::warn!("ignoring KDS root key {}: {why}", record.ptr());
+                    continue;
+                }
+            };
+            let id = match Guid::from_str(name.name()) {
+                Ok(id) => id,
+                Err(why) => {
+                    log::warn!("ignoring KDS root key with invalid name '{name}': {why}");
+                    continue;
+                }
+            };
+            match read_root_key(&record, id.clone()) {
+                Ok(root_key) => {
+                    root_keys.insert(id, root_key);
+                }
+                Err(why) => log::warn!("ignoring KDS root key {id}: {why}"),
+            }
+        }
+        log::info!("found {} KDS root keys", root_keys.len());
+        Ok(root_keys)
+    }
+
+    /// derives the current and the previous passwords of all group managed
+    /// service accounts
+    pub fn gmsa_passwords(&self) -> anyhow::Result<Vec<GmsaPassword>> {
+        let root_keys = self.kds_root_keys()?;
+
+        // attributes which are missing in the schema have no values
+        let metadata = self.data_table().metadata();
+        let key_id_attributes: Vec<_> = [
+            ("current", "msDS-ManagedPasswordId"),
+            ("previous", "msDS-ManagedPasswordPreviousId"),
+        ]
+        .into_iter()
+        .filter_map(|(generation, name)| {
+            let attribute_id = metadata.attribute_id(name);
+            if attribute_id.is_none() {
+                log::warn!("the schema contains no attribute named '{name}'");
+            }
+            Some((generation, attribute_id?))
+        })
+        .collect();
+
+        let mut passwords = Vec::new();
+        for record in self.records_of_class("ms-DS-Group-Managed-Service-Account") {
+            for (generation, attribute_id) in key_id_attributes.iter().copied() {
+                let key_id = record
+                    .get_value_by_attribute_id_opt::<Vec<u8>>(attribute_id)
+                    .and_then(|key_id| {
+                        key_id
+                            .map(|key_id| GroupKeyIdentifier::try_from(&key_id[..]))
+                            .transpose()
+                            .map_err(anyhow::Error::from)
+                    });
+                let key_id = match key_id {
+                    Ok(Some(key_id)) => key_id,
+                    Ok(None) => continue,
+                    Err(why) => {
+                        log::warn!(
+                            "invalid {generation} password id of {}: {why}",
+                            record.ptr()
+                        );
+                        continue;
+                    }
+                };
+                let root_key = match root_keys.get(key_id.root_key_id()) {
+                    Some(root_key) => root_key,
+                    None => {
+                        log::warn!(
+                            "unable to derive the {generation} password of {}: there is no KDS root key with id {}",
+                            record.ptr(),
+                            key_id.root_key_id()
+                        );
+                        continue;
+                    }
+                };
+                let dn = self.object_tree().dn_of(record.ptr());
+                match GmsaPassword::from_record(&record, generation, &key_id, root_key, dn) {
+                    Ok(password) => passwords.push(password),
+                    Err(why) => log::warn!(
+                        "unable to derive the {generation} password of {}: {why}",
+                        record.ptr()
+                    ),
+                }
+            }
+        }
+        Ok(passwords)
+    }
+
+    pub fn show_gmsa_passwords(&self, format: Option<OutputFormat>) -> anyhow::Result<()> {
+        let passwords = self.gmsa_passwords()?;
+        match format {
+            None => {
+                for password in passwords {
+                    print!("{password}");
+                }
+                Ok(())
+            }
+            Some(format) => format.write_records(passwords.into_iter()),
+        }
+    }
+
+    /// reads all BitLocker recovery information objects, together with the
+    /// computers they belong to
+    pub fn bitlocker_recovery_information(&self) -> anyhow::Result<Vec<BitlockerRecovery>> {
+        // the BitLocker attributes are only available after a schema
+        // extension (Windows Server 2008), so older schemas do not contain them
+        let records: Vec<_> = self.records_of_class("ms-FVE-RecoveryInformation").collect();
+        if records.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let attribute_ids = BitlockerAttributeIds {
+            recovery_password: self.attribute_id("msFVE-RecoveryPassword")?,
+            volume_guid: self.attribute_id("msFVE-VolumeGuid")?,
+            key_package: self.attribute_id("msFVE-KeyPackage")?,
+        };
+
+        let mut recovery_information = Vec::new();
+        for record in records {
+            let computer = self.object_tree().parent_of(record.ptr());
+            let computer_dn = computer
+                .as_ref()
+                .map(|entry| entry.distinguished_name().clone());
+            let computer = computer
+                .map(|entry| self.data_table().data_table_record_from(*entry.record_ptr()))
+                .transpose()?;
+            recovery_information.push(BitlockerRecovery::from_record(
+                &record,
+                computer.as_ref(),
+                computer_dn,
+                &attribute_ids,
+            )?);
+        }
+        Ok(recovery_information)
+    }
+
+    pub fn show_bitlocker_recovery_information(&self, format: OutputFormat) -> anyhow::Result<()> {
+        let recovery_information = self.bitlocker_recovery_information()?;
+        format.write_records(recovery_information.into_iter())
+    }
+
+    /// decrypts all LSA secrets which are stored below `CN=System`, such as
+    /// the DPAPI domain backup keys
+    pub fn lsa_secrets(&self) -> anyhow::Result<Vec<LsaSecret>> {
+        let pek_list = self.require_pek_list()?;
+        let class_id = self
+            .class_id("Secret")
+            .ok_or(anyhow::anyhow!("the schema contains no class for secrets"))?;
+        let system = self
+            .special_records()
+            .domain_root()
+            .find_child_by_name("System")
+            .ok_or(anyhow::anyhow!("db has no `System` entry"))?;
+
+        let metadata = self.data_table().metadata();
+        let mut secrets = Vec::new();
+        for entry in system.children().borrow().iter() {
+            if metadata[entry.record_ptr()].object_category() != &Some(class_id) {
+                continue;
+            }
+            let record = self.data_table().data_table_record_from(*entry.record_ptr())?;
+            let distinguished_name = Some(entry.distinguished_name().clone());
+            match LsaSecret::from_record(&record, pek_list, distinguished_name) {
+                Ok(secret) => secrets.push(secret),
+                Err(why) => log::warn!("unable to decrypt the secret {}: {why}", record.ptr()),
+            }
+        }
+        secrets.sort_by(|a, b| a.name().cmp(b.name()));
+        Ok(secrets)
+    }
+
+    pub fn show_lsa_secrets(&self, format: OutputFormat) -> anyhow::Result<()> {
+        format.write_records(self.lsa_secrets()?.into_iter())
+    }
+
+    /// writes the DPAPI domain backup keys to `output_dir`: RSA keys as
+    /// `.pvk` and `.pem` files (together with the certificate as `.der`
+    /// file), and legacy keys as `.key` files
+    pub fn export_backup_keys(&self, output_dir: &Path) -> anyhow::Result<()> {
+        if is_redaction_enabled() {
+            anyhow::bail!("the backup keys cannot be exported if `--redact` is specified");
+        }
+        for secret in self.lsa_secrets()? {
+            let (id, value) = match (secret.backup_key_id(), secret.current_value()) {
+                (Some(id), Some(value)) => (id, value),
+                _ => continue,
+            };
+            let key = match BackupKey::try_from(&value[..]) {
+                Ok(key) => key,
+                Err(why) => {
+                    log::warn!("unable to export the backup key {id}: {why}");
+                    continue;
+                }
+            };
+            match key {
+                BackupKey::Rsa(key) => {
+                    let pem = match key.to_pem() {
+                        Ok(pem) => pem,
+                        Err(why) => {
+                            log::warn!("unable to export the backup key {id}: {why}");
+                            continue;
+                        }
+                    };
+                    let base_name = format!("ntds_capi_{id}");
+                    std::fs::write(output_dir.join(format!("{base_name}.pvk")), key.to_pvk())?;
+                    std::fs::write(output_dir.join(format!("{base_name}.pem")), pem)?;
+                    std::fs::write(
+                        output_dir.join(format!("{base_name}.der")),
+                        key.certificate(),
+                    )?;
+                    log::info!("exported the RSA backup key {id}");
+                }
+                BackupKey::Legacy(key) => {
+                    std::fs::write(output_dir.join(format!("ntds_legacy_{id}.key")), key)?;
+                    log::info!("exported the legacy backup key {id}");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// returns the DNS name of this domain, which is derived from the `DC=`
+    /// components of the domain root
+    pub fn domain_dns_name(&self) -> String {
+        dns_name_of(self.special_records().domain_root().distinguished_name())
+    }
+
+    /// returns the `CN=Configuration` partition
+    pub(crate) fn configuration(&self) -> anyhow::Result<Rc<ObjectTreeEntry>> {
+        self.special_records()
+            .domain_root()
+            .find_child_by_name("Configuration")
+            .ok_or(anyhow::anyhow!("db has no `Configuration` entry"))
+    }
+
+    /// returns the entry which is found by following the common names in
+    /// `path`, starting at `entry`
+    pub(crate) fn find_descendant(
+        entry: Rc<ObjectTreeEntry>,
+        path: &[&str],
+    ) -> Option<Rc<ObjectTreeEntry>> {
+        path.iter()
+            .try_fold(entry, |entry, name| entry.find_child_by_name(name))
+    }
+
+    /// returns `true` if the optional feature with the common name `feature`
+    /// (e.g. `Recycle Bin Feature`) is enabled in the whole forest
+    fn is_optional_feature_enabled(&self, partitions: &DataTableRecord, feature: &str) -> bool {
+        let metadata = self.data_table().metadata();
+        self.link_table
+            .forward_links_of("ms-DS-Enabled-Feature", partitions.ptr().ds_record_id())
+            .iter()
+            .any(|ptr| metadata[ptr].rdn().name() == feature)
+    }
+
+    /// reads the configuration and the default password policy of the
+    /// domain, together with some settings of the forest
+    pub fn domain_summary(&self) -> anyhow::Result<DomainSummary> {
+        let domain_root = self.special_records().domain_root();
+        let domain = self
+            .data_table()
+            .data_table_record_from(*domain_root.record_ptr())?;
+        let configuration = self.configuration()?;
+        let record_of = |entry: Option<Rc<ObjectTreeEntry>>| {
+            entry
+                .map(|entry| self.data_table().data_table_record_from(*entry.record_ptr()))
+                .transpose()
+        };
+
+        let partitions = record_of(configuration.find_child_by_name("Partitions"))?;
+        if partitions.is_none() {
+            log::warn!("db has no `Partitions` entry");
+        }
+        let directory_service = record_of(Self::find_descendant(
+            Rc::clone(&configuration),
+            &["Services", "Windows NT", "Directory Service"],
+        ))?;
+        if directory_service.is_none() {
+            log::warn!("db has no `Directory Service` entry");
+        }
+
+        // the NetBIOS name is stored in the `crossRef` object which refers
+        // to the domain partition
+        let mut netbios_name = None;
+        if let Some(partitions) = &partitions {
+            for ptr in self.data_table().metadata().children_ptr_of(partitions.ptr()) {
+                let cross_ref = self.data_table().data_table_record_from(*ptr)?;
+                let nc_name = cross_ref.att_nc_name_opt()?;
+                if nc_name.as_ref() == Some(domain_root.record_ptr().ds_record_id()) {
+                    netbios_name = cross_ref.att_netbios_name_opt()?;
+                    break;
+                }
+            }
+        }
+
+        let deleted_object_lifetime = match (
+            &directory_service,
+            self.data_table()
+                .metadata()
+                .attribute_id("msDS-DeletedObjectLifetime"),
+        ) {
+            (Some(record), Some(attribute_id)) => {
+                record.get_value_by_attribute_id_opt::<i32>(attribute_id)?
+            }
+            _ => None,
+        };
+        let recycle_bin_enabled = partitions.as_ref().is_some_and(|partitions| {
+            self.is_optional_feature_enabled(partitions, "Recycle Bin Feature")
+        });
+
+        DomainSummary::from_records(
+            &domain,
+            partitions.as_ref(),
+            directory_service.as_ref(),
+            DomainNames {
+                dns_name: self.domain_dns_name(),
+                forest_name: dns_name_of(configuration.distinguished_name()),
+                netbios_name,
+            },
+            deleted_object_lifetime,
+            recycle_bin_enabled,
+        )
+    }
+
+    pub fn show_domain_summary(&self, format: OutputFormat) -> anyhow::Result<()> {
+        format.write_records(std::iter::once(self.domain_summary()?))
+    }
+
+    /// returns the default password policy of the domain, together with all
+    /// fine-grained password policies
+    pub fn password_policies(&self) -> anyhow::Result<&PasswordPolicies> {
+        if let Some(policies) = self.password_policies.get() {
+            return Ok(policies);
+        }
+        let policies = PasswordPolicies::read(self)?;
+        Ok(self.password_policies.get_or_init(|| policies))
+    }
+
+    /// returns the password policy which applies to the account `record`
+    pub fn effective_password_policy(
+        &self,
+        record: &DataTableRecord,
+    ) -> anyhow::Result<&PasswordPolicy> {
+        Ok(self
+            .password_policies()?
+            .effective_policy(
+                record.ptr().ds_record_id(),
+                &global_security_groups_of(self, record),
+            ))
+    }
+
+    pub fn show_password_policies(&self, format: OutputFormat) -> anyhow::Result<()> {
+        format.write_records(self.password_policies()?.iter())
+    }
+
+    /// reads all trust relationships, and decrypts the trust keys if a boot
+    /// key has been provided
+    pub fn trusted_domains(&self) -> anyhow::Result<Vec<TrustedDomain>> {
+        let realm = self.domain_dns_name();
+        if self.pek_list().is_none() {
+            log::warn!("no boot key was given, so the trust keys cannot be decrypted");
+        }
+        self.records_of_class("Trusted-Domain")
+            .map(|record| TrustedDomain::from_record(&record, self.pek_list().as_ref(), &realm))
+            .collect()
+    }
+
+    pub fn show_trusted_domains(&self, format: OutputFormat) -> anyhow::Result<()> {
+        format.write_records(self.trusted_domains()?.into_iter())
+    }
+
+    /// returns the domain and all organizational units, which are the
+    /// containers GPOs can be linked to, together with their `gPLink`s
+    fn gp_link_containers(&self) -> anyhow::Result<Vec<(DataTableRecord<'info, 'db>, GpLink)>> {
+        let domain_root = self
+            .data_table()
+            .data_table_record_from(*self.special_records().domain_root().record_ptr())?;
+        std::iter::once(domain_root)
+            .chain(self.records_of_class("Organizational-Unit"))
+            .map(|record| {
+                let gp_link = record.att_gp_link_opt()?.unwrap_or_default();
+                Ok((record, gp_link))
+            })
+            .collect()
+    }
+
+    /// returns the display names of all GPOs
+    fn gpo_display_names(&self) -> anyhow::Result<HashMap<Guid, String>> {
+        let mut display_names = HashMap::new();
+        for record in self.records_of_class("Group-Policy-Container") {
+            if let (Some(guid), Some(display_name)) =
+                (gpo_guid(&record)?, record.att_display_name_opt()?)
+            {
+                display_names.insert(guid, display_name);
+            }
+        }
+        Ok(display_names)
+    }
+
+    /// returns all enabled GPO links which apply to the container `ptr`,
+    /// starting with the links of the container itself and walking up to
+    /// the domain root. `links` contains the `gPLink` and the inheritance
+    /// blocking flag of every container.
+    fn effective_gp_links<'l>(
+        &self,
+        ptr: &RecordPointer,
+        links: &HashMap<RecordPointer, (&'l GpLink, bool)>,
+    ) -> Vec<&'l GpLinkEntry> {
+        let domain_root = *self.special_records().domain_root().record_ptr();
+        let mut effective: Vec<&GpLinkEntry> = Vec::new();
+        let mut blocked = false;
+        let mut current = Some(*ptr);
+        while let Some(ptr) = current {
+            if let Some((gp_link, block_inheritance)) = links.get(&ptr) {
+                for entry in gp_link.entries() {
+                    if entry.is_disabled() || (blocked && !entry.is_enforced()) {
+                        continue;
+                    }
+                    if !effective
+                        .iter()
+                        .any(|e| e.gpo_dn().eq_ignore_ascii_case(entry.gpo_dn()))
+                    {
+                        effective.push(entry);
+                    }
+                }
+                blocked |= *block_inheritance;
+            }
+            if ptr == domain_root {
+                break;
+            }
+            current = self
+                .object_tree()
+                .parent_of(&ptr)
+                .map(|entry| *entry.record_ptr());
+        }
+        effective
+    }
+
+    /// returns the domain and all organizational units, together with the
+    /// GPOs which are linked to them and which apply to them
+    pub fn organizational_units(&self) -> anyhow::Result<Vec<OrganizationalUnit>> {
+        let display_names = self.gpo_display_names()?;
+        let containers = self.gp_link_containers()?;
+        let mut links = HashMap::new();
+        for (record, gp_link) in containers.iter() {
+            links.insert(*record.ptr(), (gp_link, blocks_gpo_inheritance(record)?));
+        }
+
+        let mut organizational_units = Vec::new();
+        for (record, gp_link) in containers.iter() {
+            let describe = |entry: &GpLinkEntry| describe_gp_link(entry, &display_names);
+            let effective_gpos = if record.att_is_deleted_opt()?.unwrap_or(false) {
+                Vec::new()
+            } else {
+                self.effective_gp_links(record.ptr(), &links)
+                    .into_iter()
+                    .map(describe)
+                    .collect()
+            };
+            organizational_units.push(OrganizationalUnit::from_record(
+                record,
+                self.object_tree().dn_of(record.ptr()),
+                gp_link.entries().iter().map(describe).collect(),
+                effective_gpos,
+            )?);
+        }
+        Ok(organizational_units)
+    }
+
+    pub fn show_organizational_units(&self, format: OutputFormat) -> anyhow::Result<()> {
+        format.write_records(self.organizational_units()?.into_iter())
+    }
+
+    /// returns all GPOs, together with the containers they are linked to
+    pub fn group_policy_objects(&self) -> anyhow::Result<Vec<GroupPolicyObject>> {
+        let mut links: HashMap<Guid, Vec<(String, GpLinkEntry)>> = HashMap::new();
+        for (record, gp_link) in self.gp_link_containers()? {
+            let distinguished_name = self
+                .object_tree()
+                .dn_of(record.ptr())
+                .unwrap_or_else(|| record.ptr().ds_record_id().to_string());
+            for entry in gp_link.entries() {
+                if let Some(guid) = entry.gpo_guid() {
+                    links
+                        .entry(guid)
+                        .or_default()
+                        .push((distinguished_name.clone(), entry.clone()));
+                }
+            }
+        }
+
+        self.records_of_class("Group-Policy-Container")
+            .map(|record| {
+                let links = match gpo_guid(&record)?.and_then(|guid| links.get(&guid)) {
+                    Some(links) => &links[..],
+                    None => &[],
+                };
+                GroupPolicyObject::from_record(
+                    &record,
+                    self.object_tree().dn_of(record.ptr()),
+                    links,
+                )
+            })
+            .collect()
+    }
+
+    pub fn show_group_policy_objects(&self, format: OutputFormat) -> anyhow::Result<()> {
+        format.write_records(self.group_policy_objects()?.into_iter())
+    }
+
+    /// returns the records of all AD-integrated DNS zones, including
+    /// tombstoned records and deleted nodes
+    pub fn dns_records(&self) -> anyhow::Result<Vec<DnsResourceRecord>> {
+        let metadata = self.data_table().metadata();
+        let mut records = Vec::new();
+        for node in self.records_of_class("Dns-Node") {
+            let zone = match metadata.record(&self.container_of(&node)?) {
+                Some(zone) => zone,
+                None => {
+                    log::warn!("the DNS node {} has no zone", node.ptr());
+                    continue;
+                }
+            };
+            let partition = dns_partition_of(&metadata.dn(zone).unwrap_or_default());
+            let name = metadata[node.ptr()].rdn().name();
+
+            for value in node.att_dns_records()? {
+                match DnsRecord::try_from(&value[..]) {
+                    Ok(record) => records.push(DnsResourceRecord::from_record(
+                        &node,
+                        zone.rdn().name(),
+                        partition,
+                        name,
+                        record,
+                    )?),
+                    Err(why) => log::warn!("invalid DNS record in {}: {why}", node.ptr()),
+                }
+            }
+        }
+        Ok(records)
+    }
+
+    pub fn show_dns_records(&self, format: OutputFormat) -> anyhow::Result<()> {
+        format.write_records(self.dns_records()?.into_iter())
+    }
+
+    /// writes one BIND zone file per DNS zone into `output_dir`. Zones with
+    /// the same name can exist in several partitions, so every partition
+    /// gets its own subdirectory.
+    pub fn export_zone_files(&self, output_dir: &Path) -> anyhow::Result<()> {
+        let records = self.dns_records()?;
+        let mut zones: HashMap<(&str, &str), Vec<&DnsResourceRecord>> = HashMap::new();
+        for record in records.iter() {
+            zones
+                .entry((record.partition(), record.zone()))
+                .or_default()
+                .push(record);
+        }
+        for ((partition, zone), records) in zones {
+            let directory = output_dir.join(partition);
+            std::fs::create_dir_all(&directory)?;
+            let path = directory.join(zone_file_name(zone));
+            let mut writer = BufWriter::new(File::create(&path)?);
+            write_zone_file(&mut writer, zone, &records)?;
+            writer.flush()?;
+            log::info!("exported {} records of zone {zone} to {}", records.len(), path.display());
+        }
+        Ok(())
+    }
+
+    /// returns the container of `record`. Deleted objects have been moved
+    /// to `CN=Deleted Objects`, so their last known parent is used instead.
+    pub(crate) fn container_of(&self, record: &DataTableRecord) -> anyhow::Result<RecordId> {
+        Ok(record
+            .att_last_known_parent_opt()?
+            .unwrap_or(*self.data_table().metadata()[record.ptr()].parent()))
+    }
+
+    /// returns the common name of the object `id`
+    pub(crate) fn name_of_record(&self, id: &RecordId) -> Option<String> {
+        self.data_table()
+            .metadata()
+            .record(id)
+            .map(|entry| entry.rdn().name().to_string())
+    }
+
+    /// returns the names of the objects which are referenced by the linked
+    /// attribute `attribute` of `record`
+    pub(crate) fn linked_names(&self, attribute: &str, record: &DataTableRecord) -> Vec<String> {
+        let metadata = self.data_table().metadata();
+        self.link_table
+            .forward_links_of(attribute, record.ptr().ds_record_id())
+            .iter()
+            .map(|ptr| metadata[ptr].rdn().name().to_string())
+            .collect()
+    }
+
+    pub fn show_topology(
+        &self,
+        objects: TopologyObjects,
+        format: OutputFormat,
+    ) -> anyhow::Result<()> {
+        match objects {
+            TopologyObjects::Sites => format.write_records(Site::read_all(self)?.into_iter()),
+            TopologyObjects::Subnets => format.write_records(Subnet::read_all(self)?.into_iter()),
+            TopologyObjects::SiteLinks => {
+                format.write_records(SiteLink::read_all(self)?.into_iter())
+            }
+            TopologyObjects::Servers => {
+                format.write_records(SiteServer::read_all(self)?.into_iter())
+            }
+        }
+    }
+
+    pub fn show_certificate_services(
+        &self,
+        objects: AdcsObjects,
+        format: OutputFormat,
+    ) -> anyhow::Result<()> {
+        match objects {
+            AdcsObjects::Templates => {
+                format.write_records(CertificateTemplate::read_all(self)?.into_iter())
+            }
+            AdcsObjects::EnrollmentServices => {
+                format.write_records(EnrollmentService::read_all(self)?.into_iter())
+            }
+            AdcsObjects::Certificates => {
+                format.write_records(PkiCertificate::read_all(self)?.into_iter())
+            }
+        }
+    }
+
+    /// returns the device ids of all registered devices (`msDS-Device`)
+    fn registered_device_ids(&self) -> anyhow::Result<HashSet<Guid>> {
+        let device_id = match self.data_table().metadata().attribute_id("msDS-DeviceID") {
+            Some(device_id) => device_id,
+            None => return Ok(HashSet::new()),
+        };
+        let mut device_ids = HashSet::new();
+        for record in self.records_of_class("ms-DS-Device") {
+            if let Some(id) = record.get_value_by_attribute_id_opt::<Guid>(device_id)? {
+                device_ids.insert(id);
+            }
+        }
+        Ok(device_ids)
+    }
+
+    /// finds all key credentials (`msDS-KeyCredentialLink`) which do not
+    /// belong to a registered device
+    pub fn shadow_credentials(&self) -> anyhow::Result<Vec<ShadowCredential>> {
+        let registered_devices = self.registered_device_ids()?;
+        log::info!("found {} registered devices", registered_devices.len());
+
+        let mut shadow_credentials = Vec::new();
+        for owner_id in self.link_table().key_credential_owners() {
+            let ptr = match self.data_table().metadata().ptr_from_id(owner_id) {
+                Some(ptr) => ptr,
+                None => continue,
+            };
+            let owner = self.data_table().data_table_record_from(*ptr)?;
+            let distinguished_name = self.object_tree().dn_of(ptr);
+            for credential in self.link_table().key_credentials_of(owner_id) {
+                if let Some(shadow_credential) = ShadowCredential::from_record(
+                    &owner,
+                    distinguished_name.clone(),
+                    &credential,
+                    &registered_devices,
+                )? {
+                    shadow_credentials.push(shadow_credential);
+                }
+            }
+        }
+        Ok(shadow_credentials)
+    }
+
+    pub fn show_shadow_credentials(&self, format: OutputFormat) -> anyhow::Result<()> {
+        format.write_records(self.shadow_credentials()?.into_iter())
+    }
+
+    /// returns all values of the `member` attribute. If `include_absent` is
+    /// set, this includes also the members which have been removed from
+    /// their group
+    pub fn group_memberships(&self, include_absent: bool) -> anyhow::Result<Vec<GroupMembership>> {
+        let metadata = self.data_table().metadata();
+        let dn_of = |ptr: &RecordPointer| {
+            metadata
+                .ptr_from_id(ptr.ds_record_id())
+                .and_then(|ptr| self.object_tree().dn_of(ptr))
+                .map(|dn| pseudonymize_dn(&dn).to_string())
+                .unwrap_or_else(|| ptr.ds_record_id().to_string())
+        };
+
+        let mut memberships = Vec::new();
+        for link in self.link_table().member_links() {
+            if link.is_absent() && !include_absent {
+                continue;
+            }
+            let member_sam_account_name = match metadata.ptr_from_id(link.member().ds_record_id()) {
+                Some(ptr) => self
+                    .data_table()
+                    .data_table_record_from(*ptr)?
+                    .att_sam_account_name_opt()?
+                    .map(|name| pseudonymize_name(&name).to_string()),
+                None => None,
+            };
+            memberships.push(GroupMembership::from_link(
+                link,
+                dn_of(link.group()),
+                dn_of(link.member()),
+                member_sam_account_name,
+                |invocation_id| self.originating_dsa(invocation_id),
+            ));
+        }
+        Ok(memberships)
+    }
+
+    pub fn show_group_memberships(
+        &self,
+        format: OutputFormat,
+        include_absent: bool,
+    ) -> anyhow::Result<()> {
+        format.write_records(self.group_memberships(include_absent)?.into_iter())
+    }
+
+    /// cross-checks the timestamps, USNs and the replication metadata of all
+    /// objects, to detect manipulations like timestomping or DCShadow
+    pub fn anomalies(&self) -> anyhow::Result<Vec<Anomaly>> {
+        let metadata = self.data_table().metadata();
+        let mut anomalies = Vec::new();
+        let mut objects = Vec::new();
+        let mut local_creations = Vec::new();
+
+        for entry in metadata.iter().filter(|entry| entry.object_category().is_some()) {
+            let timestamps = match self.object_timestamps(entry.record_ptr()) {
+                Ok(timestamps) => timestamps,
+                Err(why) => {
+                    log::warn!("unable to read the timestamps of {}: {why}", entry.record_ptr());
+                    continue;
+                }
+            };
+
+            let name = entry
+                .sam_account_name()
+                .as_ref()
+                .map(|name| pseudonymize_name(name).to_string())
+                .unwrap_or_else(|| pseudonymize_name(entry.rdn().name()).to_string());
+            let distinguished_name = self
+                .object_tree()
+                .dn_of(entry.record_ptr())
+                .map(|dn| pseudonymize_dn(&dn).to_string());
+            let record_id = entry.record_ptr().ds_record_id().inner();
+
+            for (anomaly, details) in timestamps.check() {
+                anomalies.push(Anomaly::new(
+                    anomaly,
+                    record_id,
+                    Some(name.clone()),
+                    distinguished_name.clone(),
+                    details,
+                ));
+            }
+
+            if let Some(creation) = timestamps.local_creation() {
+                local_creations.push(creation);
+                objects.push((record_id, name, distinguished_name));
+            }
+        }
+
+        for (idx, details) in find_usn_rollbacks(&local_creations) {
+            let (record_id, name, distinguished_name) = objects[idx].clone();
+            anomalies.push(Anomaly::new(
+                AnomalyKind::UsnRollback,
+                record_id,
+                Some(name),
+                distinguished_name,
+                details,
+            ));
+        }
+        Ok(anomalies)
+    }
+
+    fn object_timestamps(&self, ptr: &RecordPointer) -> anyhow::Result<ObjectTimestamps> {
+        let record = self.data_table().data_table_record_from(*ptr)?;
+        Ok(ObjectTimestamps {
+            record_time: record.ds_record_time_opt()?.map(|ts| ts.into()),
+            when_created: record.att_when_created_opt()?.map(|ts| ts.into()),
+            when_changed: record.att_when_changed_opt()?.map(|ts| ts.into()),
+            usn_created: record.att_usn_created_opt()?,
+            usn_changed: record.att_usn_changed_opt()?,
+            replication_metadata: self.replication_metadata(&record),
+        })
+    }
+
+    pub fn show_anomalies(&self, format: OutputFormat) -> anyhow::Result<()> {
+        format.write_records(self.anomalies()?.into_iter())
+    }
+
     pub fn show_users<T: SerializationType>(&self, options: &OutputOptions) -> anyhow::Result<()> {
         log::debug!("show_users()");
         self.show_typed_objects::<Person<T>>(options, ObjectType::Person)
@@ -127,6 +1267,18 @@ impl<'info, 'db> DataTable<'info, 'db> {
         self.show_typed_objects::<Computer<T>>(options, ObjectType::Computer)
     }
 
+    /// displays all computers whose local administrator password is managed
+    /// by LAPS
+    pub fn show_laps<T: SerializationType>(&self, options: &OutputOptions) -> anyhow::Result<()> {
+        log::debug!("show_laps()");
+        if self.pek_list().is_none() {
+            log::warn!("no boot key was given, so encrypted LAPS passwords cannot be decrypted");
+        }
+        self.show_filtered_objects::<Computer<T>>(options, ObjectType::Computer, |record| {
+            LapsCredentials::is_managed(record, self)
+        })
+    }
+
     pub fn show_type_names<T>(&self, options: &OutputOptions) -> anyhow::Result<()>
     where
         T: SerializationType,
@@ -159,6 +1311,116 @@ impl<'info, 'db> DataTable<'info, 'db> {
         Ok(())
     }
 
+    /// returns the domain controllers of this forest, which are found in the
+    /// `Sites` container of the configuration partition. Demoted domain
+    /// controllers are included, because the replication metadata of objects
+    /// they have changed still references their invocation ids.
+    pub fn dsa_resolver(&self) -> &DsaResolver {
+        self.dsa_resolver.get_or_init(|| {
+            let resolver = self.read_dsa_resolver();
+            log::info!("found {} domain controllers", resolver.len());
+            resolver
+        })
+    }
+
+    fn read_dsa_resolver(&self) -> DsaResolver {
+        let mut resolver = DsaResolver::default();
+        let dsas: Vec<_> = self
+            .records_of_class("NTDS-DSA")
+            .chain(self.records_of_class("NTDS-DSA-RO"))
+            .collect();
+
+        for dsa in dsas {
+            // `nTDSDSA` objects are stored in `CN=<server>,CN=Servers,CN=<site>,CN=Sites`
+            let server = match self.container_of(&dsa) {
+                Ok(server) => server,
+                Err(why) => {
+                    log::warn!("unable to find the server of DSA {}: {why}", dsa.ptr());
+                    continue;
+                }
+            };
+            let server_name = match self.name_of_record(&server) {
+                Some(name) => name,
+                None => {
+                    log::warn!("unable to find the server of DSA {}", dsa.ptr());
+                    continue;
+                }
+            };
+            let site = self
+                .data_table()
+                .metadata()
+                .ptr_from_id(&server)
+                .map(|ptr| self.data_table().data_table_record_from(*ptr))
+                .transpose()
+                .map_err(anyhow::Error::from)
+                .and_then(|server| match server {
+                    Some(server) => site_of_server(self, &server),
+                    None => Ok(None),
+                });
+            let site = match site {
+                Ok(site) => site.and_then(|site| self.name_of_record(&site)),
+                Err(why) => {
+                    log::warn!("unable to find the site of {server_name}: {why}");
+                    None
+                }
+            };
+
+            let mut invocation_ids = Vec::new();
+            match dsa.att_invocation_id_opt() {
+                Ok(invocation_id) => invocation_ids.extend(invocation_id),
+                Err(why) => log::warn!("invalid invocation id of {server_name}: {why}"),
+            }
+            match dsa.att_retired_repl_dsa_signatures_opt() {
+                Ok(Some(signatures)) => invocation_ids.extend(
+                    signatures
+                        .signatures()
+                        .iter()
+                        .map(|signature| signature.invocation_id().clone()),
+                ),
+                Ok(None) => (),
+                Err(why) => {
+                    log::warn!("invalid retired DSA signatures of {server_name}: {why}")
+                }
+            }
+
+            resolver.insert(
+                *dsa.ptr().ds_record_id(),
+                DomainController::new(
+                    pseudonymize_name(&server_name).to_string(),
+                    site.map(|site| pseudonymize_name(&site).to_string()),
+                ),
+                invocation_ids,
+            );
+        }
+        resolver
+    }
+
+    /// returns the name of the domain controller which uses (or has used)
+    /// `invocation_id`
+    pub fn originating_dsa(&self, invocation_id: &Guid) -> Option<String> {
+        self.dsa_resolver()
+            .resolve(invocation_id, self.data_table().metadata())
+            .map(|domain_controller| domain_controller.to_string())
+    }
+
+    /// reads the replication metadata of a record and resolves the attribute names
+    pub fn replication_metadata(&self, record: &DataTableRecord) -> Option<ReplicationMetadata> {
+        match record.att_repl_property_meta_data_opt() {
+            Ok(metadata) => metadata.map(|metadata| {
+                metadata
+                    .with_attribute_names(self.data_table().metadata())
+                    .with_dsa_names(|invocation_id| self.originating_dsa(invocation_id))
+            }),
+            Err(why) => {
+                log::warn!(
+                    "unable to read the replication metadata of {}: {why}",
+                    record.ptr()
+                );
+                None
+            }
+        }
+    }
+
     pub fn show_entry(&self, entry_id: EntryId, entry_format: EntryFormat) -> Result<()> {
         let record = match entry_id {
             EntryId::Id(id) => self.data_table.metadata().record(&id),
@@ -171,6 +1433,7 @@ impl<'info, 'db> DataTable<'info, 'db> {
                 let record = self
                     .data_table()
                     .data_table_record_from(*entry.record_ptr())?;
+                let replication_metadata = self.replication_metadata(&record);
 
                 match entry_format {
                     EntryFormat::Simple => {
@@ -196,26 +1459,55 @@ impl<'info, 'db> DataTable<'info, 'db> {
                             let value = all_attributes.get(header.0).unwrap();
                             println!("{: <header_width$}: {}", header.1, value.value());
                         }
+
+                        if let Some(metadata) = &replication_metadata {
+                            println!();
+                            println!("replication metadata:");
+                            let name_width = metadata
+                                .entries()
+                                .iter()
+                                .map(|entry| entry.attribute().as_ref().map_or(0, String::len))
+                                .max()
+                                .unwrap_or(0);
+                            for entry in metadata.entries() {
+                                println!(
+                                    "{: <name_width$}: version {}, changed at {} by {} (originating USN {}, local USN {})",
+                                    entry
+                                        .attribute()
+                                        .clone()
+                                        .unwrap_or_else(|| entry.attribute_id().to_string()),
+                                    entry.version(),
+                                    entry.originating_change_time(),
+                                    entry.originating_dsa_name(),
+                                    entry.originating_usn(),
+                                    entry.local_usn()
+                                );
+                            }
+                        }
                     }
                     EntryFormat::Json => {
-                        let _ = serde_json::to_writer_pretty(stdout(), &json!(record));
+                        let mut value = json!(record);
+                        if let Some(metadata) = &replication_metadata {
+                            value["replication_metadata"] = json!(metadata);
+                        }
+                        let _ = serde_json::to_writer_pretty(stdout(), &value);
                     }
                     EntryFormat::Table => {
                         let mut table = term_table::Table::from(&record);
 
                         if let Some(size) = termsize::get() {
                             let attrib_size = 20;
-                            let value_size = if size.cols > (attrib_size + 2) {
-                                size.cols - (attrib_size + 2)
-                            } else {
-                                0
-                            };
+                            let value_size = size.cols.saturating_sub(attrib_size + 2);
                             table.set_max_column_widths(vec![
                                 (0, attrib_size.into()),
                                 (1, value_size.into()),
                             ])
                         }
-                        println!("{}", table.render())
+                        println!("{}", table.render());
+
+                        if let Some(metadata) = &replication_metadata {
+                            println!("{}", term_table::Table::from(metadata).render());
+                        }
                     }
                 }
             }
@@ -285,6 +1577,16 @@ impl<'info, 'db> DataTable<'info, 'db> {
         &self,
         options: &OutputOptions,
         object_type: ObjectType,
+    ) -> anyhow::Result<()> {
+        self.show_filtered_objects::<O>(options, object_type, |_| Ok(true))
+    }
+
+    /// displays all objects of the given type for which `filter` returns `true`
+    pub fn show_filtered_objects<O: ntds::FromDataTable + ntds::IsMemberOf>(
+        &self,
+        options: &OutputOptions,
+        object_type: ObjectType,
+        filter: impl Fn(&DataTableRecord) -> anyhow::Result<bool>,
     ) -> anyhow::Result<()> {
         let type_record = self
             .find_type_record(object_type)?
@@ -292,46 +1594,204 @@ impl<'info, 'db> DataTable<'info, 'db> {
         let type_record_id = type_record.ds_record_id()?;
         log::info!("found type record with id {type_record_id}");
 
+        let records: Vec<_> = self
+            .data_table()
+            .metadata()
+            .entries_of_type(&type_record_id)
+            .map(|e| *e.record_ptr())
+            .collect();
+        self.show_objects::<O>(options, &object_type.to_string(), &records, filter)
+    }
+
+    /// displays all objects of the class with the `lDAPDisplayName`
+    /// `class_name`, including objects of its subclasses
+    pub fn show_objects_of_class<T: SerializationType>(
+        &self,
+        options: &OutputOptions,
+        class_name: &str,
+    ) -> anyhow::Result<()> {
+        let hierarchy = self.class_hierarchy()?;
+        let class = hierarchy
+            .find(class_name)
+            .ok_or(anyhow::anyhow!("the schema contains no class named '{class_name}'"))?;
+        let classes = hierarchy.subclasses_of(*class.governs_id());
+        log::info!(
+            "'{}' has {} subclasses",
+            class.ldap_display_name(),
+            classes.len() - 1
+        );
+
+        let attributes: Vec<_> = hierarchy
+            .attributes_of(*class.governs_id())
+            .into_iter()
+            .filter_map(|attribute_id| {
+                let syntax = self
+                    .data_table()
+                    .esedbinfo()
+                    .mapping()
+                    .syntax_by_attribute_id(attribute_id);
+                self.data_table()
+                    .metadata()
+                    .attribute_name(attribute_id)
+                    .and_then(|name| ClassAttribute::new(attribute_id, name, syntax))
+            })
+            .collect();
+
+        let mut records = Vec::new();
+        for entry in self
+            .data_table()
+            .metadata()
+            .iter()
+            .filter(|entry| entry.object_category().is_some())
+        {
+            let object_classes = self
+                .data_table()
+                .data_table_record_from(*entry.record_ptr())
+                .map_err(anyhow::Error::from)
+                .and_then(|record| record.att_object_classes());
+            match object_classes {
+                Ok(object_classes) => {
+                    if object_classes
+                        .iter()
+                        .any(|object_class| classes.contains(object_class))
+                    {
+                        records.push(*entry.record_ptr());
+                    }
+                }
+                Err(why) => {
+                    log::warn!("unable to read the classes of {}: {why}", entry.record_ptr());
+                }
+            }
+        }
+
+        self.show_class_objects::<T>(options, class.ldap_display_name(), &records, &attributes)
+    }
+
+    /// displays objects together with the attributes of their class. The
+    /// names of those attributes are only known at runtime, so the CSV
+    /// header cannot be derived from the type of the objects.
+    fn show_class_objects<T: SerializationType>(
+        &self,
+        options: &OutputOptions,
+        caption: &str,
+        records: &[RecordPointer],
+        attributes: &[ClassAttribute],
+    ) -> anyhow::Result<()> {
         let mut csv_wtr = csv::WriterBuilder::new()
             .flexible(false)
+            .has_headers(false)
             .from_writer(std::io::stdout());
         let bar = create_progressbar(
-            format!("loading {object_type} records"),
-            (self
-                .data_table()
-                .metadata()
-                .entries_of_type(&type_record_id)
-                .count())
-            .try_into()?,
+            format!("loading {caption} records"),
+            records.len().try_into()?,
         )?;
 
-        let mut records = Vec::new();
+        let mut objects = Vec::new();
+        let mut has_header = false;
+        for ptr in records {
+            bar.inc(1);
+            let object: GenericObject<T> =
+                self.object_from(options, self.data_table().data_table_record_from(*ptr)?)?;
+            let record = self.data_table().data_table_record_from(*ptr)?;
+            let object = ClassObject::new(&object, attributes, &record, self)?;
+
+            match options.format().unwrap() {
+                OutputFormat::Csv => {
+                    if !has_header {
+                        csv_wtr.write_record(object.header())?;
+                        has_header = true;
+                    }
+                    csv_wtr.write_record(object.csv_record())?;
+                    csv_wtr.flush()?;
+                }
+                OutputFormat::Json => {
+                    objects.push(object);
+                }
+                OutputFormat::JsonLines => {
+                    println!("{}", serde_json::to_string(&object)?);
+                }
+            }
+        }
+
+        if options.format().unwrap() == OutputFormat::Json {
+            println!("{}", serde_json::to_string_pretty(&objects)?);
+        }
+
+        bar.finish_and_clear();
+        Ok(())
+    }
 
-        for record in self
+    /// reads all `classSchema` objects
+    fn class_hierarchy(&self) -> anyhow::Result<ClassHierarchy> {
+        let mut hierarchy = ClassHierarchy::default();
+        for entry in self
             .data_table()
             .metadata()
-            .entries_of_type(&type_record_id)
-            .map(|e| self.data_table().data_table_record_from(*e.record_ptr()))
+            .children_of(&self.schema_record_id)
         {
-            let record = record?;
-            let dn = if *options.include_dn() {
-                match self.object_tree().dn_of(record.ptr()) {
-                    Some(dn) => FormattedValue::Value(dn),
-                    None => FormattedValue::NoValue,
-                }
-            } else {
-                FormattedValue::Hide
+            let record = self.data_table().data_table_record_from(*entry.record_ptr())?;
+            let governs_id = match record.att_governs_id_opt()? {
+                Some(governs_id) => governs_id,
+                None => continue,
+            };
+            let ldap_display_name = match record.att_ldap_display_name_opt()? {
+                Some(ldap_display_name) => ldap_display_name,
+                None => continue,
             };
 
-            let sd = record
-                .att_nt_security_descriptor_opt()?
-                .and_then(|sd_id| self.sd_table().descriptor(&sd_id).map(Result::unwrap));
+            let mut attributes = Vec::new();
+            for column in [
+                NtdsAttributeId::AttMustContain,
+                NtdsAttributeId::AttMayContain,
+                NtdsAttributeId::AttSystemMustContain,
+                NtdsAttributeId::AttSystemMayContain,
+            ] {
+                attributes.extend(record.get_values::<i32>(column)?);
+            }
+            let mut auxiliary_classes =
+                record.get_values::<i32>(NtdsAttributeId::AttAuxiliaryClass)?;
+            auxiliary_classes
+                .extend(record.get_values::<i32>(NtdsAttributeId::AttSystemAuxiliaryClass)?);
 
-            let mut record = O::new(record, options, self, &self.link_table, dn, sd.as_ref())?;
+            hierarchy.insert(ClassSchema::new(
+                ldap_display_name,
+                governs_id,
+                record.att_sub_class_of_opt()?,
+                attributes,
+                auxiliary_classes,
+            ));
+        }
+        log::info!("found {} classes in the schema", hierarchy.len());
+        Ok(hierarchy)
+    }
 
-            if member_of_attribute() == MemberOfAttribute::Dn {
-                record.update_membership_dn(self.object_tree());
+    fn show_objects<O: ntds::FromDataTable + ntds::IsMemberOf>(
+        &self,
+        options: &OutputOptions,
+        caption: &str,
+        records: &[RecordPointer],
+        filter: impl Fn(&DataTableRecord) -> anyhow::Result<bool>,
+    ) -> anyhow::Result<()> {
+        let mut csv_wtr = csv::WriterBuilder::new()
+            .flexible(false)
+            .from_writer(std::io::stdout());
+        let bar = create_progressbar(
+            format!("loading {caption} records"),
+            records.len().try_into()?,
+        )?;
+
+        let mut objects = Vec::new();
+
+        for record in records
+            .iter()
+            .map(|ptr| self.data_table().data_table_record_from(*ptr))
+        {
+            let record = record?;
+            bar.inc(1);
+            if !filter(&record)? {
+                continue;
             }
+            let record: O = self.object_from(options, record)?;
 
             match options.format().unwrap() {
                 OutputFormat::Csv => {
@@ -339,17 +1799,16 @@ impl<'info, 'db> DataTable<'info, 'db> {
                     csv_wtr.flush()?;
                 }
                 OutputFormat::Json => {
-                    records.push(record);
+                    objects.push(record);
                 }
                 OutputFormat::JsonLines => {
                     println!("{}", serde_json::to_string(&record)?);
                 }
             }
-            bar.inc(1);
         }
 
         if options.format().unwrap() == OutputFormat::Json {
-            println!("{}", serde_json::to_string_pretty(&records)?);
+            println!("{}", serde_json::to_string_pretty(&objects)?);
         }
 
         bar.finish_and_clear();
@@ -358,6 +1817,33 @@ impl<'info, 'db> DataTable<'info, 'db> {
         Ok(())
     }
 
+    /// creates the object which is displayed for `record`
+    fn object_from<O: ntds::FromDataTable + ntds::IsMemberOf>(
+        &self,
+        options: &OutputOptions,
+        record: DataTableRecord,
+    ) -> anyhow::Result<O> {
+        let dn = if *options.include_dn() {
+            match self.object_tree().dn_of(record.ptr()) {
+                Some(dn) => FormattedValue::Value(dn),
+                None => FormattedValue::NoValue,
+            }
+        } else {
+            FormattedValue::Hide
+        };
+
+        let sd = record
+            .att_nt_security_descriptor_opt()?
+            .and_then(|sd_id| self.sd_table().descriptor(&sd_id).map(Result::unwrap));
+
+        let mut object = O::new(record, options, self, &self.link_table, dn, sd.as_ref())?;
+
+        if member_of_attribute() == MemberOfAttribute::Dn {
+            object.update_membership_dn(self.object_tree());
+        }
+        Ok(object)
+    }
+
     fn timelines_from_supported_type(
         &self,
         record: DataTableRecord,
@@ -391,9 +1877,31 @@ impl<'info, 'db> DataTable<'info, 'db> {
                 distinguished_name,
                 None
             )?),
+            ObjectType::Object => Vec::<Bodyfile3Line>::from(GenericObject::<CsvSerialization>::new(
+                record,
+                options,
+                self,
+                link_table,
+                distinguished_name,
+                None
+            )?),
         })
     }
 
+    /// returns the replication metadata of `record`, if the timeline shall
+    /// contain attribute changes
+    fn attribute_changes_of(
+        &self,
+        options: &OutputOptions,
+        record: &DataTableRecord,
+    ) -> Option<ReplicationMetadata> {
+        if *options.show_attribute_changes() {
+            self.replication_metadata(record)
+        } else {
+            None
+        }
+    }
+
     fn show_timeline_for_records<'a, W>(
         &self,
         options: &OutputOptions,
@@ -419,6 +1927,19 @@ impl<'info, 'db> DataTable<'info, 'db> {
                 let record = r?;
                 match format {
                     TimelineFormat::Bodyfile => {
+                        let attribute_changes = match self.attribute_changes_of(options, &record) {
+                            Some(replication_metadata) => record
+                                .attribute_changes_to_bodyfile(
+                                    self.data_table().metadata(),
+                                    &replication_metadata,
+                                )
+                                .unwrap_or_else(|why| {
+                                    log::warn!("{why}");
+                                    Vec::new()
+                                }),
+                            None => Vec::new(),
+                        };
+
                         let lines = if let Some(object_type) = record.att_object_type_id_opt()? {
                             if let Some(record_type) = known_types.get(&object_type) {
                                 self.timelines_from_supported_type(
@@ -435,7 +1956,7 @@ impl<'info, 'db> DataTable<'info, 'db> {
                             record.to_bodyfile(self.data_table().metadata())?
                         };
 
-                        for line in lines.into_iter() {
+                        for line in lines.into_iter().chain(attribute_changes) {
                             println!("{line}");
                         }
                     }
@@ -444,6 +1965,20 @@ impl<'info, 'db> DataTable<'info, 'db> {
                             Ok(r) => ser.serialize(r)?,
                             Err(why) => log::warn!("{why}"),
                         }
+                        let attribute_changes = self.attribute_changes_of(options, &record);
+                        if let Some(replication_metadata) = attribute_changes {
+                            match record.attribute_changes_to_flow_records(
+                                self.data_table().metadata(),
+                                &replication_metadata,
+                            ) {
+                                Ok(changes) => {
+                                    for change in changes {
+                                        ser.serialize(change)?
+                                    }
+                                }
+                                Err(why) => log::warn!("{why}"),
+                            }
+                        }
                     }
                 }
                 Ok(())
@@ -508,6 +2043,37 @@ impl<'info, 'db> DataTable<'info, 'db> {
             .unwrap();
         }
 
+        if *options.show_membership_changes() {
+            for membership in self.group_memberships(true)? {
+                match format {
+                    TimelineFormat::Bodyfile => {
+                        for line in membership.to_bodyfile() {
+                            println!("{line}");
+                        }
+                    }
+                    TimelineFormat::Record => {
+                        for change in membership.to_flow_records() {
+                            serializer.serialize(change)?;
+                        }
+                    }
+                }
+            }
+        }
+
         Ok(())
     }
 }
+
+/// returns the DNS name which corresponds to the `DC=` components of a
+/// distinguished name
+fn dns_name_of(distinguished_name: &str) -> String {
+    distinguished_name
+        .split(',')
+        .filter_map(|component| {
+            component
+                .strip_prefix("DC=")
+                .or_else(|| component.strip_prefix("dc="))
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
@@ -4,9 +4,12 @@ use crate::ntds::{Error, NtdsAttributeId};
 use crate::value::FromValue;
 use crate::win32_types::TimelineEntry;
 use crate::win32_types::{
-    Rdn, SamAccountType, Sid, TruncatedWindowsFileTime, UserAccountControl, WindowsFileTime,
+    CertificateNameFlags, EnrollmentFlags, FunctionalLevel, GpLink, GroupType, Guid,
+    PasswordProperties, Rdn, ReplicationMetadata, RetiredDsaSignatures, SamAccountType, Sid,
+    TimeInterval, TrustAttributes, TrustDirection, TrustType, TruncatedWindowsFileTime,
+    UserAccountControl, WindowsFileTime,
 };
-use crate::ColumnInfoMapping;
+use crate::{pseudonymize_dn, pseudonymize_name, ColumnInfoMapping};
 use bodyfile::Bodyfile3Line;
 use chrono::{DateTime, Utc};
 use concat_idents::concat_idents;
@@ -19,7 +22,7 @@ use std::collections::HashMap;
 use term_table::row::Row;
 use term_table::table_cell::{Alignment, TableCell};
 
-use super::{AttributeName, AttributeValue};
+use super::{attribute_change_to_bodyfile, AttributeName, AttributeValue, NtdsAttributeChange};
 
 #[derive(Getters, Serialize)]
 #[getset(get = "pub")]
@@ -80,6 +83,51 @@ impl<'info, 'db> DataTableRecord<'info, 'db> {
             Some(v) => Ok(Some(<T>::from_value(v)?)),
         })
     }
+
+    /// reads all values of a multi-valued attribute
+    pub fn get_values<T>(&self, column: NtdsAttributeId) -> anyhow::Result<Vec<T>>
+    where
+        T: FromValue,
+    {
+        let column = *self.mapping()[column].id();
+        self.inner.with_values(column, |values| {
+            Ok(values
+                .iter()
+                .filter_map(|value| T::from_value_opt(value).transpose())
+                .collect::<crate::ntds::Result<_>>()?)
+        })
+    }
+
+    /// reads the value of an attribute which is not known to
+    /// [`NtdsAttributeId`]. If the database has no column for this
+    /// attribute, `None` is returned.
+    pub fn get_value_by_attribute_id_opt<T>(&self, attribute_id: i32) -> anyhow::Result<Option<T>>
+    where
+        T: FromValue,
+    {
+        match self.mapping().info_by_attribute_id(attribute_id) {
+            None => Ok(None),
+            Some(info) => self.inner.with_value(*info.id(), |v| match v {
+                None => Ok(None),
+                Some(v) => Ok(Some(<T>::from_value(v)?)),
+            }),
+        }
+    }
+
+    /// calls `function` with all values of an attribute which is not known
+    /// to [`NtdsAttributeId`]. If the database has no column for this
+    /// attribute, `function` is called without values.
+    pub fn with_values_by_attribute_id<T>(
+        &self,
+        attribute_id: i32,
+        mut function: impl FnMut(&[cache::Value]) -> anyhow::Result<T>,
+    ) -> anyhow::Result<T> {
+        match self.mapping().info_by_attribute_id(attribute_id) {
+            None => function(&[]),
+            Some(info) => self.inner.with_values(*info.id(), function),
+        }
+    }
+
     fn has_value<T>(&self, column: NtdsAttributeId, other: &T) -> anyhow::Result<bool>
     where
         T: FromValue + Eq,
@@ -90,6 +138,47 @@ impl<'info, 'db> DataTableRecord<'info, 'db> {
         })
     }
 
+    /// returns the `governsID`s of all classes of this object (`objectClass`)
+    pub fn att_object_classes(&self) -> anyhow::Result<Vec<i32>> {
+        self.get_values(NtdsAttributeId::AttObjectClass)
+    }
+
+    /// returns the raw values of `dnsRecord`, which must be parsed into
+    /// [`DnsRecord`](crate::win32_types::DnsRecord)s
+    pub fn att_dns_records(&self) -> anyhow::Result<Vec<Vec<u8>>> {
+        self.get_values(NtdsAttributeId::AttDnsRecord)
+    }
+
+    /// returns the extended key usages of a certificate template (`pKIExtendedKeyUsage`)
+    pub fn att_extended_key_usages(&self) -> anyhow::Result<Vec<String>> {
+        self.get_values(NtdsAttributeId::AttPkiExtendedKeyUsage)
+    }
+
+    /// returns the application policies of a certificate template
+    /// (`msPKI-Certificate-Application-Policy`), which replace the extended
+    /// key usages in templates of schema version 2 and above
+    pub fn att_certificate_application_policies(&self) -> anyhow::Result<Vec<String>> {
+        self.get_values(NtdsAttributeId::AttMsPkiCertificateApplicationPolicy)
+    }
+
+    /// returns the application policies which are required in the signatures
+    /// of enrollment agents (`msPKI-RA-Application-Policies`)
+    pub fn att_ra_application_policies(&self) -> anyhow::Result<Vec<String>> {
+        self.get_values(NtdsAttributeId::AttMsPkiRaApplicationPolicies)
+    }
+
+    /// returns the names of the certificate templates which are published by
+    /// an enrollment service (`certificateTemplates`)
+    pub fn att_certificate_templates(&self) -> anyhow::Result<Vec<String>> {
+        self.get_values(NtdsAttributeId::AttCertificateTemplates)
+    }
+
+    /// returns the DER encoded certificates of a certification authority
+    /// (`cACertificate`)
+    pub fn att_ca_certificates(&self) -> anyhow::Result<Vec<Vec<u8>>> {
+        self.get_values(NtdsAttributeId::AttCaCertificate)
+    }
+
     record_attribute!(ds_record_id, DsRecordId, RecordId);
     record_attribute!(object_category, AttObjectCategory, RecordId);
     record_attribute!(ds_parent_record_id, DsParentRecordId, RecordId);
@@ -98,6 +187,8 @@ impl<'info, 'db> DataTableRecord<'info, 'db> {
     record_attribute!(att_object_sid, AttObjectSid, Sid);
     record_attribute!(att_when_created, AttWhenCreated, TruncatedWindowsFileTime);
     record_attribute!(att_when_changed, AttWhenChanged, TruncatedWindowsFileTime);
+    record_attribute!(att_usn_created, AttUsnCreated, i64);
+    record_attribute!(att_usn_changed, AttUsnChanged, i64);
     record_attribute!(att_object_type_id, AttObjectCategory, RecordId);
     record_attribute!(att_object_name, AttCommonName, Rdn);
     record_attribute!(att_object_name2, AttRdn, Rdn);
@@ -133,7 +224,103 @@ impl<'info, 'db> DataTableRecord<'info, 'db> {
     record_attribute!(att_admin_count, AttAdminCount, i32);
     record_attribute!(att_is_deleted, AttIsDeleted, bool);
     record_attribute!(att_last_known_parent, AttLastKnownParent, RecordId);
+    record_attribute!(att_dns_tombstoned, AttDnsTombstoned, bool);
     record_attribute!(att_nt_security_descriptor, AttNtSecurityDescriptor, i64);
+    record_attribute!(att_pek_list, AttPekList, Vec<u8>);
+    record_attribute!(att_unicode_pwd, AttUnicodePwd, Vec<u8>);
+    record_attribute!(att_dbcs_pwd, AttDbcsPwd, Vec<u8>);
+    record_attribute!(att_nt_pwd_history, AttNtPwdHistory, Vec<u8>);
+    record_attribute!(att_lm_pwd_history, AttLmPwdHistory, Vec<u8>);
+    record_attribute!(att_trust_partner, AttTrustPartner, String);
+    record_attribute!(att_trust_direction, AttTrustDirection, TrustDirection);
+    record_attribute!(att_trust_type, AttTrustType, TrustType);
+    record_attribute!(att_trust_attributes, AttTrustAttributes, TrustAttributes);
+    record_attribute!(att_flat_name, AttFlatName, String);
+    record_attribute!(att_security_identifier, AttSecurityIdentifier, Sid);
+    record_attribute!(att_trust_auth_incoming, AttTrustAuthIncoming, Vec<u8>);
+    record_attribute!(att_trust_auth_outgoing, AttTrustAuthOutgoing, Vec<u8>);
+    record_attribute!(att_current_value, AttCurrentValue, Vec<u8>);
+    record_attribute!(att_prior_value, AttPriorValue, Vec<u8>);
+    record_attribute!(att_last_set_time, AttLastSetTime, WindowsFileTime);
+    record_attribute!(att_prior_set_time, AttPriorSetTime, WindowsFileTime);
+    record_attribute!(
+        att_repl_property_meta_data,
+        AttReplPropertyMetaData,
+        ReplicationMetadata
+    );
+    record_attribute!(
+        att_supplemental_credentials,
+        AttSupplementalCredentials,
+        Vec<u8>
+    );
+    record_attribute!(att_invocation_id, AttInvocationId, Guid);
+    record_attribute!(att_governs_id, AttGovernsId, i32);
+    record_attribute!(att_sub_class_of, AttSubClassOf, i32);
+    record_attribute!(
+        att_retired_repl_dsa_signatures,
+        AttRetiredReplDsaSignatures,
+        RetiredDsaSignatures
+    );
+    record_attribute!(att_display_name, AttDisplayName, String);
+    record_attribute!(att_flags, AttFlags, i32);
+    record_attribute!(att_gp_link, AttGpLink, GpLink);
+    record_attribute!(att_gp_options, AttGpOptions, i32);
+    record_attribute!(att_gpc_file_sys_path, AttGpcFileSysPath, String);
+    record_attribute!(att_version_number, AttVersionNumber, i32);
+    record_attribute!(att_behavior_version, AttMsDsBehaviorVersion, FunctionalLevel);
+    record_attribute!(att_min_pwd_length, AttMinPwdLength, i32);
+    record_attribute!(att_pwd_history_length, AttPwdHistoryLength, i32);
+    record_attribute!(att_min_pwd_age, AttMinPwdAge, TimeInterval);
+    record_attribute!(att_max_pwd_age, AttMaxPwdAge, TimeInterval);
+    record_attribute!(att_pwd_properties, AttPwdProperties, PasswordProperties);
+    record_attribute!(att_lockout_threshold, AttLockoutThreshold, i32);
+    record_attribute!(att_lockout_duration, AttLockoutDuration, TimeInterval);
+    record_attribute!(
+        att_lockout_observation_window,
+        AttLockOutObservationWindow,
+        TimeInterval
+    );
+    record_attribute!(att_machine_account_quota, AttMsDsMachineAccountQuota, i32);
+    record_attribute!(att_tombstone_lifetime, AttTombstoneLifetime, i32);
+    record_attribute!(att_nc_name, AttNcName, RecordId);
+    record_attribute!(att_object_guid, AttObjectGuid, Guid);
+    record_attribute!(att_group_type, AttGroupType, GroupType);
+    record_attribute!(att_description, AttDescription, String);
+    record_attribute!(att_location, AttLocation, String);
+    record_attribute!(att_options, AttOptions, i32);
+    record_attribute!(att_cost, AttCost, i32);
+    record_attribute!(att_repl_interval, AttReplInterval, i32);
+    record_attribute!(att_enrollment_flag, AttMsPkiEnrollmentFlag, EnrollmentFlags);
+    record_attribute!(
+        att_certificate_name_flag,
+        AttMsPkiCertificateNameFlag,
+        CertificateNameFlags
+    );
+    record_attribute!(att_ra_signature, AttMsPkiRaSignature, i32);
+    record_attribute!(att_template_schema_version, AttMsPkiTemplateSchemaVersion, i32);
+    record_attribute!(att_cert_template_oid, AttMsPkiCertTemplateOid, String);
+    record_attribute!(att_minimal_key_size, AttMsPkiMinimalKeySize, i32);
+    record_attribute!(att_expiration_period, AttPkiExpirationPeriod, TimeInterval);
+    record_attribute!(att_overlap_period, AttPkiOverlapPeriod, TimeInterval);
+    record_attribute!(att_netbios_name, AttNetbiosName, String);
+
+    /// returns `true` if this record is a user, computer or trust account
+    pub fn is_account(&self) -> anyhow::Result<bool> {
+        Ok(matches!(
+            self.att_sam_account_type_opt()?,
+            Some(SamAccountType::SAM_USER_OBJECT)
+                | Some(SamAccountType::SAM_MACHINE_ACCOUNT)
+                | Some(SamAccountType::SAM_TRUST_ACCOUNT)
+        ))
+    }
+
+    /// returns the domain part of the user principal name. This is the
+    /// same way secretsdump obtains the domain name of an account.
+    pub fn upn_suffix(&self) -> anyhow::Result<Option<String>> {
+        Ok(self
+            .att_user_principal_name_opt()?
+            .and_then(|upn| upn.rsplit_once('@').map(|(_, domain)| domain.to_string())))
+    }
 
     pub fn mapping(&self) -> &ColumnInfoMapping {
         self.inner.esedbinfo().mapping()
@@ -183,24 +370,35 @@ impl<'info, 'db> DataTableRecord<'info, 'db> {
         })
     }
 
-    pub fn to_bodyfile(&self, metadata: &MetaDataCache) -> anyhow::Result<Vec<Bodyfile3Line>> {
-        let my_name = self
-            .att_sam_account_name()
-            .or(self.att_object_name().map(|s| s.name().to_string()));
+    /// returns the name which is used to identify this record in a timeline
+    fn timeline_name(&self) -> anyhow::Result<String> {
+        self.att_sam_account_name()
+            .or(self.att_object_name().map(|s| s.name().to_string()))
+            .map(|name| pseudonymize_name(&name).to_string())
+    }
 
+    /// returns the object type, together with the information if (and from
+    /// where) this object has been deleted
+    fn object_type_caption(&self, metadata: &MetaDataCache) -> anyhow::Result<String> {
         let object_type_name = self.object_type_name(metadata)?;
-        let object_type_caption =
+        Ok(
             if let Some(last_known_parent) = self.att_last_known_parent_opt()? {
                 metadata
                     .record(&last_known_parent)
                     .and_then(|entry| metadata.dn(entry))
-                    .map(|e| format!("{object_type_name}, deleted from {e}"))
+                    .map(|e| format!("{object_type_name}, deleted from {}", pseudonymize_dn(&e)))
                     .unwrap_or(format!("deleted {object_type_name}"))
             } else if self.att_is_deleted_opt()?.unwrap_or(false) {
                 format!("deleted {object_type_name}")
             } else {
                 object_type_name
-            };
+            },
+        )
+    }
+
+    pub fn to_bodyfile(&self, metadata: &MetaDataCache) -> anyhow::Result<Vec<Bodyfile3Line>> {
+        let my_name = self.timeline_name();
+        let object_type_caption = self.object_type_caption(metadata)?;
 
         let inode = self.ptr.ds_record_id().to_string();
         if let Ok(upn) = &my_name {
@@ -241,10 +439,61 @@ impl<'info, 'db> DataTableRecord<'info, 'db> {
             Ok(Vec::new())
         }
     }
+
+    /// creates one bodyfile line for every attribute change which is
+    /// recorded in the replication metadata
+    pub fn attribute_changes_to_bodyfile(
+        &self,
+        metadata: &MetaDataCache,
+        replication_metadata: &ReplicationMetadata,
+    ) -> anyhow::Result<Vec<Bodyfile3Line>> {
+        let upn = match self.timeline_name() {
+            Ok(upn) => upn,
+            _ => return Ok(Vec::new()),
+        };
+        let object_type_caption = self.object_type_caption(metadata)?;
+        let inode = self.ptr.ds_record_id().to_string();
+
+        Ok(replication_metadata
+            .entries()
+            .iter()
+            .map(|entry| attribute_change_to_bodyfile(entry, &upn, &object_type_caption, &inode))
+            .collect())
+    }
+
+    /// creates one flow record for every attribute change which is recorded
+    /// in the replication metadata
+    pub fn attribute_changes_to_flow_records(
+        &self,
+        metadata: &MetaDataCache,
+        replication_metadata: &ReplicationMetadata,
+    ) -> anyhow::Result<Vec<NtdsAttributeChange>> {
+        let name = self.timeline_name()?;
+        let object_type = self.object_type_name(metadata)?;
+
+        Ok(replication_metadata
+            .entries()
+            .iter()
+            .map(|entry| NtdsAttributeChange {
+                name: name.clone(),
+                object_type: object_type.clone(),
+                record_id: self.ptr.ds_record_id().inner(),
+                attribute: entry
+                    .attribute()
+                    .clone()
+                    .unwrap_or_else(|| entry.attribute_id().to_string()),
+                version: *entry.version(),
+                change_time: entry.originating_change_time().into(),
+                originating_invocation_id: entry.originating_invocation_id().to_string(),
+                originating_dsa: entry.originating_dsa().clone().unwrap_or_default(),
+                originating_usn: *entry.originating_usn(),
+                local_usn: *entry.local_usn(),
+            })
+            .collect())
+    }
+
     pub fn to_flow_record(&self, metadata: &MetaDataCache) -> anyhow::Result<NtdsEntry> {
-        let name = self
-            .att_sam_account_name()
-            .or(self.att_object_name().map(|s| s.name().to_string()))?;
+        let name = self.timeline_name()?;
 
         let object_type = self.object_type_name(metadata)?;
         let deleted_from = self
@@ -253,7 +502,8 @@ impl<'info, 'db> DataTableRecord<'info, 'db> {
                 metadata
                     .record(&last_known_parent)
                     .and_then(|entry| metadata.dn(entry))
-            });
+            })
+            .map(|dn| pseudonymize_dn(&dn).to_string());
 
         Ok(NtdsEntry {
             name,
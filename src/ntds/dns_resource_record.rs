@@ -0,0 +1,183 @@
+use std::io::Write;
+
+use getset::Getters;
+use serde::Serialize;
+
+use crate::win32_types::{DnsRecord, TruncatedWindowsFileTime, WindowsFileTime};
+use crate::{pseudonymize_dns_name, redact_free_text};
+
+use super::DataTableRecord;
+
+/// the name of the node which holds the records of the zone itself
+pub const ZONE_APEX: &str = "@";
+
+/// the zone which contains the root hints. Its nodes contain fully
+/// qualified names.
+const ROOT_HINTS_ZONE: &str = "RootDNSServers";
+
+/// a single resource record of an AD-integrated DNS zone, which is stored in
+/// the `dnsRecord` attribute of a `dnsNode` object
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct DnsResourceRecord {
+    zone: String,
+
+    /// the partition which stores the zone: `DomainDnsZones`,
+    /// `ForestDnsZones` or `Domain` (for zones in `CN=System`)
+    partition: &'static str,
+    name: String,
+    fqdn: String,
+    record_type: String,
+    ttl: u32,
+    data: String,
+    serial: u32,
+
+    /// the time when a dynamic record has been registered or refreshed the
+    /// last time. Static records are never scavenged.
+    timestamp: Option<WindowsFileTime>,
+    is_static: bool,
+
+    /// the node has been deleted (`dNSTombstoned`), but it is still kept by
+    /// the DNS server
+    is_tombstoned: bool,
+    when_created: Option<TruncatedWindowsFileTime>,
+    when_changed: Option<TruncatedWindowsFileTime>,
+}
+
+/// returns the partition which contains the object with the distinguished
+/// name `distinguished_name`
+pub fn dns_partition_of(distinguished_name: &str) -> &'static str {
+    let distinguished_name = distinguished_name.to_ascii_lowercase();
+    if distinguished_name.contains("dc=domaindnszones,") {
+        "DomainDnsZones"
+    } else if distinguished_name.contains("dc=forestdnszones,") {
+        "ForestDnsZones"
+    } else {
+        "Domain"
+    }
+}
+
+/// pseudonymizes a DNS name, but keeps a trailing dot
+fn pseudonymize_fqdn(name: &str) -> String {
+    match name.strip_suffix('.') {
+        Some("") => name.to_string(),
+        Some(name) => format!("{}.", pseudonymize_dns_name(name)),
+        None => pseudonymize_dns_name(name).to_string(),
+    }
+}
+
+impl DnsResourceRecord {
+    /// `zone` and `name` are the (not yet pseudonymized) names of the zone
+    /// and of the `dnsNode` object `node`
+    pub fn from_record(
+        node: &DataTableRecord,
+        zone: &str,
+        partition: &'static str,
+        name: &str,
+        record: DnsRecord,
+    ) -> anyhow::Result<Self> {
+        let fqdn = if zone == ROOT_HINTS_ZONE {
+            name.to_string()
+        } else if name == ZONE_APEX {
+            zone.to_string()
+        } else {
+            format!("{name}.{zone}")
+        };
+        let record_type = record.type_name();
+        let record = record.map_names(pseudonymize_fqdn, |text| redact_free_text(text).to_string());
+
+        Ok(Self {
+            zone: pseudonymize_dns_name(zone).to_string(),
+            partition,
+            name: if name == ZONE_APEX {
+                name.to_string()
+            } else {
+                pseudonymize_dns_name(name).to_string()
+            },
+            fqdn: pseudonymize_dns_name(&fqdn).to_string(),
+            record_type,
+            ttl: *record.ttl(),
+            data: record.data().to_string(),
+            serial: *record.serial(),
+            is_static: record.timestamp().is_none(),
+            timestamp: record.timestamp().clone(),
+            is_tombstoned: node.att_dns_tombstoned_opt()?.unwrap_or(false)
+                || record.is_tombstone(),
+            when_created: node.att_when_created_opt()?,
+            when_changed: node.att_when_changed_opt()?,
+        })
+    }
+
+    /// writes this record as a line of a BIND zone file. Tombstoned records
+    /// are written as comments.
+    fn write_zone_file_line<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let comment = if self.is_tombstoned { "; " } else { "" };
+        let annotation = match &self.timestamp {
+            Some(timestamp) => format!("dynamic, refreshed at {timestamp}"),
+            None => "static".to_string(),
+        };
+        writeln!(
+            writer,
+            "{comment}{}\t{}\tIN\t{}\t{}\t; {annotation}",
+            self.name, self.ttl, self.record_type, self.data
+        )
+    }
+}
+
+/// returns the name of the zone file of `zone`. Zone names are read from the
+/// database, so they must not be able to escape the output directory.
+pub fn zone_file_name(zone: &str) -> String {
+    let name = zone
+        .trim_start_matches('.')
+        .replace(['/', '\\'], "_")
+        .replace("..", "_");
+    if name.is_empty() {
+        "_.zone".to_string()
+    } else {
+        format!("{name}.zone")
+    }
+}
+
+/// writes all records of the zone `zone` as a BIND zone file. `records`
+/// must contain only records of this zone.
+pub fn write_zone_file<W: Write>(
+    writer: &mut W,
+    zone: &str,
+    records: &[&DnsResourceRecord],
+) -> std::io::Result<()> {
+    let origin = if zone == ROOT_HINTS_ZONE {
+        ".".to_string()
+    } else {
+        format!("{zone}.")
+    };
+    writeln!(writer, "; zone {zone}, extracted from ntds.dit")?;
+    writeln!(writer, "$ORIGIN {origin}")?;
+
+    // the SOA record must be the first record of a zone file
+    let mut records = records.to_vec();
+    records.sort_by_key(|record| {
+        (
+            record.record_type != "SOA",
+            record.name != ZONE_APEX,
+            record.name.clone(),
+        )
+    });
+    for record in records {
+        record.write_zone_file_line(writer)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::zone_file_name;
+
+    #[test]
+    fn test_zone_file_name() {
+        assert_eq!(zone_file_name("example.com"), "example.com.zone");
+        assert_eq!(zone_file_name("..TrustAnchors"), "TrustAnchors.zone");
+        assert_eq!(zone_file_name("../../etc/passwd"), "___etc_passwd.zone");
+        assert_eq!(zone_file_name("a\\..\\b"), "a___b.zone");
+        assert_eq!(zone_file_name(""), "_.zone");
+    }
+}
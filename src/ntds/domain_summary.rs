@@ -0,0 +1,107 @@
+use getset::Getters;
+use serde::Serialize;
+
+use crate::win32_types::{
+    FunctionalLevel, PasswordProperties, Sid, TimeInterval, TruncatedWindowsFileTime,
+};
+use crate::{pseudonymize_dns_name, pseudonymize_name};
+
+use super::DataTableRecord;
+
+/// the tombstone lifetime (in days) which is used if `tombstoneLifetime` is not set
+const DEFAULT_TOMBSTONE_LIFETIME: i32 = 60;
+
+/// the names of a domain, which are not stored in the domain object itself
+pub struct DomainNames {
+    pub dns_name: String,
+    pub forest_name: String,
+
+    /// the NetBIOS name, which is stored in the `crossRef` object of the domain
+    pub netbios_name: Option<String>,
+}
+
+/// the configuration of the domain, together with the default password
+/// policy and some settings of the forest
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct DomainSummary {
+    domain_name: String,
+    netbios_name: Option<String>,
+    forest_name: String,
+    domain_sid: Option<Sid>,
+    when_created: Option<TruncatedWindowsFileTime>,
+    domain_functional_level: Option<FunctionalLevel>,
+    forest_functional_level: Option<FunctionalLevel>,
+
+    min_pwd_length: Option<i32>,
+    pwd_history_length: Option<i32>,
+    min_pwd_age: Option<TimeInterval>,
+
+    /// a value of `0 seconds` means that passwords never expire
+    max_pwd_age: Option<TimeInterval>,
+    pwd_properties: Option<PasswordProperties>,
+
+    /// a value of `0` means that accounts are never locked out
+    lockout_threshold: Option<i32>,
+    lockout_duration: Option<TimeInterval>,
+    lockout_observation_window: Option<TimeInterval>,
+
+    /// the number of computer accounts every user is allowed to create
+    machine_account_quota: Option<i32>,
+
+    /// the number of days deleted objects are kept as tombstones
+    tombstone_lifetime: i32,
+
+    /// the number of days deleted objects can be restored, if the
+    /// Recycle Bin is enabled
+    deleted_object_lifetime: i32,
+    recycle_bin_enabled: bool,
+}
+
+impl DomainSummary {
+    /// `partitions` is the `CN=Partitions` container of the configuration
+    /// partition and `directory_service` is `CN=Directory Service`, which
+    /// contains the tombstone lifetime. `deleted_object_lifetime` is the
+    /// value of `msDS-DeletedObjectLifetime`.
+    pub fn from_records(
+        domain: &DataTableRecord,
+        partitions: Option<&DataTableRecord>,
+        directory_service: Option<&DataTableRecord>,
+        names: DomainNames,
+        deleted_object_lifetime: Option<i32>,
+        recycle_bin_enabled: bool,
+    ) -> anyhow::Result<Self> {
+        let tombstone_lifetime = directory_service
+            .map(|record| record.att_tombstone_lifetime_opt())
+            .transpose()?
+            .flatten()
+            .unwrap_or(DEFAULT_TOMBSTONE_LIFETIME);
+
+        Ok(Self {
+            domain_name: pseudonymize_dns_name(&names.dns_name).to_string(),
+            netbios_name: names
+                .netbios_name
+                .map(|name| pseudonymize_name(&name).to_string()),
+            forest_name: pseudonymize_dns_name(&names.forest_name).to_string(),
+            domain_sid: domain.att_object_sid_opt()?,
+            when_created: domain.att_when_created_opt()?,
+            domain_functional_level: domain.att_behavior_version_opt()?,
+            forest_functional_level: partitions
+                .map(|record| record.att_behavior_version_opt())
+                .transpose()?
+                .flatten(),
+            min_pwd_length: domain.att_min_pwd_length_opt()?,
+            pwd_history_length: domain.att_pwd_history_length_opt()?,
+            min_pwd_age: domain.att_min_pwd_age_opt()?,
+            max_pwd_age: domain.att_max_pwd_age_opt()?,
+            pwd_properties: domain.att_pwd_properties_opt()?,
+            lockout_threshold: domain.att_lockout_threshold_opt()?,
+            lockout_duration: domain.att_lockout_duration_opt()?,
+            lockout_observation_window: domain.att_lockout_observation_window_opt()?,
+            machine_account_quota: domain.att_machine_account_quota_opt()?,
+            tombstone_lifetime,
+            deleted_object_lifetime: deleted_object_lifetime.unwrap_or(tombstone_lifetime),
+            recycle_bin_enabled,
+        })
+    }
+}
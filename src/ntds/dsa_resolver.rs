@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use getset::Getters;
+
+use crate::cache::{MetaDataCache, RecordId};
+use crate::win32_types::Guid;
+
+/// a domain controller, represented by its `nTDSDSA` object (`NTDS Settings`)
+#[derive(Getters, Clone)]
+#[getset(get = "pub")]
+pub struct DomainController {
+    server: String,
+    site: Option<String>,
+}
+
+impl DomainController {
+    pub fn new(server: String, site: Option<String>) -> Self {
+        Self { server, site }
+    }
+}
+
+impl Display for DomainController {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.site {
+            Some(site) => write!(f, "{} ({site})", self.server),
+            None => write!(f, "{}", self.server),
+        }
+    }
+}
+
+/// resolves the GUIDs of originating DSAs, which are referenced by the
+/// replication metadata, to the domain controllers they belong to
+#[derive(Default)]
+pub struct DsaResolver {
+    by_invocation_id: HashMap<Guid, DomainController>,
+    by_dsa: HashMap<RecordId, DomainController>,
+}
+
+impl DsaResolver {
+    /// registers the `nTDSDSA` object `dsa`, together with its current and
+    /// retired invocation ids
+    pub fn insert(
+        &mut self,
+        dsa: RecordId,
+        domain_controller: DomainController,
+        invocation_ids: impl IntoIterator<Item = Guid>,
+    ) {
+        for invocation_id in invocation_ids {
+            self.by_invocation_id
+                .insert(invocation_id, domain_controller.clone());
+        }
+        self.by_dsa.insert(dsa, domain_controller);
+    }
+
+    /// returns the domain controller which uses (or has used) `guid` as its
+    /// invocation id. Because the first invocation id of a domain controller
+    /// is the `objectGUID` of its `nTDSDSA` object, this falls back to
+    /// searching for an object with this GUID.
+    pub fn resolve(&self, guid: &Guid, metadata: &MetaDataCache) -> Option<&DomainController> {
+        self.by_invocation_id.get(guid).or_else(|| {
+            metadata
+                .ptr_from_guid(guid)
+                .and_then(|ptr| self.by_dsa.get(ptr.ds_record_id()))
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_dsa.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_dsa.is_empty()
+    }
+}
@@ -35,7 +35,13 @@ pub enum Error {
     UuidError(#[from] uuid::Error),
 
     #[error("Invalid SDDL: {0}")]
-    SddlError(#[from] sddl::Error)
+    SddlError(#[from] sddl::Error),
+
+    #[error("invalid replication metadata: {0}")]
+    InvalidReplicationMetadata(&'static str),
+
+    #[error("invalid DNS record: {0}")]
+    InvalidDnsRecord(&'static str),
 }
 
 pub type Result<T> = core::result::Result<T, Error>;
\ No newline at end of file
@@ -0,0 +1,98 @@
+use std::fmt::Display;
+
+use getset::Getters;
+use serde::Serialize;
+
+use crate::crypto::{
+    machine_account_salt, GroupKeyIdentifier, KdsRootKey, ManagedPassword, PasswordHash,
+};
+use crate::{pseudonymize_dn, pseudonymize_dns_name, pseudonymize_name, redact_secret};
+
+use super::DataTableRecord;
+
+/// the password of a group managed service account (gMSA), which has been
+/// derived from a KDS root key
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct GmsaPassword {
+    sam_account_name: String,
+    domain: String,
+    rid: u32,
+    is_deleted: bool,
+    distinguished_name: Option<String>,
+
+    /// `current` for the key from `msDS-ManagedPasswordId`, and `previous`
+    /// for the key from `msDS-ManagedPasswordPreviousId`
+    generation: &'static str,
+
+    /// the L0, L1 and L2 indices of the group key
+    key_id: String,
+    root_key_id: String,
+    nt_hash: PasswordHash,
+    aes128_key: String,
+    aes256_key: String,
+}
+
+impl GmsaPassword {
+    pub fn from_record(
+        record: &DataTableRecord,
+        generation: &'static str,
+        key_id: &GroupKeyIdentifier,
+        root_key: &KdsRootKey,
+        distinguished_name: Option<String>,
+    ) -> anyhow::Result<Self> {
+        let sid = record.att_object_sid()?;
+        let sam_account_name = record.att_sam_account_name()?;
+        let password = ManagedPassword::derive(root_key, key_id, &sid.to_bytes());
+        let salt = machine_account_salt(key_id.domain_name(), &sam_account_name);
+
+        Ok(Self {
+            sam_account_name: pseudonymize_name(&sam_account_name).to_string(),
+            domain: pseudonymize_dns_name(key_id.domain_name()).to_string(),
+            rid: *sid.get_rid(),
+            is_deleted: record.att_is_deleted_opt()?.unwrap_or(false),
+            distinguished_name: distinguished_name.map(|dn| pseudonymize_dn(&dn).to_string()),
+            generation,
+            key_id: key_id.to_string(),
+            root_key_id: root_key.id().to_string(),
+            nt_hash: password.nt_hash(),
+            aes128_key: redact_secret(&hex::encode(password.aes128_key(&salt))).to_string(),
+            aes256_key: redact_secret(&hex::encode(password.aes256_key(&salt))).to_string(),
+        })
+    }
+
+    pub fn user_name(&self) -> String {
+        format!("{}\\{}", self.domain, self.sam_account_name)
+    }
+}
+
+/// displays the NT hash and the AES keys in the format used by secretsdump
+impl Display for GmsaPassword {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let user_name = self.user_name();
+        let mut suffix = String::new();
+        if self.generation != "current" {
+            suffix.push_str(&format!(" ({} password)", self.generation));
+        }
+        if self.is_deleted {
+            suffix.push_str(" (deleted)");
+        }
+        writeln!(
+            f,
+            "{user_name}:{}:{}:{}:::{suffix}",
+            self.rid,
+            PasswordHash::EMPTY_LM,
+            redact_secret(&self.nt_hash.to_string())
+        )?;
+        writeln!(
+            f,
+            "{user_name}:aes256-cts-hmac-sha1-96:{}{suffix}",
+            self.aes256_key
+        )?;
+        writeln!(
+            f,
+            "{user_name}:aes128-cts-hmac-sha1-96:{}{suffix}",
+            self.aes128_key
+        )
+    }
+}
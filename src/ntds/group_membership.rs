@@ -0,0 +1,122 @@
+use bodyfile::Bodyfile3Line;
+use getset::Getters;
+use serde::Serialize;
+
+use crate::win32_types::{Guid, TimelineEntry, TruncatedWindowsFileTime, WindowsFileTime};
+
+use super::{MemberLink, NtdsMembershipChange};
+
+/// a value of the `member` attribute of a group, together with the
+/// information when it has been added or removed
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct GroupMembership {
+    group: String,
+    member: String,
+    member_sam_account_name: Option<String>,
+
+    /// `absent` if the member has been removed from the group
+    state: &'static str,
+    added: Option<WindowsFileTime>,
+    removed: Option<TruncatedWindowsFileTime>,
+    last_changed: Option<WindowsFileTime>,
+    version: Option<u32>,
+
+    /// the invocation ID of the DC where the last change took place
+    originating_invocation_id: Option<Guid>,
+    originating_dsa: Option<String>,
+    originating_usn: Option<i64>,
+    local_usn: Option<i64>,
+}
+
+impl GroupMembership {
+    /// `group` and `member` must already be pseudonymized. `resolve_dsa`
+    /// returns the name of the DC which uses a given invocation id.
+    pub fn from_link(
+        link: &MemberLink,
+        group: String,
+        member: String,
+        member_sam_account_name: Option<String>,
+        resolve_dsa: impl Fn(&Guid) -> Option<String>,
+    ) -> Self {
+        let metadata = link.metadata().as_ref();
+        Self {
+            group,
+            member,
+            member_sam_account_name,
+            state: if link.is_absent() { "absent" } else { "active" },
+            added: metadata.map(|m| m.creation_time().clone()),
+            removed: link.deletion_time().clone(),
+            last_changed: metadata.map(|m| m.change_time().clone()),
+            version: metadata.map(|m| *m.version()),
+            originating_invocation_id: metadata.map(|m| m.originating_invocation_id().clone()),
+            originating_dsa: metadata.and_then(|m| resolve_dsa(m.originating_invocation_id())),
+            originating_usn: metadata.map(|m| *m.originating_usn()),
+            local_usn: *link.usn_changed(),
+        }
+    }
+
+    pub fn is_absent(&self) -> bool {
+        self.removed.is_some()
+    }
+
+    fn member_name(&self) -> &str {
+        self.member_sam_account_name
+            .as_deref()
+            .unwrap_or(&self.member)
+    }
+
+    fn dc_caption(&self) -> String {
+        match (&self.originating_dsa, &self.originating_invocation_id) {
+            (Some(dsa), _) => format!(" on DC {dsa}"),
+            (None, Some(invocation_id)) => format!(" on DC {invocation_id}"),
+            (None, None) => String::new(),
+        }
+    }
+
+    /// creates one bodyfile line for the addition and (if the member has
+    /// been removed) one for the removal of the member
+    pub fn to_bodyfile(&self) -> Vec<Bodyfile3Line> {
+        let object_type = "group membership";
+        let added_caption = format!("added to {}", self.group);
+        let removed_caption = format!("removed from {}{}", self.group, self.dc_caption());
+        [
+            self.added
+                .as_ref()
+                .map(|ts| ts.c_entry(self.member_name(), &added_caption, object_type)),
+            self.removed
+                .as_ref()
+                .map(|ts| ts.c_entry(self.member_name(), &removed_caption, object_type)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// creates one flow record for the addition and (if the member has been
+    /// removed) one for the removal of the member
+    pub fn to_flow_records(&self) -> Vec<NtdsMembershipChange> {
+        let record =
+            |change: &str, change_time: chrono::DateTime<chrono::Utc>| NtdsMembershipChange {
+                group: self.group.clone(),
+                member: self.member_name().to_string(),
+                change: change.to_string(),
+                change_time,
+                version: self.version.unwrap_or_default(),
+                originating_invocation_id: self
+                    .originating_invocation_id
+                    .as_ref()
+                    .map(|id| id.to_string())
+                    .unwrap_or_default(),
+                originating_dsa: self.originating_dsa.clone().unwrap_or_default(),
+                originating_usn: self.originating_usn.unwrap_or_default(),
+            };
+        [
+            self.added.as_ref().map(|ts| record("added", ts.into())),
+            self.removed.clone().map(|ts| record("removed", ts.into())),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use getset::Getters;
+use serde::Serialize;
+
+use crate::win32_types::{GpLinkEntry, Guid, TruncatedWindowsFileTime};
+use crate::{is_pseudonymization_enabled, pseudonymize_dn, redact_free_text};
+
+use super::DataTableRecord;
+
+/// a Group Policy Object (`groupPolicyContainer` object)
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct GroupPolicyObject {
+    guid: Option<Guid>,
+    display_name: Option<String>,
+    distinguished_name: Option<String>,
+    when_created: Option<TruncatedWindowsFileTime>,
+    when_changed: Option<TruncatedWindowsFileTime>,
+    is_deleted: bool,
+
+    /// the version of the user configuration, which is the upper half of
+    /// `versionNumber`
+    user_version: Option<u16>,
+
+    /// the version of the computer configuration, which is the lower half
+    /// of `versionNumber`
+    computer_version: Option<u16>,
+
+    /// `enabled`, `user settings disabled`, `computer settings disabled` or
+    /// `all settings disabled` (see `flags`)
+    status: Option<&'static str>,
+
+    /// the path of the Group Policy Template in the SYSVOL share
+    file_sys_path: Option<String>,
+
+    /// the containers to which this GPO is linked
+    linked_to: String,
+}
+
+/// the GUID of a GPO is stored as its common name, e.g. `{31B2F340-016D-11D2-945F-00C04FB984F9}`
+pub fn gpo_guid(record: &DataTableRecord) -> anyhow::Result<Option<Guid>> {
+    Ok(record.att_object_name_opt()?.and_then(|name| {
+        name.name()
+            .trim_start_matches('{')
+            .trim_end_matches('}')
+            .parse()
+            .ok()
+    }))
+}
+
+/// describes a link to a GPO, using the display name of the GPO if it is known
+pub fn describe_gp_link(entry: &GpLinkEntry, display_names: &HashMap<Guid, String>) -> String {
+    match entry
+        .gpo_guid()
+        .and_then(|guid| display_names.get(&guid))
+        .filter(|_| !is_pseudonymization_enabled())
+    {
+        Some(display_name) => format!("{display_name} {entry}"),
+        None => entry.to_string(),
+    }
+}
+
+impl GroupPolicyObject {
+    /// `links` contains the (not yet pseudonymized) distinguished names of
+    /// all containers which link to this GPO, together with the link itself
+    pub fn from_record(
+        record: &DataTableRecord,
+        distinguished_name: Option<String>,
+        links: &[(String, GpLinkEntry)],
+    ) -> anyhow::Result<Self> {
+        let version_number = record
+            .att_version_number_opt()?
+            .map(|version| u32::from_ne_bytes(version.to_ne_bytes()));
+        let status = record.att_flags_opt()?.map(|flags| match flags & 3 {
+            0 => "enabled",
+            1 => "user settings disabled",
+            2 => "computer settings disabled",
+            _ => "all settings disabled",
+        });
+        let linked_to = links
+            .iter()
+            .map(|(dn, entry)| {
+                let mut link = pseudonymize_dn(dn).to_string();
+                if entry.is_enforced() {
+                    link.push_str(" (enforced)");
+                }
+                if entry.is_disabled() {
+                    link.push_str(" (disabled)");
+                }
+                link
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        Ok(Self {
+            guid: gpo_guid(record)?,
+            display_name: record
+                .att_display_name_opt()?
+                .map(|name| redact_free_text(&name).to_string()),
+            distinguished_name: distinguished_name.map(|dn| pseudonymize_dn(&dn).to_string()),
+            when_created: record.att_when_created_opt()?,
+            when_changed: record.att_when_changed_opt()?,
+            is_deleted: record.att_is_deleted_opt()?.unwrap_or(false),
+            user_version: version_number.map(|version| (version >> 16) as u16),
+            computer_version: version_number.map(|version| (version & 0xffff) as u16),
+            status,
+            file_sys_path: record
+                .att_gpc_file_sys_path_opt()?
+                .map(|path| redact_free_text(&path).to_string()),
+            linked_to,
+        })
+    }
+}
@@ -1,19 +1,55 @@
 use anyhow::Result;
+use getset::Getters;
 use std::collections::{HashMap, HashSet};
 
 use crate::cache::RecordPointer;
 use crate::cache::{self, RecordId};
+use crate::crypto::KeyCredential;
 use crate::ntds::link_table_builder::LinkTableBuilder;
-use crate::win32_types::Rdn;
+use crate::win32_types::{LinkValueMetadata, Rdn, TruncatedWindowsFileTime};
 use crate::{Membership, MembershipSet, SerializationType};
 
 use super::DataTable;
 
+/// linked attributes (besides `member` and `msDS-KeyCredentialLink`) whose
+/// values are read from the link table, identified by their common name
+pub(crate) const FORWARD_LINK_ATTRIBUTES: &[&str] = &[
+    "ms-DS-Enabled-Feature",
+    "ms-DS-PSO-Applies-To",
+    "Server-Reference",
+    "Site-Object",
+    "Site-List",
+];
+
 /// wraps a ESEDB Table.
 /// This class assumes the a NTDS link_table is being wrapped
 pub struct LinkTable {
     pub(crate) _forward_map: HashMap<RecordId, HashSet<RecordPointer>>,
     pub(crate) backward_map: HashMap<RecordId, HashSet<RecordPointer>>,
+    pub(crate) member_links: Vec<MemberLink>,
+    pub(crate) key_credential_links: HashMap<RecordId, Vec<Vec<u8>>>,
+    pub(crate) forward_links: HashMap<&'static str, HashMap<RecordId, Vec<RecordPointer>>>,
+}
+
+/// a value of the `member` attribute. In contrast to the maps of the link
+/// table, this contains also values which have been removed ("absent" values),
+/// together with the linked value replication metadata.
+#[derive(Getters)]
+#[getset(get = "pub")]
+pub struct MemberLink {
+    pub(crate) group: RecordPointer,
+    pub(crate) member: RecordPointer,
+
+    /// the time when the member has been removed, if this value is absent
+    pub(crate) deletion_time: Option<TruncatedWindowsFileTime>,
+    pub(crate) usn_changed: Option<i64>,
+    pub(crate) metadata: Option<LinkValueMetadata>,
+}
+
+impl MemberLink {
+    pub fn is_absent(&self) -> bool {
+        self.deletion_time.is_some()
+    }
 }
 
 impl LinkTable {
@@ -29,10 +65,49 @@ impl LinkTable {
         builder.build(data_table.metadata())
     }
 
+    /// returns all values of the `member` attribute, including absent values
+    pub fn member_links(&self) -> &[MemberLink] {
+        &self.member_links
+    }
+
+    /// returns the targets of the linked attribute `attribute` (one of
+    /// [`FORWARD_LINK_ATTRIBUTES`]) of the object `dnt`. Absent values are
+    /// not returned.
+    pub fn forward_links_of(&self, attribute: &str, dnt: &RecordId) -> &[RecordPointer] {
+        self.forward_links
+            .get(attribute)
+            .and_then(|links| links.get(dnt))
+            .map(|targets| &targets[..])
+            .unwrap_or_default()
+    }
+
     pub(crate) fn member_of(&self, dnt: &RecordId) -> Option<&HashSet<RecordPointer>> {
         self.backward_map.get(dnt)
     }
 
+    /// returns the ids of all objects which have at least one value in
+    /// `msDS-KeyCredentialLink`
+    pub(crate) fn key_credential_owners(&self) -> impl Iterator<Item = &RecordId> {
+        self.key_credential_links.keys()
+    }
+
+    /// parses the values of `msDS-KeyCredentialLink` of an object. Values
+    /// which cannot be parsed are ignored.
+    pub(crate) fn key_credentials_of(&self, dnt: &RecordId) -> Vec<KeyCredential> {
+        self.key_credential_links
+            .get(dnt)
+            .into_iter()
+            .flatten()
+            .filter_map(|value| match KeyCredential::try_from(&value[..]) {
+                Ok(credential) => Some(credential),
+                Err(why) => {
+                    log::warn!("unable to parse a key credential of {dnt}: {why}");
+                    None
+                }
+            })
+            .collect()
+    }
+
     pub fn member_names_of(&self, object_id: RecordId, data_table: &DataTable<'_, '_>) -> Vec<Rdn> {
         let member_of = if let Some(children) = self.member_of(&object_id) {
             children
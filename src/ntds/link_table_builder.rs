@@ -2,10 +2,35 @@ use std::collections::{HashMap, HashSet};
 
 use anyhow::{anyhow, ensure, Result};
 
-use crate::cache::{self, MetaDataCache, RecordId, RecordPointer, Value, WithValue};
+use crate::cache::{self, ColumnIndex, MetaDataCache, RecordId, RecordPointer, Value, WithValue};
 use crate::value::FromValue;
+use crate::win32_types::TruncatedWindowsFileTime;
 
-use super::{LinkTable, NtdsAttributeId};
+use super::{LinkTable, MemberLink, NtdsAttributeId, FORWARD_LINK_ATTRIBUTES};
+
+/// a single value of a linked attribute with binary data
+struct LinkValue<T> {
+    owner: RecordId,
+    data: T,
+
+    /// the time when the value has been removed from the attribute
+    deletion_time: Option<TruncatedWindowsFileTime>,
+}
+
+/// groups the values of a linked attribute by the objects which own them.
+/// Absent values are kept until the tombstone lifetime has expired, but they
+/// are no longer part of the attribute, so they are ignored.
+fn present_values_by_owner<T>(
+    values: impl IntoIterator<Item = LinkValue<T>>,
+) -> HashMap<RecordId, Vec<T>> {
+    let mut links: HashMap<RecordId, Vec<T>> = HashMap::new();
+    for value in values {
+        if value.deletion_time.is_none() {
+            links.entry(value.owner).or_default().push(value.data);
+        }
+    }
+    links
+}
 
 pub(crate) struct LinkTableBuilder<'info, 'db> {
     link_table: cache::LinkTable<'info, 'db>,
@@ -37,11 +62,12 @@ impl<'info, 'db> LinkTableBuilder<'info, 'db> {
 
         let mut forward_map = HashMap::new();
         let mut backward_map = HashMap::new();
+        let mut member_links = Vec::new();
 
         for record in self.link_table.iter().filter(|r| {
             r.with_value(*link_base_id, |value| match value {
                 Some(Value::U32(v)) => Ok(*v == member_link_id),
-                Some(Value::I32(v)) => Ok(u32::try_from(*v).map_or(false, |v| v == link_base)),
+                Some(Value::I32(v)) => Ok(u32::try_from(*v) == Ok(link_base)),
                 _ => Ok(false),
             })
             .unwrap_or(false)
@@ -68,14 +94,35 @@ impl<'info, 'db> LinkTableBuilder<'info, 'db> {
                             })
                         })
                 }) {
-                    forward_map
-                        .entry(*forward_link.ds_record_id())
-                        .or_insert_with(HashSet::new)
-                        .insert(*backward_link);
-                    backward_map
-                        .entry(*backward_link.ds_record_id())
-                        .or_insert_with(HashSet::new)
-                        .insert(*forward_link);
+                    let metadata = self
+                        .read_optional(&record, *self.link_table.link_metadata_id())
+                        .unwrap_or_else(|why| {
+                            log::warn!("unable to parse linked value metadata: {why}");
+                            None
+                        });
+                    let member_link = MemberLink {
+                        group: *forward_link,
+                        member: *backward_link,
+                        deletion_time: self
+                            .read_optional(&record, *self.link_table.link_deltime_id())?,
+                        usn_changed: self
+                            .read_optional(&record, *self.link_table.link_usnchanged_id())?,
+                        metadata,
+                    };
+
+                    // absent values are kept until the tombstone lifetime has
+                    // expired, but they are no longer members
+                    if !member_link.is_absent() {
+                        forward_map
+                            .entry(*forward_link.ds_record_id())
+                            .or_insert_with(HashSet::new)
+                            .insert(*backward_link);
+                        backward_map
+                            .entry(*backward_link.ds_record_id())
+                            .or_insert_with(HashSet::new)
+                            .insert(*forward_link);
+                    }
+                    member_links.push(member_link);
                 }
             }
         }
@@ -93,13 +140,162 @@ impl<'info, 'db> LinkTableBuilder<'info, 'db> {
             forward_map.len(),
             backward_map.len()
         );
+        log::debug!(
+            "found {} absent member links",
+            member_links.iter().filter(|link| link.is_absent()).count()
+        );
+
+        let key_credential_links = self.find_key_credential_links(metadata)?;
+        log::debug!(
+            "found key credentials of {} objects",
+            key_credential_links.len()
+        );
+
+        let forward_links = self.find_forward_links(metadata)?;
 
         Ok(LinkTable {
             _forward_map: forward_map,
             backward_map,
+            member_links,
+            key_credential_links,
+            forward_links,
         })
     }
 
+    /// reads the value of a column which might not exist in older databases
+    fn read_optional<T: FromValue>(
+        &self,
+        record: &cache::Record,
+        column: Option<ColumnIndex>,
+    ) -> Result<Option<T>> {
+        match column {
+            Some(column) => record.with_value(column, |v| match v {
+                Some(v) => Ok(T::from_value_opt(v)?),
+                None => Ok(None),
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// reads the binary values of `msDS-KeyCredentialLink` (which are stored
+    /// in the `link_data` column), grouped by the objects which own them
+    fn find_key_credential_links(
+        &self,
+        metadata: &MetaDataCache,
+    ) -> Result<HashMap<RecordId, Vec<Vec<u8>>>> {
+        let link_id = match self.try_find_link_id("ms-DS-Key-Credential-Link")? {
+            Some(link_id) => link_id,
+            None => {
+                log::info!("the schema contains no attribute 'ms-DS-Key-Credential-Link'");
+                return Ok(HashMap::new());
+            }
+        };
+        let link_base = link_id / 2;
+        let link_dnt_id = self.link_table.link_dnt_id();
+        let link_base_id = self.link_table.link_base_id();
+        let link_data_id = self.link_table.link_data_id();
+
+        let mut values = Vec::new();
+        for record in self.link_table.iter() {
+            let is_key_credential = record.with_value(*link_base_id, |value| match value {
+                Some(Value::U32(v)) => Ok(*v == link_base),
+                Some(Value::I32(v)) => Ok(u32::try_from(*v) == Ok(link_base)),
+                _ => Ok(false),
+            })?;
+            if !is_key_credential {
+                continue;
+            }
+
+            let owner = record.with_value(*link_dnt_id, |v| match v {
+                Some(v) => Ok(RecordId::from_value_opt(v)?),
+                None => Ok(None),
+            })?;
+            let data = record.with_value(*link_data_id, |v| match v {
+                Some(v) => Ok(Vec::<u8>::from_value_opt(v)?),
+                None => Ok(None),
+            })?;
+            match (owner.and_then(|id| metadata.ptr_from_id(&id)), data) {
+                (Some(owner), Some(data)) => values.push(LinkValue {
+                    owner: *owner.ds_record_id(),
+                    data,
+                    deletion_time: self
+                        .read_optional(&record, *self.link_table.link_deltime_id())?,
+                }),
+                _ => log::warn!("ignoring an incomplete value of msDS-KeyCredentialLink"),
+            }
+        }
+        Ok(present_values_by_owner(values))
+    }
+
+    /// reads the (present) values of all attributes in
+    /// [`FORWARD_LINK_ATTRIBUTES`], grouped by attribute and by the objects
+    /// which own them
+    #[allow(clippy::type_complexity)]
+    fn find_forward_links(
+        &self,
+        metadata: &MetaDataCache,
+    ) -> Result<HashMap<&'static str, HashMap<RecordId, Vec<RecordPointer>>>> {
+        let mut attributes = HashMap::new();
+        for attribute in FORWARD_LINK_ATTRIBUTES {
+            match self.try_find_link_id(attribute)? {
+                Some(link_id) => {
+                    attributes.insert(link_id / 2, *attribute);
+                }
+                None => log::info!("the schema contains no attribute '{attribute}'"),
+            }
+        }
+
+        let mut links: HashMap<_, HashMap<_, Vec<_>>> = HashMap::new();
+        if attributes.is_empty() {
+            return Ok(links);
+        }
+        let link_dnt_id = self.link_table.link_dnt_id();
+        let backlink_dnt_id = self.link_table.backlink_dnt_id();
+        let link_base_id = self.link_table.link_base_id();
+
+        for record in self.link_table.iter() {
+            let attribute = record.with_value(*link_base_id, |value| match value {
+                Some(Value::U32(v)) => Ok(attributes.get(v)),
+                Some(Value::I32(v)) => {
+                    Ok(u32::try_from(*v).ok().and_then(|v| attributes.get(&v)))
+                }
+                _ => Ok(None),
+            })?;
+            let attribute = match attribute {
+                Some(attribute) => *attribute,
+                None => continue,
+            };
+            if self
+                .read_optional::<TruncatedWindowsFileTime>(
+                    &record,
+                    *self.link_table.link_deltime_id(),
+                )?
+                .is_some()
+            {
+                continue;
+            }
+
+            let read_id = |column| {
+                record.with_value(column, |v| match v {
+                    Some(v) => Ok(RecordId::from_value_opt(v)?),
+                    None => Ok(None),
+                })
+            };
+            let owner = read_id(*link_dnt_id)?.and_then(|id| metadata.ptr_from_id(&id));
+            let target = read_id(*backlink_dnt_id)?.and_then(|id| metadata.ptr_from_id(&id));
+            match (owner, target) {
+                (Some(owner), Some(target)) => links
+                    .entry(attribute)
+                    .or_default()
+                    .entry(*owner.ds_record_id())
+                    .or_default()
+                    .push(*target),
+                _ => log::warn!("ignoring an incomplete value of {attribute}"),
+            }
+        }
+        Ok(links)
+    }
+
     fn find_member_link_id_pair(&self) -> anyhow::Result<(u32, u32)> {
         log::info!("searching for link attributes 'Member' and 'Is-Member-Of-DL'");
 
@@ -125,12 +321,23 @@ impl<'info, 'db> LinkTableBuilder<'info, 'db> {
     }
 
     fn find_link_id(&self, attribute_name: &String) -> anyhow::Result<u32> {
-        let entry = self
+        Ok(self
+            .try_find_link_id(attribute_name)?
+            .unwrap_or_else(|| panic!("found no record by that name: '{attribute_name}'")))
+    }
+
+    /// returns the Link-ID of an attribute, or `None` if the schema contains
+    /// no attribute by that name
+    fn try_find_link_id(&self, attribute_name: &str) -> anyhow::Result<Option<u32>> {
+        let entry = match self
             .data_table
             .metadata()
             .children_of(&self.schema_record_id)
             .find(|r| r.rdn().name() == attribute_name)
-            .unwrap_or_else(|| panic!("found no record by that name: '{attribute_name}'"));
+        {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
 
         let link_id_column = NtdsAttributeId::AttLinkId.id(self.data_table.esedbinfo());
 
@@ -138,7 +345,33 @@ impl<'info, 'db> LinkTableBuilder<'info, 'db> {
             .data_table
             .table()
             .record(entry.record_ptr().esedb_row().inner())?;
-        Ok(u32::from_record_opt(&record, link_id_column)?
-            .unwrap_or_else(|| panic!("missing link-id attribute in {attribute_name}")))
+        Ok(Some(u32::from_record_opt(&record, link_id_column)?.unwrap_or_else(
+            || panic!("missing link-id attribute in {attribute_name}"),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cache::RecordId;
+    use crate::win32_types::TruncatedWindowsFileTime;
+
+    use super::{present_values_by_owner, LinkValue};
+
+    #[test]
+    fn test_absent_values() {
+        let value = |owner, data, deletion_time| LinkValue {
+            owner: RecordId::from(owner),
+            data,
+            deletion_time,
+        };
+        let deletion_time = Some(TruncatedWindowsFileTime::from(13_300_000_000u64));
+        let links = present_values_by_owner([
+            value(1, "present", None),
+            value(1, "absent", deletion_time.clone()),
+            value(2, "absent", deletion_time),
+        ]);
+        assert_eq!(links.get(&RecordId::from(1)), Some(&vec!["present"]));
+        assert!(!links.contains_key(&RecordId::from(2)));
     }
 }
@@ -0,0 +1,132 @@
+use std::str::FromStr;
+
+use getset::Getters;
+use serde::Serialize;
+
+use crate::crypto::{BackupKey, PekList};
+use crate::win32_types::{Guid, WindowsFileTime};
+use crate::{pseudonymize_dn, redact_secret};
+
+use super::DataTableRecord;
+
+const BACKUP_KEY_PREFIX: &str = "BCKUPKEY_";
+const SECRET_SUFFIX: &str = " Secret";
+
+/// the secret object which contains the id of the preferred backup key
+const PREFERRED_BACKUP_KEY: &str = "BCKUPKEY_P Secret";
+
+/// the secret object which contains the id of the preferred legacy backup key
+const PREFERRED_LEGACY_BACKUP_KEY: &str = "BCKUPKEY_PREFERRED Secret";
+
+/// a LSA secret object (class `secret`), such as the DPAPI domain backup keys
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct LsaSecret {
+    name: String,
+    distinguished_name: Option<String>,
+
+    /// explains the content of well-known secrets
+    description: Option<String>,
+
+    #[serde(serialize_with = "serialize_secret")]
+    current_value: Option<Vec<u8>>,
+    last_set_time: Option<WindowsFileTime>,
+
+    #[serde(serialize_with = "serialize_secret")]
+    prior_value: Option<Vec<u8>>,
+    prior_set_time: Option<WindowsFileTime>,
+}
+
+impl LsaSecret {
+    pub fn from_record(
+        record: &DataTableRecord,
+        pek_list: &PekList,
+        distinguished_name: Option<String>,
+    ) -> anyhow::Result<Self> {
+        let name = record.att_object_name()?.name().to_string();
+        let current_value = record
+            .att_current_value_opt()?
+            .map(|value| pek_list.decrypt_secret(&value))
+            .transpose()?;
+        let prior_value = record
+            .att_prior_value_opt()?
+            .map(|value| pek_list.decrypt_secret(&value))
+            .transpose()?;
+
+        Ok(Self {
+            description: current_value
+                .as_deref()
+                .and_then(|value| describe(&name, value)),
+            name,
+            distinguished_name: distinguished_name.map(|dn| pseudonymize_dn(&dn).to_string()),
+            current_value,
+            last_set_time: record.att_last_set_time_opt()?,
+            prior_value,
+            prior_set_time: record.att_prior_set_time_opt()?,
+        })
+    }
+
+    /// returns the id of the DPAPI domain backup key, if this secret
+    /// contains one
+    pub fn backup_key_id(&self) -> Option<Guid> {
+        backup_key_id(&self.name)
+    }
+}
+
+fn serialize_secret<S>(value: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match value {
+        Some(value) => serializer.serialize_str(&redact_secret(&hex::encode(value))),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// extracts the id from names like `BCKUPKEY_<guid> Secret`
+fn backup_key_id(name: &str) -> Option<Guid> {
+    let id = name
+        .strip_prefix(BACKUP_KEY_PREFIX)?
+        .strip_suffix(SECRET_SUFFIX)?;
+    Guid::from_str(id).ok()
+}
+
+fn describe(name: &str, value: &[u8]) -> Option<String> {
+    let guid = || {
+        let bytes: [u8; 16] = value.get(..16)?.try_into().ok()?;
+        Some(Guid::from_bytes_le(bytes))
+    };
+    match name {
+        PREFERRED_BACKUP_KEY => Some(format!("id of the preferred backup key: {}", guid()?)),
+        PREFERRED_LEGACY_BACKUP_KEY => Some(format!(
+            "id of the preferred legacy backup key: {}",
+            guid()?
+        )),
+        _ => {
+            backup_key_id(name)?;
+            match BackupKey::try_from(value) {
+                Ok(BackupKey::Rsa(key)) => {
+                    Some(format!("DPAPI backup key (RSA, {} bit)", key.bit_length()))
+                }
+                Ok(BackupKey::Legacy(_)) => Some("DPAPI backup key (legacy)".to_string()),
+                Err(why) => Some(format!("DPAPI backup key ({why})")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::backup_key_id;
+
+    #[test]
+    fn test_backup_key_id() {
+        assert_eq!(
+            backup_key_id("BCKUPKEY_e5e3f8a0-5f1a-4e0e-9d7c-2c7c8f6d4b21 Secret")
+                .unwrap()
+                .to_string(),
+            "e5e3f8a0-5f1a-4e0e-9d7c-2c7c8f6d4b21"
+        );
+        assert!(backup_key_id("BCKUPKEY_P Secret").is_none());
+    }
+}
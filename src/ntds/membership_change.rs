@@ -0,0 +1,18 @@
+use chrono::{DateTime, Utc};
+use flow_record::derive::*;
+use flow_record::prelude::*;
+
+/// a member which has been added to or removed from a group, which has been
+/// taken from the linked value replication metadata
+#[derive(FlowRecord)]
+#[flow_record(version = 1, source = "ntdsextract2", classification = "ntds")]
+pub struct NtdsMembershipChange {
+    pub(crate) group: String,
+    pub(crate) member: String,
+    pub(crate) change: String,
+    pub(crate) change_time: DateTime<Utc>,
+    pub(crate) version: u32,
+    pub(crate) originating_invocation_id: String,
+    pub(crate) originating_dsa: String,
+    pub(crate) originating_usn: i64,
+}
@@ -2,6 +2,7 @@ mod data_table;
 mod link_table;
 mod sd_table;
 mod attribute_id;
+mod attribute_syntax;
 mod link_table_builder;
 mod object_type;
 mod data_table_record;
@@ -11,13 +12,35 @@ mod object;
 mod schema;
 mod attribute_name;
 mod attribute_value;
+mod attribute_change;
 mod attribute_id_impl;
 mod is_member_of;
+mod account_hashes;
+mod account_credentials;
+mod password_audit;
+mod gmsa_password;
+mod bitlocker_recovery;
+mod lsa_secret;
+mod trusted_domain;
+mod shadow_credential;
+mod group_membership;
+mod membership_change;
+mod dsa_resolver;
+mod anomaly;
+mod class_schema;
+mod organizational_unit;
+mod group_policy_object;
+mod domain_summary;
+mod password_policy;
+mod dns_resource_record;
+mod topology;
+mod certificate_services;
 
 pub use data_table::*;
 pub use link_table::*;
 pub use sd_table::*;
 pub use attribute_id::*;
+pub use attribute_syntax::*;
 pub use object_type::*;
 pub use data_table_record::*;
 pub use error::*;
@@ -26,4 +49,25 @@ pub use object::*;
 pub use schema::*;
 pub use attribute_name::*;
 pub use attribute_value::*;
+pub use attribute_change::*;
 pub use is_member_of::*;
+pub use account_hashes::*;
+pub use account_credentials::*;
+pub use password_audit::*;
+pub use gmsa_password::*;
+pub use bitlocker_recovery::*;
+pub use lsa_secret::*;
+pub use trusted_domain::*;
+pub use shadow_credential::*;
+pub use group_membership::*;
+pub use membership_change::*;
+pub use dsa_resolver::*;
+pub use anomaly::*;
+pub use class_schema::*;
+pub use organizational_unit::*;
+pub use group_policy_object::*;
+pub use domain_summary::*;
+pub use password_policy::*;
+pub use dns_resource_record::*;
+pub use topology::*;
+pub use certificate_services::*;
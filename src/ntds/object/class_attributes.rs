@@ -0,0 +1,350 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use serde::ser::{SerializeMap, SerializeStruct};
+use serde::Serialize;
+
+use crate::cache::{RecordId, Value};
+use crate::ntds::{AttributeSyntax, DataTable, DataTableRecord};
+use crate::value::FromValue;
+use crate::win32_types::{Guid, Sid, TruncatedWindowsFileTime, WindowsFileTime};
+use crate::{pseudonymize_dn, redact_free_text, redact_secret, SerializationType};
+
+use super::GenericObject;
+
+/// attributes which are already part of [`super::Object`]
+const COMMON_ATTRIBUTES: &[&str] = &[
+    "accountExpires",
+    "adminCount",
+    "badPasswordTime",
+    "badPwdCount",
+    "cn",
+    "comment",
+    "distinguishedName",
+    "isDeleted",
+    "lastLogon",
+    "lastLogonTimestamp",
+    "logonCount",
+    "memberOf",
+    "name",
+    "nTSecurityDescriptor",
+    "objectSid",
+    "primaryGroupID",
+    "pwdLastSet",
+    "replPropertyMetaData",
+    "sAMAccountName",
+    "sAMAccountType",
+    "servicePrincipalName",
+    "userAccountControl",
+    "userPrincipalName",
+    "whenChanged",
+    "whenCreated",
+];
+
+/// attributes which contain (encrypted) secrets
+const SECRET_ATTRIBUTES: &[&str] = &[
+    "currentValue",
+    "dBCSPwd",
+    "initialAuthIncoming",
+    "initialAuthOutgoing",
+    "lmPwdHistory",
+    "ms-Mcs-AdmPwd",
+    "msDS-ManagedPassword",
+    "msFVE-KeyPackage",
+    "msFVE-RecoveryPassword",
+    "msKds-RootKeyData",
+    "msLAPS-EncryptedDSRMPassword",
+    "msLAPS-EncryptedDSRMPasswordHistory",
+    "msLAPS-EncryptedPassword",
+    "msLAPS-EncryptedPasswordHistory",
+    "msLAPS-Password",
+    "msPKIAccountCredentials",
+    "msPKIDPAPIMasterKeys",
+    "msPKIRoamingTimeStamp",
+    "msTPM-OwnerInformation",
+    "ntPwdHistory",
+    "pekList",
+    "priorValue",
+    "supplementalCredentials",
+    "trustAuthIncoming",
+    "trustAuthOutgoing",
+    "unicodePwd",
+];
+
+/// an attribute which is defined by the class which is being extracted
+pub struct ClassAttribute {
+    attribute_id: i32,
+
+    /// the `lDAPDisplayName`, which is used as field name
+    name: String,
+
+    /// the syntax, which is used to format the values
+    syntax: Option<AttributeSyntax>,
+}
+
+impl ClassAttribute {
+    /// returns `None` if this attribute is already part of every object
+    pub fn new(attribute_id: i32, name: &str, syntax: Option<AttributeSyntax>) -> Option<Self> {
+        if COMMON_ATTRIBUTES
+            .iter()
+            .any(|common| common.eq_ignore_ascii_case(name))
+        {
+            None
+        } else {
+            Some(Self {
+                attribute_id,
+                name: name.to_string(),
+                syntax,
+            })
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn is_secret(&self) -> bool {
+        SECRET_ATTRIBUTES
+            .iter()
+            .any(|secret| secret.eq_ignore_ascii_case(&self.name))
+    }
+
+    /// `FILETIME`s use the syntax of large integers, so they can only be
+    /// recognized by their name
+    fn is_timestamp(&self) -> bool {
+        let name = self.name.to_lowercase();
+        name.contains("time")
+            || name.starts_with("lastlog")
+            || ["accountexpires", "pwdlastset"].contains(&name.as_str())
+    }
+
+    /// formats a single value according to the syntax of this attribute,
+    /// and redacts or pseudonymizes it if requested
+    fn format(&self, value: &Value, data_table: &DataTable) -> Option<String> {
+        if matches!(value, Value::Null(())) {
+            return None;
+        }
+        let raw = || crate::value::ToString::to_string(value);
+        if self.is_secret() {
+            return Some(redact_secret(&raw()).to_string());
+        }
+
+        let formatted = match (self.syntax, value) {
+            (Some(AttributeSyntax::DistinguishedName), Value::I32(dnt)) => data_table
+                .data_table()
+                .metadata()
+                .ptr_from_id(&RecordId::from(*dnt))
+                .and_then(|ptr| data_table.object_tree().dn_of(ptr))
+                .map(|dn| pseudonymize_dn(&dn).to_string()),
+            (Some(AttributeSyntax::Sid), Value::Binary(v) | Value::LargeBinary(v)) => {
+                Sid::try_from(v.as_ref())
+                    .ok()
+                    .map(|sid| sid.to_pseudonymized_string())
+            }
+            (
+                Some(AttributeSyntax::OctetString),
+                Value::Binary(v) | Value::LargeBinary(v) | Value::Guid(v),
+            ) if self.name.to_lowercase().contains("guid") => <[u8; 16]>::try_from(&v[..])
+                .ok()
+                .map(|bytes| Guid::from_bytes_le(bytes).to_string()),
+            (Some(AttributeSyntax::UtcTime), value) => {
+                TruncatedWindowsFileTime::from_value_opt(value)
+                    .ok()
+                    .flatten()
+                    .map(|ts| ts.to_string())
+            }
+            (Some(AttributeSyntax::LargeInteger), value) if self.is_timestamp() => {
+                WindowsFileTime::from_value_opt(value)
+                    .ok()
+                    .flatten()
+                    .map(|ts| ts.to_string())
+            }
+            (Some(AttributeSyntax::Boolean), value) => bool::from_value_opt(value)
+                .ok()
+                .flatten()
+                .map(|value| value.to_string()),
+            (
+                Some(
+                    AttributeSyntax::CaseExactString
+                    | AttributeSyntax::CaseIgnoreString
+                    | AttributeSyntax::PrintableString
+                    | AttributeSyntax::UnicodeString
+                    | AttributeSyntax::DnWithString
+                    | AttributeSyntax::PresentationAddress,
+                )
+                | None,
+                _,
+            ) => Some(redact_free_text(&raw()).to_string()),
+            _ => None,
+        };
+        Some(formatted.unwrap_or_else(raw))
+    }
+}
+
+/// an object of the class which is being extracted by `objects`, together
+/// with the values of all attributes which are defined by its class. The
+/// names of those attributes are only known at runtime, so all fields are
+/// stored together with their names.
+pub struct ClassObject {
+    fields: Vec<(Cow<'static, str>, serde_json::Value)>,
+}
+
+impl ClassObject {
+    /// `attributes` are the attributes of the class which is being extracted
+    pub fn new<T: SerializationType>(
+        object: &GenericObject<T>,
+        attributes: &[ClassAttribute],
+        record: &DataTableRecord,
+        data_table: &DataTable,
+    ) -> anyhow::Result<Self> {
+        let mut fields = FieldCollector::default();
+        object.serialize_fields(&mut fields)?;
+        let mut fields = fields.0;
+
+        for attribute in attributes {
+            let values: Vec<String> =
+                record.with_values_by_attribute_id(attribute.attribute_id, |values| {
+                    Ok(values
+                        .iter()
+                        .filter_map(|value| attribute.format(value, data_table))
+                        .collect())
+                })?;
+            let values = serde_json::to_value(AttributeValues::<T> {
+                values: &values,
+                _marker: PhantomData,
+            })?;
+            fields.push((Cow::Owned(attribute.name.clone()), values));
+        }
+        Ok(Self { fields })
+    }
+
+    /// the names of all fields, which are used as CSV header
+    pub fn header(&self) -> impl Iterator<Item = &str> {
+        self.fields.iter().map(|(name, _)| name.as_ref())
+    }
+
+    /// the values of all fields, in the same order as [`Self::header`]
+    pub fn csv_record(&self) -> impl Iterator<Item = String> + '_ {
+        self.fields.iter().map(|(_, value)| match value {
+            serde_json::Value::Null => String::new(),
+            serde_json::Value::String(value) => value.clone(),
+            value => value.to_string(),
+        })
+    }
+}
+
+impl Serialize for ClassObject {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.fields.len()))?;
+        for (name, value) in &self.fields {
+            map.serialize_entry(name, value)?;
+        }
+        map.end()
+    }
+}
+
+/// collects the fields of a struct, together with their names
+#[derive(Default)]
+struct FieldCollector(Vec<(Cow<'static, str>, serde_json::Value)>);
+
+impl SerializeStruct for FieldCollector {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_field<V>(&mut self, key: &'static str, value: &V) -> Result<(), Self::Error>
+    where
+        V: ?Sized + Serialize,
+    {
+        self.0.push((Cow::Borrowed(key), serde_json::to_value(value)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// the values of a single attribute. Attributes with more than one value
+/// are serialized as list.
+struct AttributeValues<'v, T: SerializationType> {
+    values: &'v [String],
+    _marker: PhantomData<T>,
+}
+
+impl<T: SerializationType> Serialize for AttributeValues<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.values {
+            [] => serializer.serialize_none(),
+            [value] => T::serialize(Some(value.clone()), serializer),
+            values => T::serialize_list(values.iter().cloned().map(Some), serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::marker::PhantomData;
+
+    use serde::ser::SerializeStruct;
+
+    use crate::ntds::AttributeSyntax;
+    use crate::{CsvSerialization, JsonSerialization};
+
+    use super::{AttributeValues, ClassAttribute, ClassObject, FieldCollector};
+
+    #[test]
+    fn test_class_attribute() {
+        let attribute = |name| ClassAttribute::new(1, name, Some(AttributeSyntax::OctetString));
+        assert!(attribute("whenCreated").is_none());
+        assert!(attribute("msPKIAccountCredentials").unwrap().is_secret());
+        assert!(attribute("msTPM-OwnerInformation").unwrap().is_secret());
+        assert!(!attribute("msDS-CloudAnchor").unwrap().is_secret());
+        assert!(attribute("lockoutTime").unwrap().is_timestamp());
+        assert!(attribute("lastLogoff").unwrap().is_timestamp());
+        assert!(!attribute("maxStorage").unwrap().is_timestamp());
+    }
+
+    #[test]
+    fn test_class_object() {
+        let values = ["a".to_string(), "b".to_string()];
+        let object = |values| {
+            let mut fields = FieldCollector::default();
+            fields.serialize_field("sid", &None::<String>).unwrap();
+            fields.serialize_field("is_deleted", &false).unwrap();
+            fields.0.push((Cow::Owned("msDS-Values".to_string()), values));
+            ClassObject { fields: fields.0 }
+        };
+
+        let csv = object(
+            serde_json::to_value(AttributeValues::<CsvSerialization> {
+                values: &values,
+                _marker: PhantomData,
+            })
+            .unwrap(),
+        );
+        assert_eq!(
+            csv.header().collect::<Vec<_>>(),
+            vec!["sid", "is_deleted", "msDS-Values"]
+        );
+        assert_eq!(csv.csv_record().collect::<Vec<_>>(), vec!["", "false", "a,b"]);
+
+        let json = object(
+            serde_json::to_value(AttributeValues::<JsonSerialization> {
+                values: &values[..1],
+                _marker: PhantomData,
+            })
+            .unwrap(),
+        );
+        assert_eq!(
+            serde_json::to_string(&json).unwrap(),
+            r#"{"sid":null,"is_deleted":false,"msDS-Values":"a"}"#
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use serde::ser::SerializeStruct;
+
+use crate::ntds::{DataTable, DataTableRecord};
+use crate::win32_types::{UserAccountControl, WindowsFileTime};
+
+/// the password policy which applies to an account, together with the time
+/// when its current password expires
+pub struct EffectivePasswordPolicy {
+    name: String,
+
+    /// `None` if the password never expires
+    password_expires: Option<WindowsFileTime>,
+}
+
+impl EffectivePasswordPolicy {
+    pub fn from_record(record: &DataTableRecord, data_table: &DataTable) -> anyhow::Result<Self> {
+        let policy = data_table.effective_password_policy(record)?;
+        let never_expires = record
+            .att_user_account_control_opt()?
+            .is_some_and(|uac| uac.contains(UserAccountControl::ADS_UF_DONT_EXPIRE_PASSWD));
+
+        // a maximum password age of zero means that passwords never expire
+        let max_pwd_age = policy
+            .max_pwd_age()
+            .filter(|age| !age.is_zero())
+            .and_then(|age| age.duration());
+
+        let password_last_set = record.att_password_last_set_opt()?;
+        let password_expires = match (never_expires, max_pwd_age, password_last_set) {
+            (false, Some(max_pwd_age), Some(last_set)) => Some(WindowsFileTime::from(
+                DateTime::<Utc>::from(last_set) + max_pwd_age,
+            )),
+            _ => None,
+        };
+
+        Ok(Self {
+            name: policy.name().clone(),
+            password_expires,
+        })
+    }
+
+    pub fn serialize_to<S>(&self, s: &mut S) -> Result<(), S::Error>
+    where
+        S: SerializeStruct,
+    {
+        s.serialize_field("password_policy", &self.name)?;
+        s.serialize_field("password_expires", &self.password_expires)?;
+        Ok(())
+    }
+}
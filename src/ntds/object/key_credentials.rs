@@ -0,0 +1,68 @@
+use std::marker::PhantomData;
+
+use serde::Serialize;
+
+use crate::crypto::KeyCredential;
+use crate::win32_types::WindowsFileTime;
+use crate::SerializationType;
+
+/// the parsed values of `msDS-KeyCredentialLink`
+pub struct KeyCredentials<T: SerializationType> {
+    credentials: Vec<KeyCredential>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: SerializationType> From<Vec<KeyCredential>> for KeyCredentials<T> {
+    fn from(credentials: Vec<KeyCredential>) -> Self {
+        Self {
+            credentials,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: SerializationType> KeyCredentials<T> {
+    pub fn credentials(&self) -> &[KeyCredential] {
+        &self.credentials
+    }
+}
+
+impl<T: SerializationType> Serialize for KeyCredentials<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        T::serialize_list(
+            self.credentials
+                .iter()
+                .map(|credential| Some(describe(credential))),
+            serializer,
+        )
+    }
+}
+
+/// creates a short description like
+/// `<key id> [NGC; AD; RSA 2048 bit; device <guid>; created <timestamp>]`
+fn describe(credential: &KeyCredential) -> String {
+    let details: Vec<_> = [
+        credential.key_usage().map(|usage| usage.to_string()),
+        credential.key_source().map(|source| source.to_string()),
+        credential.key_type(),
+        credential
+            .device_id()
+            .as_ref()
+            .map(|device_id| format!("device {device_id}")),
+        credential
+            .creation_time()
+            .map(|creation_time| format!("created {}", WindowsFileTime::from(creation_time))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    format!(
+        "{} [{}]",
+        credential.key_id_string().unwrap_or_default(),
+        details.join("; ")
+    )
+}
@@ -0,0 +1,215 @@
+use serde::ser::SerializeStruct;
+
+use crate::crypto::{EncryptedLapsPassword, LapsPassword};
+use crate::ntds::{DataTable, DataTableRecord};
+use crate::value::FromValue;
+use crate::win32_types::{Sid, WindowsFileTime};
+use crate::{pseudonymize_name, redact_secret};
+
+/// the local administrator password of a computer, which has been stored
+/// by the legacy Microsoft LAPS or by Windows LAPS
+#[derive(Default)]
+pub struct LapsCredentials {
+    /// `ms-Mcs-AdmPwd`
+    legacy_password: Option<String>,
+
+    /// `ms-Mcs-AdmPwdExpirationTime`
+    legacy_expiration_time: Option<WindowsFileTime>,
+
+    /// `msLAPS-PasswordExpirationTime`
+    expiration_time: Option<WindowsFileTime>,
+
+    /// `true` if the password is stored in `msLAPS-EncryptedPassword`
+    is_encrypted: bool,
+
+    /// the principal which is allowed to decrypt the password
+    authorized_sid: Option<Sid>,
+
+    account_name: Option<String>,
+    password: Option<String>,
+    password_update_time: Option<WindowsFileTime>,
+
+    /// `msLAPS-EncryptedDSRMPassword`, which is only set for domain controllers
+    dsrm_authorized_sid: Option<Sid>,
+    dsrm_password: Option<String>,
+    dsrm_password_update_time: Option<WindowsFileTime>,
+}
+
+impl LapsCredentials {
+    /// reads the LAPS attributes of a computer. Encrypted passwords are
+    /// decrypted if the KDS root keys are available, which requires the
+    /// boot key. Malformed attributes are skipped, so that a single
+    /// computer cannot prevent displaying all other computers.
+    pub fn from_record(record: &DataTableRecord, data_table: &DataTable) -> Self {
+        let mut laps = Self {
+            legacy_password: attribute_value_or_warn(record, data_table, "ms-Mcs-AdmPwd"),
+            legacy_expiration_time: attribute_value_or_warn(
+                record,
+                data_table,
+                "ms-Mcs-AdmPwdExpirationTime",
+            ),
+            expiration_time: attribute_value_or_warn(
+                record,
+                data_table,
+                "msLAPS-PasswordExpirationTime",
+            ),
+            ..Default::default()
+        };
+
+        if let Some(json) =
+            attribute_value_or_warn::<String>(record, data_table, "msLAPS-Password")
+        {
+            match LapsPassword::from_json(&json) {
+                Ok(password) => laps.set_password(&password),
+                Err(why) => log::warn!("invalid LAPS password of {}: {why}", record.ptr()),
+            }
+        }
+
+        if let Some(encrypted) = encrypted_password(record, data_table, "msLAPS-EncryptedPassword")
+        {
+            laps.is_encrypted = true;
+            laps.authorized_sid = encrypted.blob().authorized_sid();
+            laps.password_update_time = Some(encrypted.update_timestamp());
+            if let Some(password) = decrypt(&encrypted, data_table, record) {
+                laps.set_password(&password);
+            }
+        }
+
+        if let Some(encrypted) =
+            encrypted_password(record, data_table, "msLAPS-EncryptedDSRMPassword")
+        {
+            laps.dsrm_authorized_sid = encrypted.blob().authorized_sid();
+            laps.dsrm_password_update_time = Some(encrypted.update_timestamp());
+            if let Some(password) = decrypt(&encrypted, data_table, record) {
+                laps.dsrm_password = Some(password.password().clone());
+                laps.dsrm_password_update_time = password.update_timestamp();
+            }
+        }
+        laps
+    }
+
+    fn set_password(&mut self, password: &LapsPassword) {
+        self.account_name = password.account_name().clone();
+        self.password = Some(password.password().clone());
+        self.password_update_time = password.update_timestamp();
+    }
+
+    /// returns `true` if LAPS manages the local administrator password of
+    /// this computer, without decrypting anything
+    pub fn is_managed(record: &DataTableRecord, data_table: &DataTable) -> anyhow::Result<bool> {
+        for name in ["ms-Mcs-AdmPwdExpirationTime", "msLAPS-PasswordExpirationTime"] {
+            if attribute_value::<WindowsFileTime>(record, data_table, name)?.is_some() {
+                return Ok(true);
+            }
+        }
+        for name in ["ms-Mcs-AdmPwd", "msLAPS-Password"] {
+            if attribute_value::<String>(record, data_table, name)?.is_some() {
+                return Ok(true);
+            }
+        }
+        for name in ["msLAPS-EncryptedPassword", "msLAPS-EncryptedDSRMPassword"] {
+            if attribute_value::<Vec<u8>>(record, data_table, name)?.is_some() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    pub fn serialize_to<S>(&self, s: &mut S) -> Result<(), S::Error>
+    where
+        S: SerializeStruct,
+    {
+        s.serialize_field(
+            "laps_legacy_password",
+            &self.legacy_password.as_deref().map(redact_secret),
+        )?;
+        s.serialize_field("laps_legacy_expiration_time", &self.legacy_expiration_time)?;
+        s.serialize_field("laps_expiration_time", &self.expiration_time)?;
+        s.serialize_field("laps_is_encrypted", &self.is_encrypted)?;
+        s.serialize_field("laps_authorized_sid", &self.authorized_sid)?;
+        s.serialize_field(
+            "laps_account_name",
+            &self.account_name.as_deref().map(pseudonymize_name),
+        )?;
+        s.serialize_field(
+            "laps_password",
+            &self.password.as_deref().map(redact_secret),
+        )?;
+        s.serialize_field("laps_password_update_time", &self.password_update_time)?;
+        s.serialize_field("laps_dsrm_authorized_sid", &self.dsrm_authorized_sid)?;
+        s.serialize_field(
+            "laps_dsrm_password",
+            &self.dsrm_password.as_deref().map(redact_secret),
+        )?;
+        s.serialize_field(
+            "laps_dsrm_password_update_time",
+            &self.dsrm_password_update_time,
+        )?;
+        Ok(())
+    }
+}
+
+/// reads an attribute which is not part of the base schema, such as the
+/// LAPS attributes, which are only available after a schema extension
+fn attribute_value<T: FromValue>(
+    record: &DataTableRecord,
+    data_table: &DataTable,
+    ldap_display_name: &str,
+) -> anyhow::Result<Option<T>> {
+    match data_table
+        .data_table()
+        .metadata()
+        .attribute_id(ldap_display_name)
+    {
+        Some(attribute_id) => record.get_value_by_attribute_id_opt(attribute_id),
+        None => Ok(None),
+    }
+}
+
+/// like [`attribute_value`], but logs a warning instead of returning an
+/// error
+fn attribute_value_or_warn<T: FromValue>(
+    record: &DataTableRecord,
+    data_table: &DataTable,
+    ldap_display_name: &str,
+) -> Option<T> {
+    attribute_value(record, data_table, ldap_display_name).unwrap_or_else(|why| {
+        log::warn!("invalid value of {ldap_display_name} in {}: {why}", record.ptr());
+        None
+    })
+}
+
+/// reads and parses an encrypted LAPS password
+fn encrypted_password(
+    record: &DataTableRecord,
+    data_table: &DataTable,
+    ldap_display_name: &str,
+) -> Option<EncryptedLapsPassword> {
+    let encrypted = attribute_value_or_warn::<Vec<u8>>(record, data_table, ldap_display_name)?;
+    match EncryptedLapsPassword::try_from(&encrypted[..]) {
+        Ok(encrypted) => Some(encrypted),
+        Err(why) => {
+            log::warn!("invalid value of {ldap_display_name} in {}: {why}", record.ptr());
+            None
+        }
+    }
+}
+
+/// decrypts an encrypted LAPS password, if the KDS root keys are available
+fn decrypt(
+    encrypted: &EncryptedLapsPassword,
+    data_table: &DataTable,
+    record: &DataTableRecord,
+) -> Option<LapsPassword> {
+    let root_keys = data_table.kds_root_keys().ok()?;
+    match encrypted.decrypt(root_keys) {
+        Ok(password) => Some(password),
+        Err(why) => {
+            log::warn!(
+                "unable to decrypt the LAPS password of {}: {why}",
+                record.ptr()
+            );
+            None
+        }
+    }
+}
@@ -2,16 +2,28 @@ mod object_base;
 mod specific_object_attribute;
 mod no_specific_attributes;
 mod has_serializable_fields;
+mod password_history;
+mod laps_credentials;
+mod key_credentials;
+mod effective_password_policy;
+mod class_attributes;
 
 mod object_computer;
 mod object_group;
 mod object_person;
+mod object_generic;
 
 pub use object_base::*;
 pub use specific_object_attribute::*;
 pub use no_specific_attributes::*;
 pub use has_serializable_fields::*;
+pub use password_history::*;
+pub use laps_credentials::*;
+pub use key_credentials::*;
+pub use effective_password_policy::*;
+pub use class_attributes::*;
 
 pub use object_computer::*;
 pub use object_group::*;
-pub use object_person::*;
\ No newline at end of file
+pub use object_person::*;
+pub use object_generic::*;
\ No newline at end of file
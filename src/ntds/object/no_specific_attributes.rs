@@ -1,6 +1,7 @@
+use serde::ser::SerializeStruct;
 use serde::{Deserialize, Serialize};
 
-use crate::ntds::DataTableRecord;
+use crate::ntds::{DataTable, DataTableRecord};
 
 use super::{HasSerializableFields, SpecificObjectAttributes};
 
@@ -14,11 +15,11 @@ impl HasSerializableFields for NoSpecificAttributes {
 }
 
 impl SpecificObjectAttributes for NoSpecificAttributes {
-    fn from(_record: &DataTableRecord) -> anyhow::Result<Self> {
+    fn from(_record: &DataTableRecord, _data_table: &DataTable) -> anyhow::Result<Self> {
         Ok(Self)
     }
 
-    fn serialize_to<S>(&self, _s: &mut S::SerializeStruct) -> Result<(), S::Error> where S: serde::Serializer {
+    fn serialize_to<S>(&self, _s: &mut S) -> Result<(), S::Error> where S: SerializeStruct {
         Ok(())
     }
 }
@@ -1,8 +1,14 @@
 use crate::cache::RecordPointer;
 use crate::cli::OutputOptions;
-use crate::win32_types::{Rdn, SecurityDescriptor, TimelineEntry, TruncatedWindowsFileTime, WindowsFileTime};
+use crate::win32_types::{
+    Rdn, ReplicationMetadata, SecurityDescriptor, TimelineEntry, TruncatedWindowsFileTime,
+    WindowsFileTime,
+};
 use crate::win32_types::{SamAccountType, Sid, UserAccountControl};
-use crate::{FormattedValue, Membership, MembershipSet, SerializationType};
+use crate::{
+    pseudonymize_dn, pseudonymize_name, pseudonymize_spn, pseudonymize_text, pseudonymize_upn,
+    redact_free_text, FormattedValue, Membership, MembershipSet, SerializationType,
+};
 use bodyfile::Bodyfile3Line;
 use getset::Getters;
 use serde::ser::SerializeStruct;
@@ -13,7 +19,10 @@ use crate::ntds::{
 };
 use std::marker::PhantomData;
 
-use super::{HasSerializableFields, SpecificObjectAttributes};
+use super::{
+    EffectivePasswordPolicy, HasSerializableFields, KeyCredentials, LapsCredentials,
+    PasswordHistory, SpecificObjectAttributes,
+};
 
 #[derive(Getters, Deserialize)]
 #[getset(get = "pub")]
@@ -60,6 +69,25 @@ where
     bad_pwd_time: Option<WindowsFileTime>,
 
     sddl: Option<String>,
+
+    #[serde(skip)]
+    nt_password_history: Option<PasswordHistory<T>>,
+
+    #[serde(skip)]
+    lm_password_history: Option<PasswordHistory<T>>,
+
+    #[serde(skip)]
+    laps: Option<LapsCredentials>,
+
+    #[serde(skip)]
+    key_credentials: Option<KeyCredentials<T>>,
+
+    #[serde(skip)]
+    password_policy: Option<EffectivePasswordPolicy>,
+
+    #[serde(skip)]
+    replication_metadata: Option<ReplicationMetadata>,
+
     //#[serde(flatten)]
     specific_attributes: A,
 
@@ -122,19 +150,46 @@ where
     {
         let mut s =
             serializer.serialize_struct("Object", Self::field_count() + A::field_count())?;
+        self.serialize_fields(&mut s)?;
+        s.end()
+    }
+}
 
+impl<T, O, A> Object<T, O, A>
+where
+    O: HasObjectType,
+    T: SerializationType,
+    A: SpecificObjectAttributes,
+{
+    /// serializes all fields of this object into `s`, which allows to
+    /// extend the fields by those of another type
+    pub fn serialize_fields<S>(&self, s: &mut S) -> Result<(), S::Error>
+    where
+        S: SerializeStruct,
+    {
         s.serialize_field("sid", self.sid())?;
 
         match &self.distinguished_name {
             FormattedValue::NoValue => s.serialize_field("distinguished_name", &None::<String>)?,
             FormattedValue::Hide => (),
-            FormattedValue::Value(dn) => s.serialize_field("distinguished_name", dn)?,
+            FormattedValue::Value(dn) => {
+                s.serialize_field("distinguished_name", &pseudonymize_dn(dn))?
+            }
         }
 
-        s.serialize_field("user_principal_name", self.user_principal_name())?;
-        s.serialize_field("service_principal_name", self.service_principal_name())?;
+        s.serialize_field(
+            "user_principal_name",
+            &self.user_principal_name().as_deref().map(pseudonymize_upn),
+        )?;
+        s.serialize_field(
+            "service_principal_name",
+            &self.service_principal_name().as_deref().map(pseudonymize_spn),
+        )?;
         s.serialize_field("rdn", self.rdn())?;
-        s.serialize_field("sam_account_name", self.sam_account_name())?;
+        s.serialize_field(
+            "sam_account_name",
+            &self.sam_account_name().as_deref().map(pseudonymize_name),
+        )?;
         s.serialize_field("sam_account_type", self.sam_account_type())?;
         s.serialize_field("user_account_control", self.user_account_control())?;
         s.serialize_field("logon_count", self.logon_count())?;
@@ -144,7 +199,7 @@ where
         s.serialize_field("primary_group_id", self.primary_group_id())?;
         s.serialize_field("primary_group", self.primary_group())?;
         s.serialize_field("member_of", self.member_of())?;
-        s.serialize_field("comment", self.comment())?;
+        s.serialize_field("comment", &self.comment().as_deref().map(redact_free_text))?;
         s.serialize_field("record_time", self.record_time())?;
         s.serialize_field("when_created", self.when_created())?;
         s.serialize_field("when_changed", self.when_changed())?;
@@ -153,10 +208,29 @@ where
         s.serialize_field("account_expires", self.account_expires())?;
         s.serialize_field("password_last_set", self.password_last_set())?;
         s.serialize_field("bad_pwd_time", self.bad_pwd_time())?;
-        s.serialize_field("sddl", self.sddl())?;
+        s.serialize_field("sddl", &self.sddl().as_deref().map(pseudonymize_text))?;
 
-        self.specific_attributes().serialize_to::<S>(&mut s)?;
-        s.end()
+        if let Some(history) = self.nt_password_history() {
+            s.serialize_field("nt_password_history", history)?;
+        }
+        if let Some(history) = self.lm_password_history() {
+            s.serialize_field("lm_password_history", history)?;
+        }
+        if let Some(laps) = self.laps() {
+            laps.serialize_to(s)?;
+        }
+        if let Some(key_credentials) = self.key_credentials() {
+            s.serialize_field("key_credentials", key_credentials)?;
+        }
+        if let Some(password_policy) = self.password_policy() {
+            password_policy.serialize_to(s)?;
+        }
+        if let Some(replication_metadata) = self.replication_metadata() {
+            s.serialize_field("replication_metadata", replication_metadata)?;
+        }
+
+        self.specific_attributes().serialize_to(s)?;
+        Ok(())
     }
 }
 
@@ -168,7 +242,7 @@ where
 {
     fn new(
         dbrecord: DataTableRecord,
-        _options: &OutputOptions,
+        options: &OutputOptions,
         data_table: &DataTable,
         link_table: &LinkTable,
         distinguished_name: FormattedValue<String>,
@@ -210,7 +284,63 @@ where
         });
 
         let member_refs = link_table.member_refs_of::<T>(object_id, data_table);
-        let specific_attributes = A::from(&dbrecord)?;
+        let specific_attributes = A::from(&dbrecord, data_table)?;
+
+        let (nt_password_history, lm_password_history) = if *options.show_password_history() {
+            let pek_list = data_table.require_pek_list()?;
+            let rid = dbrecord.att_object_sid_opt()?.map(|sid| *sid.get_rid());
+
+            // a single corrupt history must not prevent displaying the object
+            let decrypt = |encrypted: anyhow::Result<Option<Vec<u8>>>, kind: &str| {
+                let Some(rid) = rid else {
+                    return PasswordHistory::default();
+                };
+                encrypted
+                    .and_then(|encrypted| PasswordHistory::decrypt(encrypted, pek_list, rid))
+                    .unwrap_or_else(|why| {
+                        log::warn!(
+                            "unable to decrypt the {kind} password history of {object_id}: {why}"
+                        );
+                        PasswordHistory::default()
+                    })
+            };
+            (
+                Some(decrypt(dbrecord.att_nt_pwd_history_opt(), "NT")),
+                Some(decrypt(dbrecord.att_lm_pwd_history_opt(), "LM")),
+            )
+        } else {
+            (None, None)
+        };
+
+        let laps = if *options.show_laps() {
+            Some(LapsCredentials::from_record(&dbrecord, data_table))
+        } else {
+            None
+        };
+
+        let key_credentials = if *options.show_key_credentials() {
+            Some(KeyCredentials::from(link_table.key_credentials_of(&object_id)))
+        } else {
+            None
+        };
+
+        let password_policy = if *options.show_password_policy() {
+            EffectivePasswordPolicy::from_record(&dbrecord, data_table)
+                .inspect_err(|why| {
+                    log::warn!("unable to determine the password policy of {object_id}: {why}")
+                })
+                .ok()
+        } else {
+            None
+        };
+
+        // the replication metadata can only be displayed as a JSON array
+        let replication_metadata =
+            if *options.show_replication_metadata() && !*options.flat_serialization() {
+                data_table.replication_metadata(&dbrecord)
+            } else {
+                None
+            };
 
         Ok(Self {
             distinguished_name,
@@ -240,6 +370,12 @@ where
             member_of: member_refs,
             specific_attributes,
             sddl: sd.map(|sd| sd.to_string()),
+            nt_password_history,
+            lm_password_history,
+            laps,
+            key_credentials,
+            password_policy,
+            replication_metadata,
             _marker: PhantomData,
             ptr: *dbrecord.ptr(),
         })
@@ -255,16 +391,29 @@ where
     fn from(obj: Object<T, O, A>) -> Self {
         let object_type = O::object_type();
         let upn = match obj.sam_account_name() {
-            Some(n) => Some(n.to_string()),
+            Some(n) => Some(pseudonymize_name(n).to_string()),
             None => obj
                 .rdn()
                 .as_ref()
-                .map(|n| n.name().to_string())
+                .map(|n| pseudonymize_name(n.name()).to_string())
                 .or(Some("UNNAMED_OBJECT".to_string())),
         };
         let inode = obj.ptr().ds_record_id().to_string();
 
         if let Some(upn) = upn {
+            let key_credentials = obj
+                .key_credentials()
+                .iter()
+                .flat_map(|credentials| credentials.credentials())
+                .map(|credential| {
+                    credential.creation_time().map(|ts| {
+                        WindowsFileTime::from(ts)
+                            .cr_entry(&upn, "key credential created", object_type)
+                            .with_inode(&inode)
+                    })
+                })
+                .collect::<Vec<_>>();
+
             vec![
                 obj.record_time().as_ref().map(|ts| {
                     ts.cr_entry(&upn, "record creation time", object_type)
@@ -296,6 +445,7 @@ where
                 }),
             ]
             .into_iter()
+            .chain(key_credentials)
             .flatten()
             .collect()
         } else {
@@ -22,14 +22,17 @@ impl HasSerializableFields for SpecificComputerAttributes {
 }
 
 impl SpecificObjectAttributes for SpecificComputerAttributes {
-    fn from(record: &crate::ntds::DataTableRecord) -> anyhow::Result<Self> {
+    fn from(
+        record: &crate::ntds::DataTableRecord,
+        _data_table: &crate::ntds::DataTable,
+    ) -> anyhow::Result<Self> {
         let creator_sid = record.att_creator_sid_opt()?;
         Ok(Self { creator_sid })
     }
 
-    fn serialize_to<S>(&self, s: &mut S::SerializeStruct) -> Result<(), S::Error>
+    fn serialize_to<S>(&self, s: &mut S) -> Result<(), S::Error>
     where
-        S: serde::Serializer,
+        S: SerializeStruct,
     {
         s.serialize_field("creator_sid", &self.creator_sid)?;
         Ok(())
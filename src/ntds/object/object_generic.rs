@@ -0,0 +1,3 @@
+use crate::ntds::{types, NoSpecificAttributes, Object};
+
+pub type GenericObject<T> = Object<T, types::Object, NoSpecificAttributes>;
@@ -0,0 +1,58 @@
+use std::marker::PhantomData;
+
+use serde::Serialize;
+
+use crate::crypto::{PasswordHash, PekList};
+use crate::{redact_secret, SerializationType};
+
+/// the decrypted contents of `ntPwdHistory` or `lmPwdHistory`. The entry with
+/// index 0 contains the current password hash, higher indices contain older hashes.
+pub struct PasswordHistory<T: SerializationType> {
+    hashes: Vec<PasswordHash>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: SerializationType> PasswordHistory<T> {
+    pub fn decrypt(
+        encrypted: Option<Vec<u8>>,
+        pek_list: &PekList,
+        rid: u32,
+    ) -> anyhow::Result<Self> {
+        let hashes = match encrypted {
+            Some(encrypted) => pek_list.decrypt_hash_history(&encrypted, rid)?,
+            None => Vec::new(),
+        };
+        Ok(Self {
+            hashes,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn hashes(&self) -> &[PasswordHash] {
+        &self.hashes
+    }
+}
+
+impl<T: SerializationType> Default for PasswordHistory<T> {
+    fn default() -> Self {
+        Self {
+            hashes: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: SerializationType> Serialize for PasswordHistory<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        T::serialize_list(
+            self.hashes
+                .iter()
+                .enumerate()
+                .map(|(index, hash)| Some(format!("{index}:{}", redact_secret(&hash.to_string())))),
+            serializer,
+        )
+    }
+}
@@ -1,11 +1,12 @@
+use serde::ser::SerializeStruct;
 use serde::{Deserialize, Serialize};
 
-use crate::ntds::DataTableRecord;
+use crate::ntds::{DataTable, DataTableRecord};
 
 use super::HasSerializableFields;
 
 
 pub trait SpecificObjectAttributes: for<'de> Deserialize<'de> + Serialize + HasSerializableFields {
-    fn from(record: &DataTableRecord) -> anyhow::Result<Self>;
-    fn serialize_to<S>(&self, s: &mut S::SerializeStruct) -> Result<(), S::Error> where S: serde::Serializer;
+    fn from(record: &DataTableRecord, data_table: &DataTable) -> anyhow::Result<Self>;
+    fn serialize_to<S>(&self, s: &mut S) -> Result<(), S::Error> where S: SerializeStruct;
 }
@@ -5,6 +5,9 @@ pub enum ObjectType {
     Person,
     Group,
     Computer,
+
+    /// an object of any other class
+    Object,
 }
 
 pub trait HasObjectType {
@@ -17,6 +20,7 @@ pub mod types {
     pub struct Person;
     pub struct Group;
     pub struct Computer;
+    pub struct Object;
 
     impl HasObjectType for Person {
         fn object_type() -> ObjectType {
@@ -35,4 +39,10 @@ pub mod types {
             ObjectType::Computer
         }
     }
+
+    impl HasObjectType for Object {
+        fn object_type() -> ObjectType {
+            ObjectType::Object
+        }
+    }
 }
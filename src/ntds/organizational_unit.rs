@@ -0,0 +1,63 @@
+use getset::Getters;
+use serde::Serialize;
+
+use crate::win32_types::TruncatedWindowsFileTime;
+use crate::{pseudonymize_dn, pseudonymize_name};
+
+use super::DataTableRecord;
+
+/// `gPOptions` flag which blocks the inheritance of GPOs from parent containers
+const GPO_BLOCK_INHERITANCE: i32 = 1;
+
+/// a container to which GPOs can be linked, which is an
+/// `organizationalUnit` or the domain itself
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct OrganizationalUnit {
+    name: Option<String>,
+    distinguished_name: Option<String>,
+    when_created: Option<TruncatedWindowsFileTime>,
+    when_changed: Option<TruncatedWindowsFileTime>,
+    is_deleted: bool,
+
+    /// GPOs which are linked to parent containers are not applied, unless
+    /// their link is enforced
+    block_inheritance: bool,
+
+    /// the GPOs which are linked to this container (`gPLink`)
+    linked_gpos: String,
+
+    /// all GPOs which apply to objects in this container, including those
+    /// which are inherited from parent containers. GPOs which are linked to
+    /// nearer containers are listed first.
+    effective_gpos: String,
+}
+
+/// returns `true` if the `gPOptions` of `record` block the inheritance of GPOs
+pub fn blocks_gpo_inheritance(record: &DataTableRecord) -> anyhow::Result<bool> {
+    Ok(record
+        .att_gp_options_opt()?
+        .is_some_and(|options| options & GPO_BLOCK_INHERITANCE != 0))
+}
+
+impl OrganizationalUnit {
+    pub fn from_record(
+        record: &DataTableRecord,
+        distinguished_name: Option<String>,
+        linked_gpos: Vec<String>,
+        effective_gpos: Vec<String>,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            name: record
+                .att_object_name2_opt()?
+                .map(|name| pseudonymize_name(name.name()).to_string()),
+            distinguished_name: distinguished_name.map(|dn| pseudonymize_dn(&dn).to_string()),
+            when_created: record.att_when_created_opt()?,
+            when_changed: record.att_when_changed_opt()?,
+            is_deleted: record.att_is_deleted_opt()?.unwrap_or(false),
+            block_inheritance: blocks_gpo_inheritance(record)?,
+            linked_gpos: linked_gpos.join("; "),
+            effective_gpos: effective_gpos.join("; "),
+        })
+    }
+}
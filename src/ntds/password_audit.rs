@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::crypto::PasswordHash;
+use crate::{redact_secret, MembershipSet, SerializationType};
+
+use super::AccountHashes;
+
+/// RIDs of groups whose members are considered to be privileged
+pub const PRIVILEGED_GROUP_RIDS: [u32; 9] = [
+    512, // Domain Admins
+    516, // Domain Controllers
+    518, // Schema Admins
+    519, // Enterprise Admins
+    544, // Administrators
+    548, // Account Operators
+    549, // Server Operators
+    550, // Print Operators
+    551, // Backup Operators
+];
+
+/// NT hashes of known passwords, which can be loaded from a list of hashes
+/// or calculated from a wordlist
+#[derive(Default)]
+pub struct KnownPasswords(HashMap<PasswordHash, Option<String>>);
+
+impl KnownPasswords {
+    /// reads NT hashes from a file, which contains either one hash per line
+    /// or lines in the form `hash:password` (like the hashcat potfile)
+    pub fn load_hashes(&mut self, path: &Path) -> anyhow::Result<()> {
+        let count = self.0.len();
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (hash, password) = match line.split_once(':') {
+                Some((hash, password)) => (hash, Some(password.to_string())),
+                None => (&line[..], None),
+            };
+            match PasswordHash::from_str(hash) {
+                Ok(hash) => {
+                    self.0.insert(hash, password);
+                }
+                Err(why) => log::warn!("ignoring line in {}: {why}", path.display()),
+            }
+        }
+        log::info!(
+            "loaded {} known hashes from {}",
+            self.0.len() - count,
+            path.display()
+        );
+        Ok(())
+    }
+
+    /// calculates the NT hashes of all passwords in a wordlist
+    pub fn load_wordlist(&mut self, path: &Path) -> anyhow::Result<()> {
+        let count = self.0.len();
+        for line in BufReader::new(File::open(path)?).split(b'\n') {
+            let line = line?;
+            let line = line.strip_suffix(b"\r").unwrap_or(&line);
+
+            // we cannot know how to convert other encodings to UTF-16
+            match std::str::from_utf8(line) {
+                Ok(password) => {
+                    self.0.insert(
+                        PasswordHash::nt_hash_of(password),
+                        Some(password.to_string()),
+                    );
+                }
+                Err(_) => log::debug!("ignoring non UTF-8 word in {}", path.display()),
+            }
+        }
+        log::info!(
+            "hashed {} passwords from {}",
+            self.0.len() - count,
+            path.display()
+        );
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// returns `Some` if the hash is known, and the password if it is known as well
+    pub fn get(&self, hash: &PasswordHash) -> Option<&Option<String>> {
+        self.0.get(hash)
+    }
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditFinding {
+    /// the same NT hash is used by more than one account
+    ReusedHash,
+
+    /// the NT hash belongs to the empty password
+    EmptyNtHash,
+
+    /// a LM hash is stored, which is easy to crack
+    LmHashPresent,
+
+    /// the NT hash is contained in the list of known hashes or in the wordlist
+    KnownHash,
+}
+
+/// an account which is relevant for the password audit
+pub struct AuditedAccount<T: SerializationType> {
+    hashes: AccountHashes,
+    member_of: MembershipSet<T>,
+    is_privileged: bool,
+}
+
+impl<T: SerializationType> AuditedAccount<T> {
+    pub fn new(hashes: AccountHashes, member_of: MembershipSet<T>, is_privileged: bool) -> Self {
+        Self {
+            hashes,
+            member_of,
+            is_privileged,
+        }
+    }
+
+    /// returns the current NT hash and all NT hashes from the password history,
+    /// together with their source
+    fn nt_hashes(&self) -> impl Iterator<Item = (String, PasswordHash)> + '_ {
+        std::iter::once(("current".to_string(), *self.hashes.nt_hash())).chain(
+            self.hashes
+                .nt_history()
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(index, hash)| (format!("history{}", index - 1), *hash)),
+        )
+    }
+}
+
+/// a single finding of the password audit
+#[derive(Serialize)]
+#[serde(bound = "T: SerializationType")]
+pub struct PasswordAuditEntry<'a, T: SerializationType> {
+    finding: AuditFinding,
+    user_name: String,
+    rid: u32,
+    distinguished_name: &'a Option<String>,
+    hash_source: String,
+    nt_hash: PasswordHash,
+    reuse_group: Option<usize>,
+    accounts_sharing_hash: Option<usize>,
+    password: Option<String>,
+    is_privileged: bool,
+    is_disabled: bool,
+    is_deleted: bool,
+    member_of: &'a MembershipSet<T>,
+}
+
+impl<'a, T: SerializationType> PasswordAuditEntry<'a, T> {
+    fn new(
+        finding: AuditFinding,
+        account: &'a AuditedAccount<T>,
+        hash_source: String,
+        nt_hash: PasswordHash,
+    ) -> Self {
+        Self {
+            finding,
+            user_name: account.hashes.user_name(),
+            rid: *account.hashes.rid(),
+            distinguished_name: account.hashes.distinguished_name(),
+            hash_source,
+            nt_hash,
+            reuse_group: None,
+            accounts_sharing_hash: None,
+            password: None,
+            is_privileged: account.is_privileged,
+            is_disabled: *account.hashes.is_disabled(),
+            is_deleted: *account.hashes.is_deleted(),
+            member_of: &account.member_of,
+        }
+    }
+
+    fn set_password(&mut self, password: Option<&str>) {
+        self.password = password.map(|password| redact_secret(password).to_string());
+    }
+}
+
+/// checks all accounts for reused, empty and known password hashes
+pub fn audit_passwords<'a, T: SerializationType>(
+    accounts: &'a [AuditedAccount<T>],
+    known_passwords: &KnownPasswords,
+) -> Vec<PasswordAuditEntry<'a, T>> {
+    let mut entries = Vec::new();
+
+    // group all non-empty NT hashes (including the history) by their value
+    let mut users_of_hash: HashMap<PasswordHash, Vec<(usize, String)>> = HashMap::new();
+    for (idx, account) in accounts.iter().enumerate() {
+        for (source, hash) in account.nt_hashes() {
+            if hash != PasswordHash::EMPTY_NT {
+                users_of_hash.entry(hash).or_default().push((idx, source));
+            }
+        }
+    }
+
+    let mut reused_hashes: Vec<_> = users_of_hash
+        .into_iter()
+        .map(|(hash, users)| {
+            let mut account_ids: Vec<_> = users.iter().map(|(idx, _)| *idx).collect();
+            account_ids.dedup();
+            (hash, users, account_ids.len())
+        })
+        .filter(|(_, _, account_count)| *account_count > 1)
+        .collect();
+    reused_hashes.sort_by(|lhs, rhs| {
+        rhs.2
+            .cmp(&lhs.2)
+            .then(lhs.0.as_bytes().cmp(rhs.0.as_bytes()))
+    });
+
+    for (reuse_group, (hash, users, account_count)) in reused_hashes.into_iter().enumerate() {
+        for (idx, source) in users {
+            let mut entry =
+                PasswordAuditEntry::new(AuditFinding::ReusedHash, &accounts[idx], source, hash);
+            entry.reuse_group = Some(reuse_group + 1);
+            entry.accounts_sharing_hash = Some(account_count);
+            entry.set_password(known_passwords.get(&hash).and_then(Option::as_deref));
+            entries.push(entry);
+        }
+    }
+
+    for account in accounts {
+        if *account.hashes.nt_hash() == PasswordHash::EMPTY_NT {
+            entries.push(PasswordAuditEntry::new(
+                AuditFinding::EmptyNtHash,
+                account,
+                "current".to_string(),
+                PasswordHash::EMPTY_NT,
+            ));
+        }
+        if *account.hashes.lm_hash() != PasswordHash::EMPTY_LM {
+            entries.push(PasswordAuditEntry::new(
+                AuditFinding::LmHashPresent,
+                account,
+                "current".to_string(),
+                *account.hashes.nt_hash(),
+            ));
+        }
+        if !known_passwords.is_empty() {
+            for (source, hash) in account.nt_hashes() {
+                if let Some(password) = known_passwords.get(&hash) {
+                    let mut entry =
+                        PasswordAuditEntry::new(AuditFinding::KnownHash, account, source, hash);
+                    entry.set_password(password.as_deref());
+                    entries.push(entry);
+                }
+            }
+        }
+    }
+    entries
+}
@@ -0,0 +1,345 @@
+use std::collections::HashSet;
+
+use getset::Getters;
+use serde::Serialize;
+
+use crate::cache::RecordId;
+use crate::win32_types::{Guid, PasswordProperties, TimeInterval, TruncatedWindowsFileTime};
+use crate::{pseudonymize_dn, pseudonymize_name};
+
+use super::{DataTable, DataTableRecord};
+
+/// the name which is used for the default password policy of the domain
+pub const DOMAIN_PASSWORD_POLICY: &str = "domain default";
+
+/// a password policy, which is either the default policy of the domain or a
+/// fine-grained password policy (`msDS-PasswordSettings` object)
+#[derive(Getters, Serialize, Default)]
+#[getset(get = "pub")]
+pub struct PasswordPolicy {
+    name: String,
+    distinguished_name: Option<String>,
+
+    /// policies with a lower precedence win. The default policy of the
+    /// domain has no precedence, because it is only used if no fine-grained
+    /// password policy applies.
+    precedence: Option<i32>,
+    min_pwd_length: Option<i32>,
+    pwd_history_length: Option<i32>,
+    complexity_enabled: Option<bool>,
+    reversible_encryption_enabled: Option<bool>,
+    min_pwd_age: Option<TimeInterval>,
+    max_pwd_age: Option<TimeInterval>,
+    lockout_threshold: Option<i32>,
+    lockout_duration: Option<TimeInterval>,
+    lockout_observation_window: Option<TimeInterval>,
+
+    /// the users and groups this policy applies to (`msDS-PSOAppliesTo`)
+    applies_to: String,
+    when_created: Option<TruncatedWindowsFileTime>,
+    when_changed: Option<TruncatedWindowsFileTime>,
+
+    #[serde(skip)]
+    #[getset(skip)]
+    applies_to_ids: HashSet<RecordId>,
+
+    /// breaks ties between policies with the same precedence
+    #[serde(skip)]
+    #[getset(skip)]
+    guid: Option<Guid>,
+}
+
+/// the ids of the attributes of `msDS-PasswordSettings`, which must be
+/// looked up in the schema
+pub struct PasswordSettingsAttributeIds {
+    pub precedence: i32,
+    pub min_pwd_length: i32,
+    pub pwd_history_length: i32,
+    pub complexity_enabled: i32,
+    pub reversible_encryption_enabled: i32,
+    pub min_pwd_age: i32,
+    pub max_pwd_age: i32,
+    pub lockout_threshold: i32,
+    pub lockout_duration: i32,
+    pub lockout_observation_window: i32,
+}
+
+impl PasswordSettingsAttributeIds {
+    pub fn from_schema(data_table: &DataTable) -> anyhow::Result<Self> {
+        Ok(Self {
+            precedence: data_table.attribute_id("msDS-PasswordSettingsPrecedence")?,
+            min_pwd_length: data_table.attribute_id("msDS-MinimumPasswordLength")?,
+            pwd_history_length: data_table.attribute_id("msDS-PasswordHistoryLength")?,
+            complexity_enabled: data_table.attribute_id("msDS-PasswordComplexityEnabled")?,
+            reversible_encryption_enabled: data_table
+                .attribute_id("msDS-PasswordReversibleEncryptionEnabled")?,
+            min_pwd_age: data_table.attribute_id("msDS-MinimumPasswordAge")?,
+            max_pwd_age: data_table.attribute_id("msDS-MaximumPasswordAge")?,
+            lockout_threshold: data_table.attribute_id("msDS-LockoutThreshold")?,
+            lockout_duration: data_table.attribute_id("msDS-LockoutDuration")?,
+            lockout_observation_window: data_table
+                .attribute_id("msDS-LockoutObservationWindow")?,
+        })
+    }
+}
+
+impl PasswordPolicy {
+    /// reads the default password policy, which is stored in the domain object
+    pub fn from_domain(domain: &DataTableRecord) -> anyhow::Result<Self> {
+        let pwd_properties = domain.att_pwd_properties_opt()?;
+        Ok(Self {
+            name: DOMAIN_PASSWORD_POLICY.to_string(),
+            min_pwd_length: domain.att_min_pwd_length_opt()?,
+            pwd_history_length: domain.att_pwd_history_length_opt()?,
+            complexity_enabled: pwd_properties.map(|properties| {
+                properties.contains(PasswordProperties::DOMAIN_PASSWORD_COMPLEX)
+            }),
+            reversible_encryption_enabled: pwd_properties.map(|properties| {
+                properties.contains(PasswordProperties::DOMAIN_PASSWORD_STORE_CLEARTEXT)
+            }),
+            min_pwd_age: domain.att_min_pwd_age_opt()?,
+            max_pwd_age: domain.att_max_pwd_age_opt()?,
+            lockout_threshold: domain.att_lockout_threshold_opt()?,
+            lockout_duration: domain.att_lockout_duration_opt()?,
+            lockout_observation_window: domain.att_lockout_observation_window_opt()?,
+            when_created: domain.att_when_created_opt()?,
+            when_changed: domain.att_when_changed_opt()?,
+            ..Default::default()
+        })
+    }
+
+    /// reads a fine-grained password policy. `applies_to` contains the ids
+    /// and the (not yet pseudonymized) distinguished names of the users and
+    /// groups the policy applies to.
+    pub fn from_record(
+        record: &DataTableRecord,
+        distinguished_name: Option<String>,
+        applies_to: Vec<(RecordId, String)>,
+        attribute_ids: &PasswordSettingsAttributeIds,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            name: record
+                .att_object_name_opt()?
+                .map(|name| pseudonymize_name(name.name()).to_string())
+                .unwrap_or_default(),
+            distinguished_name: distinguished_name.map(|dn| pseudonymize_dn(&dn).to_string()),
+            precedence: record.get_value_by_attribute_id_opt(attribute_ids.precedence)?,
+            min_pwd_length: record.get_value_by_attribute_id_opt(attribute_ids.min_pwd_length)?,
+            pwd_history_length: record
+                .get_value_by_attribute_id_opt(attribute_ids.pwd_history_length)?,
+            complexity_enabled: record
+                .get_value_by_attribute_id_opt(attribute_ids.complexity_enabled)?,
+            reversible_encryption_enabled: record
+                .get_value_by_attribute_id_opt(attribute_ids.reversible_encryption_enabled)?,
+            min_pwd_age: record.get_value_by_attribute_id_opt(attribute_ids.min_pwd_age)?,
+            max_pwd_age: record.get_value_by_attribute_id_opt(attribute_ids.max_pwd_age)?,
+            lockout_threshold: record
+                .get_value_by_attribute_id_opt(attribute_ids.lockout_threshold)?,
+            lockout_duration: record.get_value_by_attribute_id_opt(attribute_ids.lockout_duration)?,
+            lockout_observation_window: record
+                .get_value_by_attribute_id_opt(attribute_ids.lockout_observation_window)?,
+            applies_to: applies_to
+                .iter()
+                .map(|(_, dn)| pseudonymize_dn(dn).to_string())
+                .collect::<Vec<_>>()
+                .join("; "),
+            when_created: record.att_when_created_opt()?,
+            when_changed: record.att_when_changed_opt()?,
+            applies_to_ids: applies_to.into_iter().map(|(id, _)| id).collect(),
+            guid: record.att_object_guid_opt()?,
+        })
+    }
+}
+
+/// the default password policy of the domain, together with all
+/// fine-grained password policies
+pub struct PasswordPolicies {
+    domain: PasswordPolicy,
+
+    /// the fine-grained password policies, ordered by their precedence and,
+    /// if two policies have the same precedence, by their `objectGUID`
+    settings: Vec<PasswordPolicy>,
+}
+
+impl PasswordPolicies {
+    /// reads the default password policy from the domain object and all
+    /// fine-grained password policies
+    pub fn read(data_table: &DataTable) -> anyhow::Result<Self> {
+        let domain = data_table
+            .data_table()
+            .data_table_record_from(*data_table.special_records().domain_root().record_ptr())?;
+        let domain_policy = PasswordPolicy::from_domain(&domain)?;
+
+        // fine-grained password policies are available since Windows Server
+        // 2008, so older schemas do not contain their attributes
+        let records: Vec<_> = data_table.records_of_class("ms-DS-Password-Settings").collect();
+        if records.is_empty() {
+            return Ok(Self::new(domain_policy, Vec::new()));
+        }
+
+        let attribute_ids = PasswordSettingsAttributeIds::from_schema(data_table)?;
+        let mut settings = Vec::new();
+        for record in records {
+            let applies_to = data_table
+                .link_table()
+                .forward_links_of("ms-DS-PSO-Applies-To", record.ptr().ds_record_id())
+                .iter()
+                .map(|ptr| {
+                    let dn = data_table
+                        .object_tree()
+                        .dn_of(ptr)
+                        .unwrap_or_else(|| ptr.ds_record_id().to_string());
+                    (*ptr.ds_record_id(), dn)
+                })
+                .collect();
+            settings.push(PasswordPolicy::from_record(
+                &record,
+                data_table.object_tree().dn_of(record.ptr()),
+                applies_to,
+                &attribute_ids,
+            )?);
+        }
+        log::info!("found {} fine-grained password policies", settings.len());
+        Ok(Self::new(domain_policy, settings))
+    }
+
+    pub fn new(domain: PasswordPolicy, mut settings: Vec<PasswordPolicy>) -> Self {
+        let guid = |policy: &PasswordPolicy| {
+            policy
+                .guid
+                .as_ref()
+                .map(Guid::to_bytes_le)
+                .unwrap_or([0xff; 16])
+        };
+        settings.sort_by(|a, b| {
+            a.precedence
+                .unwrap_or(i32::MAX)
+                .cmp(&b.precedence.unwrap_or(i32::MAX))
+                .then_with(|| guid(a).cmp(&guid(b)))
+        });
+        Self { domain, settings }
+    }
+
+    /// returns the policy which applies to the user `user`, who is a
+    /// (possibly indirect) member of `groups`. Policies which are applied to
+    /// the user directly take precedence over those which are applied to
+    /// one of its groups. If no fine-grained password policy applies, the
+    /// default policy of the domain is used.
+    pub fn effective_policy(&self, user: &RecordId, groups: &HashSet<RecordId>) -> &PasswordPolicy {
+        self.settings
+            .iter()
+            .find(|policy| policy.applies_to_ids.contains(user))
+            .or_else(|| {
+                self.settings
+                    .iter()
+                    .find(|policy| !policy.applies_to_ids.is_disjoint(groups))
+            })
+            .unwrap_or(&self.domain)
+    }
+
+    /// iterates over the default policy of the domain and all fine-grained
+    /// password policies
+    pub fn iter(&self) -> impl Iterator<Item = &PasswordPolicy> {
+        std::iter::once(&self.domain).chain(self.settings.iter())
+    }
+}
+
+/// returns the ids of all global security groups `record` is a (possibly
+/// indirect) member of. Fine-grained password policies which are applied
+/// to other groups are ignored by Active Directory.
+pub fn global_security_groups_of(
+    data_table: &DataTable,
+    record: &DataTableRecord,
+) -> HashSet<RecordId> {
+    let mut groups = HashSet::new();
+    let mut pending = vec![*record.ptr().ds_record_id()];
+    while let Some(object_id) = pending.pop() {
+        for group in data_table.link_table().member_of(&object_id).into_iter().flatten() {
+            if groups.insert(*group) {
+                pending.push(*group.ds_record_id());
+            }
+        }
+    }
+    groups
+        .into_iter()
+        .filter(|group| {
+            match data_table
+                .data_table()
+                .data_table_record_from(*group)
+                .map_err(anyhow::Error::from)
+                .and_then(|group| group.att_group_type_opt())
+            {
+                Ok(group_type) => group_type.is_some_and(|t| t.is_global_security_group()),
+                Err(why) => {
+                    log::warn!("unable to read the group type of {group}: {why}");
+                    false
+                }
+            }
+        })
+        .map(|group| *group.ds_record_id())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::cache::RecordId;
+    use crate::win32_types::Guid;
+
+    use super::{PasswordPolicies, PasswordPolicy, DOMAIN_PASSWORD_POLICY};
+
+    fn policy(name: &str, precedence: i32, applies_to: &[i32], guid: u8) -> PasswordPolicy {
+        PasswordPolicy {
+            name: name.to_string(),
+            precedence: Some(precedence),
+            applies_to_ids: applies_to.iter().map(|id| RecordId::from(*id)).collect(),
+            guid: Some(Guid::from_bytes_le([guid; 16])),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_effective_policy() {
+        let policies = PasswordPolicies::new(
+            PasswordPolicy {
+                name: DOMAIN_PASSWORD_POLICY.to_string(),
+                ..Default::default()
+            },
+            vec![
+                policy("admins", 10, &[100], 1),
+                policy("user", 20, &[1], 2),
+                policy("service accounts", 5, &[101, 2], 3),
+                policy("a tie", 10, &[102], 4),
+                policy("z tie", 10, &[102], 2),
+            ],
+        );
+        let groups = |ids: &[i32]| -> HashSet<RecordId> {
+            ids.iter().map(|id| RecordId::from(*id)).collect()
+        };
+
+        // policies which are applied directly win, regardless of their precedence
+        assert_eq!(
+            policies.effective_policy(&RecordId::from(1), &groups(&[100, 101])).name(),
+            "user"
+        );
+        assert_eq!(
+            policies.effective_policy(&RecordId::from(3), &groups(&[100, 101])).name(),
+            "service accounts"
+        );
+        assert_eq!(
+            policies.effective_policy(&RecordId::from(3), &groups(&[100])).name(),
+            "admins"
+        );
+        assert_eq!(
+            policies.effective_policy(&RecordId::from(3), &groups(&[])).name(),
+            DOMAIN_PASSWORD_POLICY
+        );
+
+        // policies with the same precedence are ordered by their GUID, not by their name
+        assert_eq!(
+            policies.effective_policy(&RecordId::from(3), &groups(&[102])).name(),
+            "z tie"
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use std::collections::HashSet;
+
+use getset::Getters;
+use serde::Serialize;
+
+use crate::crypto::KeyCredential;
+use crate::win32_types::{Guid, WindowsFileTime};
+use crate::{pseudonymize_dn, pseudonymize_name};
+
+use super::DataTableRecord;
+
+/// a key credential (a value of `msDS-KeyCredentialLink`) which does not
+/// belong to a registered device. Such keys can be used to authenticate as
+/// their owner using PKINIT ("Shadow Credentials").
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct ShadowCredential {
+    sam_account_name: Option<String>,
+    distinguished_name: Option<String>,
+    key_id: Option<String>,
+    key_usage: Option<String>,
+    key_source: Option<String>,
+    key_type: Option<String>,
+    device_id: Option<Guid>,
+    creation_time: Option<WindowsFileTime>,
+    last_logon_time: Option<WindowsFileTime>,
+
+    /// explains why this key is suspicious
+    reason: &'static str,
+}
+
+impl ShadowCredential {
+    /// returns `None` if `credential` belongs to one of the devices in
+    /// `registered_devices`
+    pub fn from_record(
+        owner: &DataTableRecord,
+        distinguished_name: Option<String>,
+        credential: &KeyCredential,
+        registered_devices: &HashSet<Guid>,
+    ) -> anyhow::Result<Option<Self>> {
+        let reason = match credential.device_id() {
+            None => "the key has no device id",
+            Some(device_id) if !registered_devices.contains(device_id) => {
+                "there is no registered device with this id"
+            }
+            Some(_) => return Ok(None),
+        };
+
+        Ok(Some(Self {
+            sam_account_name: owner
+                .att_sam_account_name_opt()?
+                .map(|name| pseudonymize_name(&name).to_string()),
+            distinguished_name: distinguished_name.map(|dn| pseudonymize_dn(&dn).to_string()),
+            key_id: credential.key_id_string(),
+            key_usage: credential.key_usage().map(|usage| usage.to_string()),
+            key_source: credential.key_source().map(|source| source.to_string()),
+            key_type: credential.key_type(),
+            device_id: credential.device_id().clone(),
+            creation_time: credential.creation_time().map(WindowsFileTime::from),
+            last_logon_time: credential.last_logon_time().map(WindowsFileTime::from),
+            reason,
+        }))
+    }
+}
@@ -0,0 +1,526 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use getset::Getters;
+use serde::Serialize;
+
+use crate::cache::RecordId;
+use crate::win32_types::{Guid, NtdsDsaOptions, TruncatedWindowsFileTime};
+use crate::{pseudonymize_dn, pseudonymize_dns_name, pseudonymize_name, redact_free_text};
+
+use super::{DataTable, DataTableRecord};
+
+/// the `nTDSDSA` object (`NTDS Settings`) of a server, which only exists if
+/// the server is (or has been) a domain controller
+pub struct DsaSettings<'r, 'info, 'db> {
+    pub record: &'r DataTableRecord<'info, 'db>,
+
+    /// the object category is `nTDSDSARO` instead of `nTDSDSA`
+    pub is_read_only: bool,
+}
+
+/// a site (`site` object in `CN=Sites` of the configuration partition)
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct Site {
+    name: String,
+    distinguished_name: Option<String>,
+    description: Option<String>,
+    location: Option<String>,
+    subnets: String,
+    site_links: String,
+    servers: String,
+    when_created: Option<TruncatedWindowsFileTime>,
+    when_changed: Option<TruncatedWindowsFileTime>,
+    is_deleted: bool,
+}
+
+/// an IP subnet, which is assigned to a site (`subnet` object)
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct Subnet {
+    name: String,
+    site: Option<String>,
+    description: Option<String>,
+    location: Option<String>,
+    when_created: Option<TruncatedWindowsFileTime>,
+    when_changed: Option<TruncatedWindowsFileTime>,
+    is_deleted: bool,
+}
+
+/// a link between sites, which is used to create the replication topology
+/// between sites (`siteLink` object)
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct SiteLink {
+    name: String,
+
+    /// the inter-site transport (`IP` or `SMTP`)
+    transport: Option<String>,
+    cost: Option<i32>,
+
+    /// the replication interval in minutes
+    repl_interval: Option<i32>,
+    options: Option<i32>,
+    sites: String,
+    description: Option<String>,
+    when_created: Option<TruncatedWindowsFileTime>,
+    when_changed: Option<TruncatedWindowsFileTime>,
+    is_deleted: bool,
+}
+
+/// a server object in a site, together with the settings of the domain
+/// controller which runs on this server
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct SiteServer {
+    name: String,
+    site: Option<String>,
+    dns_host_name: Option<String>,
+
+    /// the computer account of the server (`serverReference`)
+    computer_account: Option<String>,
+    is_domain_controller: bool,
+    is_global_catalog: bool,
+    is_read_only: bool,
+    dsa_options: Option<NtdsDsaOptions>,
+
+    /// the `objectGUID` of the `nTDSDSA` object
+    dsa_guid: Option<Guid>,
+    invocation_id: Option<Guid>,
+
+    /// invocation ids which have been used before the domain controller has
+    /// been restored from a backup
+    retired_invocation_ids: String,
+
+    /// the creation time of the `nTDSDSA` object, which is the time when the
+    /// server has been promoted to a domain controller
+    promoted: Option<TruncatedWindowsFileTime>,
+    when_created: Option<TruncatedWindowsFileTime>,
+    when_changed: Option<TruncatedWindowsFileTime>,
+
+    /// the server object has been deleted, which normally happens if the
+    /// domain controller has been demoted
+    is_deleted: bool,
+}
+
+/// collects the names of the objects which belong to a site, such as the
+/// subnets which are assigned to it
+#[derive(Default)]
+struct SiteMembers(HashMap<RecordId, Vec<String>>);
+
+impl SiteMembers {
+    fn add(&mut self, name: &str, sites: impl IntoIterator<Item = RecordId>) {
+        for site in sites {
+            self.0.entry(site).or_default().push(name.to_string());
+        }
+    }
+
+    fn take(&mut self, site: &RecordId) -> Vec<String> {
+        self.0.remove(site).unwrap_or_default()
+    }
+}
+
+/// selects the `nTDSDSA` object of every server. A server which has been
+/// demoted and promoted again has several `nTDSDSA` objects, of which only
+/// the newest one is not deleted.
+struct DsaSelection<T>(HashMap<RecordId, (DsaOrder, T)>);
+
+/// live objects are preferred over deleted ones, and newer ones over older
+/// ones
+type DsaOrder = (bool, Option<DateTime<Utc>>);
+
+impl<T> Default for DsaSelection<T> {
+    fn default() -> Self {
+        Self(HashMap::new())
+    }
+}
+
+impl<T> DsaSelection<T> {
+    fn insert(
+        &mut self,
+        server: RecordId,
+        is_deleted: bool,
+        when_created: Option<DateTime<Utc>>,
+        dsa: T,
+    ) {
+        let order = (!is_deleted, when_created);
+        match self.0.entry(server) {
+            Entry::Occupied(mut entry) => {
+                if order > entry.get().0 {
+                    entry.insert((order, dsa));
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert((order, dsa));
+            }
+        }
+    }
+
+    fn get(&self, server: &RecordId) -> Option<&T> {
+        self.0.get(server).map(|(_, dsa)| dsa)
+    }
+}
+
+/// returns the site which contains the server object `server`
+pub fn site_of_server(
+    data_table: &DataTable,
+    server: &DataTableRecord,
+) -> anyhow::Result<Option<RecordId>> {
+    let container = data_table.container_of(server)?;
+    Ok(site_of_servers_container(
+        data_table
+            .data_table()
+            .metadata()
+            .record(&container)
+            .map(|entry| (entry.rdn().name().as_str(), *entry.parent())),
+    ))
+}
+
+/// servers are stored in `CN=Servers,CN=<site>,CN=Sites`, so the site is the
+/// parent of the container of a server, if this container is `CN=Servers`
+fn site_of_servers_container(container: Option<(&str, RecordId)>) -> Option<RecordId> {
+    container
+        .filter(|(name, _)| name.eq_ignore_ascii_case("Servers"))
+        .map(|(_, parent)| parent)
+}
+
+impl Site {
+    /// reads all sites, including deleted ones
+    pub fn read_all(data_table: &DataTable) -> anyhow::Result<Vec<Self>> {
+        let metadata = data_table.data_table().metadata();
+        let link_table = data_table.link_table();
+        let mut subnets = SiteMembers::default();
+        for subnet in data_table.records_of_class("Subnet") {
+            let sites = link_table.forward_links_of("Site-Object", subnet.ptr().ds_record_id());
+            subnets.add(
+                metadata[subnet.ptr()].rdn().name(),
+                sites.iter().map(|site| *site.ds_record_id()),
+            );
+        }
+        let mut site_links = SiteMembers::default();
+        for site_link in data_table.records_of_class("Site-Link") {
+            let sites = link_table.forward_links_of("Site-List", site_link.ptr().ds_record_id());
+            site_links.add(
+                metadata[site_link.ptr()].rdn().name(),
+                sites.iter().map(|site| *site.ds_record_id()),
+            );
+        }
+        let mut servers = SiteMembers::default();
+        for server in data_table.records_of_class("Server") {
+            servers.add(
+                metadata[server.ptr()].rdn().name(),
+                site_of_server(data_table, &server)?,
+            );
+        }
+
+        data_table
+            .records_of_class("Site")
+            .map(|site| {
+                let id = site.ptr().ds_record_id();
+                Self::from_record(
+                    &site,
+                    metadata[site.ptr()].rdn().name(),
+                    data_table.object_tree().dn_of(site.ptr()),
+                    subnets.take(id),
+                    site_links.take(id),
+                    servers.take(id),
+                )
+            })
+            .collect()
+    }
+
+    pub fn from_record(
+        record: &DataTableRecord,
+        name: &str,
+        distinguished_name: Option<String>,
+        subnets: Vec<String>,
+        site_links: Vec<String>,
+        servers: Vec<String>,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            name: pseudonymize_name(name).to_string(),
+            distinguished_name: distinguished_name.map(|dn| pseudonymize_dn(&dn).to_string()),
+            description: record
+                .att_description_opt()?
+                .map(|description| redact_free_text(&description).to_string()),
+            location: record
+                .att_location_opt()?
+                .map(|location| redact_free_text(&location).to_string()),
+            subnets: subnets.join("; "),
+            site_links: site_links.join("; "),
+            servers: servers
+                .iter()
+                .map(|server| pseudonymize_name(server).to_string())
+                .collect::<Vec<_>>()
+                .join("; "),
+            when_created: record.att_when_created_opt()?,
+            when_changed: record.att_when_changed_opt()?,
+            is_deleted: record.att_is_deleted_opt()?.unwrap_or(false),
+        })
+    }
+}
+
+impl Subnet {
+    /// reads all subnets, together with the sites they are assigned to
+    pub fn read_all(data_table: &DataTable) -> anyhow::Result<Vec<Self>> {
+        let metadata = data_table.data_table().metadata();
+        data_table
+            .records_of_class("Subnet")
+            .map(|subnet| {
+                let sites = data_table.linked_names("Site-Object", &subnet);
+                Self::from_record(
+                    &subnet,
+                    metadata[subnet.ptr()].rdn().name(),
+                    sites.into_iter().next(),
+                )
+            })
+            .collect()
+    }
+
+    pub fn from_record(
+        record: &DataTableRecord,
+        name: &str,
+        site: Option<String>,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            name: name.to_string(),
+            site: site.map(|site| pseudonymize_name(&site).to_string()),
+            description: record
+                .att_description_opt()?
+                .map(|description| redact_free_text(&description).to_string()),
+            location: record
+                .att_location_opt()?
+                .map(|location| redact_free_text(&location).to_string()),
+            when_created: record.att_when_created_opt()?,
+            when_changed: record.att_when_changed_opt()?,
+            is_deleted: record.att_is_deleted_opt()?.unwrap_or(false),
+        })
+    }
+}
+
+impl SiteLink {
+    /// reads all site links, together with the sites they connect
+    pub fn read_all(data_table: &DataTable) -> anyhow::Result<Vec<Self>> {
+        let metadata = data_table.data_table().metadata();
+        data_table
+            .records_of_class("Site-Link")
+            .map(|site_link| {
+                // site links are stored in `CN=<transport>,CN=Inter-Site Transports`
+                let transport = data_table.name_of_record(&data_table.container_of(&site_link)?);
+                Self::from_record(
+                    &site_link,
+                    metadata[site_link.ptr()].rdn().name(),
+                    transport,
+                    data_table.linked_names("Site-List", &site_link),
+                )
+            })
+            .collect()
+    }
+
+    pub fn from_record(
+        record: &DataTableRecord,
+        name: &str,
+        transport: Option<String>,
+        sites: Vec<String>,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            name: name.to_string(),
+            transport,
+            cost: record.att_cost_opt()?,
+            repl_interval: record.att_repl_interval_opt()?,
+            options: record.att_options_opt()?,
+            sites: sites
+                .iter()
+                .map(|site| pseudonymize_name(site).to_string())
+                .collect::<Vec<_>>()
+                .join("; "),
+            description: record
+                .att_description_opt()?
+                .map(|description| redact_free_text(&description).to_string()),
+            when_created: record.att_when_created_opt()?,
+            when_changed: record.att_when_changed_opt()?,
+            is_deleted: record.att_is_deleted_opt()?.unwrap_or(false),
+        })
+    }
+}
+
+impl SiteServer {
+    /// reads all server objects, including those of demoted domain
+    /// controllers which are still present as deleted objects
+    pub fn read_all(data_table: &DataTable) -> anyhow::Result<Vec<Self>> {
+        let metadata = data_table.data_table().metadata();
+        let dsas: Vec<_> = data_table
+            .records_of_class("NTDS-DSA")
+            .map(|record| (record, false))
+            .chain(data_table.records_of_class("NTDS-DSA-RO").map(|record| (record, true)))
+            .collect();
+
+        let mut dsa_of_server = DsaSelection::default();
+        for (dsa, is_read_only) in dsas.iter() {
+            dsa_of_server.insert(
+                data_table.container_of(dsa)?,
+                dsa.att_is_deleted_opt()?.unwrap_or(false),
+                dsa.att_when_created_opt()?.map(DateTime::<Utc>::from),
+                DsaSettings {
+                    record: dsa,
+                    is_read_only: *is_read_only,
+                },
+            );
+        }
+
+        data_table
+            .records_of_class("Server")
+            .map(|server| {
+                let site = site_of_server(data_table, &server)?
+                    .and_then(|site| data_table.name_of_record(&site));
+                let computer_account = data_table
+                    .link_table()
+                    .forward_links_of("Server-Reference", server.ptr().ds_record_id())
+                    .first()
+                    .and_then(|ptr| data_table.object_tree().dn_of(ptr));
+                Self::from_record(
+                    &server,
+                    metadata[server.ptr()].rdn().name(),
+                    site,
+                    computer_account,
+                    dsa_of_server.get(server.ptr().ds_record_id()),
+                )
+            })
+            .collect()
+    }
+
+    /// `computer_account` is the (not yet pseudonymized) distinguished name
+    /// of the computer account of this server
+    pub fn from_record(
+        record: &DataTableRecord,
+        name: &str,
+        site: Option<String>,
+        computer_account: Option<String>,
+        dsa: Option<&DsaSettings>,
+    ) -> anyhow::Result<Self> {
+        let dsa_options = dsa
+            .map(|dsa| dsa.record.att_options_opt())
+            .transpose()?
+            .flatten()
+            .map(NtdsDsaOptions::from);
+        let retired_invocation_ids = match dsa {
+            Some(dsa) => dsa
+                .record
+                .att_retired_repl_dsa_signatures_opt()?
+                .map(|signatures| {
+                    signatures
+                        .signatures()
+                        .iter()
+                        .map(|signature| signature.invocation_id().to_string())
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                })
+                .unwrap_or_default(),
+            None => String::new(),
+        };
+
+        Ok(Self {
+            name: pseudonymize_name(name).to_string(),
+            site: site.map(|site| pseudonymize_name(&site).to_string()),
+            dns_host_name: record
+                .att_dns_host_name_opt()?
+                .map(|name| pseudonymize_dns_name(&name).to_string()),
+            computer_account: computer_account.map(|dn| pseudonymize_dn(&dn).to_string()),
+            is_domain_controller: dsa.is_some(),
+            is_global_catalog: dsa_options
+                .is_some_and(|options| options.contains(NtdsDsaOptions::NTDSDSA_OPT_IS_GC)),
+            is_read_only: dsa.is_some_and(|dsa| dsa.is_read_only),
+            dsa_options,
+            dsa_guid: dsa
+                .map(|dsa| dsa.record.att_object_guid_opt())
+                .transpose()?
+                .flatten(),
+            invocation_id: dsa
+                .map(|dsa| dsa.record.att_invocation_id_opt())
+                .transpose()?
+                .flatten(),
+            retired_invocation_ids,
+            promoted: dsa
+                .map(|dsa| dsa.record.att_when_created_opt())
+                .transpose()?
+                .flatten(),
+            when_created: record.att_when_created_opt()?,
+            when_changed: record.att_when_changed_opt()?,
+            is_deleted: record.att_is_deleted_opt()?.unwrap_or(false),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::cache::RecordId;
+    use crate::win32_types::NtdsDsaOptions;
+
+    use super::{site_of_servers_container, DsaSelection, SiteMembers};
+
+    #[test]
+    fn test_site_members() {
+        let mut subnets = SiteMembers::default();
+        subnets.add("10.0.0.0/24", [RecordId::from(1)]);
+        subnets.add("10.0.1.0/24", [RecordId::from(2)]);
+        subnets.add("10.0.2.0/24", [RecordId::from(1)]);
+
+        let mut site_links = SiteMembers::default();
+        site_links.add("DEFAULTIPSITELINK", [RecordId::from(1), RecordId::from(2)]);
+
+        assert_eq!(subnets.take(&RecordId::from(1)), ["10.0.0.0/24", "10.0.2.0/24"]);
+        assert_eq!(subnets.take(&RecordId::from(2)), ["10.0.1.0/24"]);
+        assert!(subnets.take(&RecordId::from(3)).is_empty());
+        assert_eq!(site_links.take(&RecordId::from(1)), ["DEFAULTIPSITELINK"]);
+        assert_eq!(site_links.take(&RecordId::from(2)), ["DEFAULTIPSITELINK"]);
+    }
+
+    #[test]
+    fn test_site_of_server() {
+        let site = RecordId::from(42);
+        assert_eq!(site_of_servers_container(Some(("Servers", site))), Some(site));
+        assert_eq!(site_of_servers_container(Some(("SERVERS", site))), Some(site));
+        assert_eq!(site_of_servers_container(Some(("Deleted Objects", site))), None);
+        assert_eq!(site_of_servers_container(None), None);
+    }
+
+    #[test]
+    fn test_dsa_selection() {
+        let server = RecordId::from(1);
+        let old = Utc.with_ymd_and_hms(2019, 1, 1, 0, 0, 0).single();
+        let new = Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).single();
+
+        let mut selection = DsaSelection::default();
+        selection.insert(server, false, old, "live");
+        selection.insert(server, true, new, "newer, but deleted");
+        assert_eq!(selection.get(&server), Some(&"live"));
+
+        let mut selection = DsaSelection::default();
+        selection.insert(server, true, old, "old");
+        selection.insert(server, true, new, "new");
+        selection.insert(server, true, None, "unknown");
+        assert_eq!(selection.get(&server), Some(&"new"));
+        assert_eq!(selection.get(&RecordId::from(2)), None);
+    }
+
+    #[test]
+    fn test_dsa_options() {
+        let options = NtdsDsaOptions::from(0x61);
+        assert!(options.contains(NtdsDsaOptions::NTDSDSA_OPT_IS_GC));
+        assert!(options.contains(NtdsDsaOptions::NTDSDSA_OPT_GENERATE_OWN_TOPO));
+        assert!(options.contains(NtdsDsaOptions::NTDSDSA_OPT_BLOCK_RPC));
+        assert!(!options.contains(NtdsDsaOptions::NTDSDSA_OPT_DISABLE_SPN_REGISTRATION));
+        assert_eq!(
+            serde_json::to_value(options).unwrap(),
+            "NTDSDSA_OPT_IS_GC | NTDSDSA_OPT_GENERATE_OWN_TOPO | NTDSDSA_OPT_BLOCK_RPC"
+        );
+
+        // unknown bits are ignored, even if the highest bit is set
+        let options = NtdsDsaOptions::from(i32::MIN | 0x10);
+        assert!(options == NtdsDsaOptions::NTDSDSA_OPT_DISABLE_SPN_REGISTRATION);
+    }
+}
@@ -0,0 +1,162 @@
+use getset::Getters;
+use serde::Serialize;
+
+use crate::crypto::{inter_realm_salt, PasswordHash, PekList, TrustAuthInformation};
+use crate::win32_types::{
+    Sid, TruncatedWindowsFileTime, TrustAttributes, TrustDirection, TrustType, WindowsFileTime,
+};
+use crate::{pseudonymize_dns_name, pseudonymize_name, redact_secret};
+
+use super::DataTableRecord;
+
+/// a trust relationship to another domain (`trustedDomain` object),
+/// together with the trust keys, if a boot key has been provided
+#[derive(Getters, Serialize)]
+#[getset(get = "pub")]
+pub struct TrustedDomain {
+    trust_partner: Option<String>,
+    flat_name: Option<String>,
+    security_identifier: Option<Sid>,
+    trust_direction: Option<TrustDirection>,
+    trust_type: Option<TrustType>,
+    trust_attributes: Option<TrustAttributes>,
+    sid_filtering: Option<&'static str>,
+    when_created: Option<TruncatedWindowsFileTime>,
+    when_changed: Option<TruncatedWindowsFileTime>,
+
+    /// the key of `krbtgt/<this domain>@<trust partner>`, which is used for
+    /// referral tickets issued by the trust partner
+    incoming_last_set: Option<WindowsFileTime>,
+    incoming_nt_hash: Option<PasswordHash>,
+    incoming_aes128_key: Option<String>,
+    incoming_aes256_key: Option<String>,
+    incoming_previous_nt_hash: Option<PasswordHash>,
+
+    /// the key of `krbtgt/<trust partner>@<this domain>`, which is used for
+    /// referral tickets issued by this domain
+    outgoing_last_set: Option<WindowsFileTime>,
+    outgoing_nt_hash: Option<PasswordHash>,
+    outgoing_aes128_key: Option<String>,
+    outgoing_aes256_key: Option<String>,
+    outgoing_previous_nt_hash: Option<PasswordHash>,
+}
+
+/// the keys of one direction of a trust
+#[derive(Default)]
+struct TrustKeys {
+    last_set: Option<WindowsFileTime>,
+    nt_hash: Option<PasswordHash>,
+    aes128_key: Option<String>,
+    aes256_key: Option<String>,
+    previous_nt_hash: Option<PasswordHash>,
+}
+
+impl TrustKeys {
+    /// decrypts the keys of one direction of the trust. If this fails, the
+    /// keys are left empty, so that the trust itself is still displayed.
+    fn decrypt(
+        encrypted: anyhow::Result<Option<Vec<u8>>>,
+        pek_list: &PekList,
+        salt: &str,
+        description: &str,
+    ) -> Self {
+        encrypted
+            .and_then(|encrypted| Self::try_decrypt(encrypted, pek_list, salt))
+            .unwrap_or_else(|why| {
+                log::warn!("unable to decrypt the {description}: {why}");
+                Self::default()
+            })
+    }
+
+    fn try_decrypt(
+        encrypted: Option<Vec<u8>>,
+        pek_list: &PekList,
+        salt: &str,
+    ) -> anyhow::Result<Self> {
+        let encrypted = match encrypted {
+            Some(encrypted) => encrypted,
+            None => return Ok(Self::default()),
+        };
+        let info = TrustAuthInformation::try_from(&pek_list.decrypt_secret(&encrypted)?[..])?;
+        let aes_key = |size| {
+            info.current_password()
+                .and_then(|password| password.aes_key(salt, size))
+                .map(|key| redact_secret(&hex::encode(key)).to_string())
+        };
+        Ok(Self {
+            last_set: info
+                .current_password()
+                .map(|password| WindowsFileTime::from(*password.last_update_time())),
+            nt_hash: info
+                .current_password()
+                .and_then(|password| password.nt_hash()),
+            aes128_key: aes_key(16),
+            aes256_key: aes_key(32),
+            previous_nt_hash: info
+                .previous_password()
+                .and_then(|password| password.nt_hash()),
+        })
+    }
+}
+
+impl TrustedDomain {
+    /// reads a `trustedDomain` object. The trust keys are decrypted only if
+    /// `pek_list` is available; `realm` is the DNS name of this domain, which
+    /// is needed to calculate the AES keys.
+    pub fn from_record(
+        record: &DataTableRecord,
+        pek_list: Option<&PekList>,
+        realm: &str,
+    ) -> anyhow::Result<Self> {
+        let trust_partner = record.att_trust_partner_opt()?;
+        let trust_attributes = record.att_trust_attributes_opt()?;
+
+        let (incoming, outgoing) = match (pek_list, &trust_partner) {
+            (Some(pek_list), Some(partner)) => (
+                TrustKeys::decrypt(
+                    record.att_trust_auth_incoming_opt(),
+                    pek_list,
+                    &inter_realm_salt(partner, realm),
+                    &format!(
+                        "incoming keys of the trust with {}",
+                        pseudonymize_dns_name(partner)
+                    ),
+                ),
+                TrustKeys::decrypt(
+                    record.att_trust_auth_outgoing_opt(),
+                    pek_list,
+                    &inter_realm_salt(realm, partner),
+                    &format!(
+                        "outgoing keys of the trust with {}",
+                        pseudonymize_dns_name(partner)
+                    ),
+                ),
+            ),
+            _ => (TrustKeys::default(), TrustKeys::default()),
+        };
+
+        Ok(Self {
+            trust_partner: trust_partner.map(|partner| pseudonymize_dns_name(&partner).to_string()),
+            flat_name: record
+                .att_flat_name_opt()?
+                .map(|name| pseudonymize_name(&name).to_string()),
+            security_identifier: record.att_security_identifier_opt()?,
+            trust_direction: record.att_trust_direction_opt()?,
+            trust_type: record.att_trust_type_opt()?,
+            sid_filtering: trust_attributes.map(|attributes| attributes.sid_filtering()),
+            trust_attributes,
+            when_created: record.att_when_created_opt()?,
+            when_changed: record.att_when_changed_opt()?,
+            incoming_last_set: incoming.last_set,
+            incoming_nt_hash: incoming.nt_hash,
+            incoming_aes128_key: incoming.aes128_key,
+            incoming_aes256_key: incoming.aes256_key,
+            incoming_previous_nt_hash: incoming.previous_nt_hash,
+            outgoing_last_set: outgoing.last_set,
+            outgoing_nt_hash: outgoing.nt_hash,
+            outgoing_aes128_key: outgoing.aes128_key,
+            outgoing_aes256_key: outgoing.aes256_key,
+            outgoing_previous_nt_hash: outgoing.previous_nt_hash,
+        })
+    }
+}
@@ -46,7 +46,11 @@ impl ObjectTree {
             .find_child_by_name("Deleted Objects")
             .ok_or(anyhow!("db has no `Deleted Objects` entry"))?;
 
-        Ok(SpecialRecords::new(schema_subpath, deleted_objects))
+        Ok(SpecialRecords::new(
+            Rc::clone(&domain_root[0]),
+            schema_subpath,
+            deleted_objects,
+        ))
     }
 
     pub(crate) fn to_termtree(&self, max_depth: u8) -> Tree<Rc<ObjectTreeEntry>> {
@@ -68,6 +72,14 @@ impl ObjectTree {
         }
     }
 
+    /// returns the entry which contains the object `ptr`
+    pub fn parent_of(&self, ptr: &RecordPointer) -> Option<Rc<ObjectTreeEntry>> {
+        self.record_index
+            .get(ptr)
+            .and_then(Weak::upgrade)
+            .and_then(|entry| entry.parent().as_ref().and_then(Weak::upgrade))
+    }
+
     pub fn dn_of(&self, ptr: &RecordPointer) -> Option<String> {
         match self.record_index.get(ptr) {
             Some(record) => Some(
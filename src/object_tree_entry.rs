@@ -11,7 +11,10 @@ use getset::Getters;
 use lazy_static::lazy_static;
 
 use crate::{
-    cache::{MetaDataCache, RecordPointer, SpecialRecords}, ntds::SdTable, win32_types::{Rdn, SecurityDescriptor}
+    cache::{MetaDataCache, RecordPointer, SpecialRecords},
+    ntds::SdTable,
+    pseudonymize_dn,
+    win32_types::{Rdn, SecurityDescriptor},
 };
 lazy_static! {
     static ref DOMAINROOT_CHILDREN: HashSet<String> = HashSet::from_iter(vec![
@@ -55,7 +58,7 @@ impl Hash for ObjectTreeEntry {
 impl Display for ObjectTreeEntry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let is_deleted = self.name().deleted_from_container().is_some();
-        let display_name = self.relative_distinguished_name();
+        let display_name = pseudonymize_dn(self.relative_distinguished_name());
         /*
         let sddl = self
             .sddl()
@@ -188,7 +191,11 @@ impl ObjectTreeEntry {
             .find_child_by_name("Deleted Objects")
             .ok_or(anyhow!("db has no `Deleted Objects` entry"))?;
 
-        Ok(SpecialRecords::new(schema_subpath, deleted_objects))
+        Ok(SpecialRecords::new(
+            Rc::clone(&domain_root[0]),
+            schema_subpath,
+            deleted_objects,
+        ))
     }
 
     /// returns the path to the domain root object, where the first entry in the list is the domain root object,
@@ -0,0 +1,337 @@
+//! redaction of secrets and pseudonymization of identifying values, so that
+//! the output can be shared with third parties
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use lazy_regex::regex_replace_all;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+/// replacement for all secret values
+pub const REDACTED: &str = "REDACTED";
+
+static REDACTION: Mutex<Option<Redaction>> = Mutex::new(None);
+
+// the flags are checked for every value which is written, so they are
+// stored separately to avoid locking [`REDACTION`]
+static REDACTION_ENABLED: AtomicBool = AtomicBool::new(false);
+static PSEUDONYMIZATION_ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Display, Serialize, Deserialize, Clone, Copy, Hash, Eq, PartialEq)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PseudonymKind {
+    /// the domain part of a SID (`S-1-5-21-x-y-z`)
+    DomainSid,
+
+    /// names of objects, such as RDNs or `sAMAccountName`s
+    Name,
+
+    /// a single label of a DNS name (and of `DC=` components)
+    DomainLabel,
+}
+
+#[derive(Serialize, Deserialize)]
+struct MappingEntry {
+    kind: PseudonymKind,
+    original: String,
+    pseudonym: String,
+}
+
+#[derive(Default)]
+struct Pseudonyms {
+    mapping: HashMap<(PseudonymKind, String), String>,
+    counters: HashMap<PseudonymKind, usize>,
+}
+
+impl Pseudonyms {
+    fn get(&mut self, kind: PseudonymKind, original: &str) -> String {
+        // names in the active directory are case-insensitive
+        let key = (kind, original.to_lowercase());
+        if let Some(pseudonym) = self.mapping.get(&key) {
+            return pseudonym.clone();
+        }
+
+        let counter = self.counters.entry(kind).or_default();
+        *counter += 1;
+        let pseudonym = match kind {
+            PseudonymKind::DomainSid => format!("S-1-5-21-0-0-{counter}"),
+            PseudonymKind::Name => format!("name{counter}"),
+            PseudonymKind::DomainLabel => format!("domain{counter}"),
+        };
+        self.mapping.insert(key, pseudonym.clone());
+        pseudonym
+    }
+
+    fn insert(&mut self, entry: MappingEntry) {
+        *self.counters.entry(entry.kind).or_default() += 1;
+        self.mapping
+            .insert((entry.kind, entry.original.to_lowercase()), entry.pseudonym);
+    }
+}
+
+struct Redaction {
+    pseudonyms: Option<Pseudonyms>,
+}
+
+/// replaces all secrets by [`REDACTED`]. If `pseudonymize` is `true`,
+/// identifying values are replaced by pseudonyms as well.
+pub fn enable_redaction(pseudonymize: bool) {
+    *REDACTION.lock().unwrap() = Some(Redaction {
+        pseudonyms: pseudonymize.then(Pseudonyms::default),
+    });
+    REDACTION_ENABLED.store(true, Ordering::Relaxed);
+    PSEUDONYMIZATION_ENABLED.store(pseudonymize, Ordering::Relaxed);
+}
+
+pub fn is_redaction_enabled() -> bool {
+    REDACTION_ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn is_pseudonymization_enabled() -> bool {
+    PSEUDONYMIZATION_ENABLED.load(Ordering::Relaxed)
+}
+
+/// reads previously generated pseudonyms, so that the same pseudonyms are
+/// used across multiple runs
+pub fn load_pseudonyms(path: &Path) -> anyhow::Result<()> {
+    let mut redaction = REDACTION.lock().unwrap();
+    if let Some(pseudonyms) = redaction.as_mut().and_then(|r| r.pseudonyms.as_mut()) {
+        let mut reader = csv::Reader::from_path(path)?;
+        for entry in reader.deserialize() {
+            pseudonyms.insert(entry?);
+        }
+        log::info!(
+            "loaded {} pseudonyms from {}",
+            pseudonyms.mapping.len(),
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// writes the mapping between original values and pseudonyms
+pub fn save_pseudonyms(path: &Path) -> anyhow::Result<()> {
+    let redaction = REDACTION.lock().unwrap();
+    if let Some(pseudonyms) = redaction.as_ref().and_then(|r| r.pseudonyms.as_ref()) {
+        let mut entries: Vec<_> = pseudonyms.mapping.iter().collect();
+        entries
+            .sort_by(|lhs, rhs| (lhs.0 .0.to_string(), lhs.1).cmp(&(rhs.0 .0.to_string(), rhs.1)));
+
+        let mut writer = csv::Writer::from_path(path)?;
+        for ((kind, original), pseudonym) in entries {
+            writer.serialize(MappingEntry {
+                kind: *kind,
+                original: original.clone(),
+                pseudonym: pseudonym.clone(),
+            })?;
+        }
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+/// replaces `value` by [`REDACTED`], if redaction is enabled
+pub fn redact_secret(value: &str) -> Cow<'_, str> {
+    if is_redaction_enabled() {
+        Cow::Borrowed(REDACTED)
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// replaces free text (which might contain anything) by [`REDACTED`], if
+/// pseudonymization is enabled
+pub fn redact_free_text(value: &str) -> Cow<'_, str> {
+    if is_pseudonymization_enabled() {
+        Cow::Borrowed(REDACTED)
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+fn pseudonym(kind: PseudonymKind, value: &str) -> Option<String> {
+    REDACTION
+        .lock()
+        .unwrap()
+        .as_mut()
+        .and_then(|r| r.pseudonyms.as_mut())
+        .map(|pseudonyms| pseudonyms.get(kind, value))
+}
+
+/// pseudonymizes the name of an object. The trailing `$` of computer
+/// and trust accounts is kept.
+pub fn pseudonymize_name(name: &str) -> Cow<'_, str> {
+    if name.is_empty() || !is_pseudonymization_enabled() {
+        return Cow::Borrowed(name);
+    }
+    let (name, suffix) = match name.strip_suffix('$') {
+        Some(name) => (name, "$"),
+        None => (name, ""),
+    };
+    let pseudonym = pseudonym(PseudonymKind::Name, name).unwrap();
+    Cow::Owned(format!("{pseudonym}{suffix}"))
+}
+
+/// pseudonymizes every label of a DNS name
+pub fn pseudonymize_dns_name(name: &str) -> Cow<'_, str> {
+    if !is_pseudonymization_enabled() {
+        return Cow::Borrowed(name);
+    }
+    Cow::Owned(
+        name.split('.')
+            .map(|label| pseudonym(PseudonymKind::DomainLabel, label).unwrap())
+            .collect::<Vec<_>>()
+            .join("."),
+    )
+}
+
+/// pseudonymizes a user principal name (`name@dns.name`)
+pub fn pseudonymize_upn(upn: &str) -> Cow<'_, str> {
+    if !is_pseudonymization_enabled() {
+        return Cow::Borrowed(upn);
+    }
+    match upn.rsplit_once('@') {
+        Some((name, domain)) => Cow::Owned(format!(
+            "{}@{}",
+            pseudonymize_name(name),
+            pseudonymize_dns_name(domain)
+        )),
+        None => pseudonymize_name(upn),
+    }
+}
+
+/// pseudonymizes a service principal name (`class/host:port/service`),
+/// keeping the service class and the port
+pub fn pseudonymize_spn(spn: &str) -> Cow<'_, str> {
+    if !is_pseudonymization_enabled() {
+        return Cow::Borrowed(spn);
+    }
+    let mut parts = spn.split('/');
+    let mut result = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let (host, port) = match part.split_once(':') {
+            Some((host, port)) => (host, format!(":{port}")),
+            None => (part, String::new()),
+        };
+        result.push('/');
+        result.push_str(&pseudonymize_dns_name(host));
+        result.push_str(&port);
+    }
+    Cow::Owned(result)
+}
+
+/// splits a distinguished name into its components, without splitting at
+/// escaped commas (e.g. `CN=Doe\, John`)
+fn split_dn(dn: &str) -> Vec<&str> {
+    let mut components = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (index, c) in dn.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            ',' => {
+                components.push(&dn[start..index]);
+                start = index + 1;
+            }
+            _ => (),
+        }
+    }
+    components.push(&dn[start..]);
+    components
+}
+
+/// removes the escape characters of an attribute value of a distinguished
+/// name
+fn unescape_dn_value(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.extend(chars.next()),
+            c => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+/// pseudonymizes every component of a distinguished name, but keeps the
+/// attribute types (e.g. `CN=`)
+pub fn pseudonymize_dn(dn: &str) -> Cow<'_, str> {
+    if !is_pseudonymization_enabled() {
+        return Cow::Borrowed(dn);
+    }
+
+    // pseudonyms never contain characters which must be escaped
+    Cow::Owned(
+        split_dn(dn)
+            .into_iter()
+            .map(|component| match component.split_once('=') {
+                Some((att, value)) if att.eq_ignore_ascii_case("dc") => {
+                    format!("{att}={}", pseudonymize_dns_name(&unescape_dn_value(value)))
+                }
+                Some((att, value)) => {
+                    format!("{att}={}", pseudonymize_name(&unescape_dn_value(value)))
+                }
+                None => pseudonymize_name(&unescape_dn_value(component)).to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(","),
+    )
+}
+
+/// pseudonymizes the domain part of a SID (`S-1-5-21-x-y-z`), but keeps the RID
+pub fn pseudonymize_domain_sid(domain_sid: &str) -> Cow<'_, str> {
+    match pseudonym(PseudonymKind::DomainSid, domain_sid) {
+        Some(pseudonym) => Cow::Owned(pseudonym),
+        None => Cow::Borrowed(domain_sid),
+    }
+}
+
+/// pseudonymizes all domain SIDs in a text, such as in an SDDL string
+pub fn pseudonymize_text(text: &str) -> Cow<'_, str> {
+    if !is_pseudonymization_enabled() {
+        return Cow::Borrowed(text);
+    }
+    regex_replace_all!(r"S-1-5-21-\d+-\d+-\d+", text, |domain_sid: &str| {
+        pseudonymize_domain_sid(domain_sid).to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_dn, unescape_dn_value, MappingEntry, PseudonymKind, Pseudonyms};
+
+    #[test]
+    fn test_pseudonyms() {
+        let mut pseudonyms = Pseudonyms::default();
+        pseudonyms.insert(MappingEntry {
+            kind: PseudonymKind::Name,
+            original: "Administrator".to_string(),
+            pseudonym: "name1".to_string(),
+        });
+        assert_eq!(pseudonyms.get(PseudonymKind::Name, "administrator"), "name1");
+        assert_eq!(pseudonyms.get(PseudonymKind::Name, "krbtgt"), "name2");
+        assert_eq!(pseudonyms.get(PseudonymKind::DomainLabel, "krbtgt"), "domain1");
+        assert_eq!(
+            pseudonyms.get(PseudonymKind::DomainSid, "S-1-5-21-1-2-3"),
+            "S-1-5-21-0-0-1"
+        );
+    }
+
+    #[test]
+    fn test_split_dn() {
+        let components = split_dn(r"CN=Doe\, John,OU=Sales\\,DC=contoso,DC=local");
+        assert_eq!(
+            components,
+            vec![r"CN=Doe\, John", r"OU=Sales\\", "DC=contoso", "DC=local"]
+        );
+        assert_eq!(unescape_dn_value(r"Doe\, John"), "Doe, John");
+        assert_eq!(unescape_dn_value(r"Sales\\"), r"Sales\");
+    }
+}
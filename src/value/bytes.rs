@@ -0,0 +1,24 @@
+use crate::cache::Value;
+
+use crate::ntds::Error;
+
+use super::FromValue;
+
+impl FromValue for Vec<u8> {
+    fn from_value_opt(value: &Value) -> Result<Option<Self>, Error>
+    where
+        Self: Sized,
+    {
+        match value {
+            Value::Binary(val)
+            | Value::LargeBinary(val)
+            | Value::SuperLarge(val)
+            | Value::Long(val) => Ok(Some(val.as_ref().clone())),
+            Value::Null(()) => Ok(None),
+            _ => Err(Error::InvalidValueDetected(
+                value.to_string(),
+                "Vec<u8> (binary)",
+            )),
+        }
+    }
+}
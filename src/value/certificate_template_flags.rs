@@ -0,0 +1,42 @@
+use crate::cache::Value;
+
+use crate::ntds::Error;
+use crate::win32_types::{CertificateNameFlags, EnrollmentFlags};
+
+use super::FromValue;
+
+impl FromValue for EnrollmentFlags {
+    fn from_value_opt(value: &Value) -> Result<Option<Self>, Error>
+    where
+        Self: Sized,
+    {
+        match value {
+            Value::I32(val) => Ok(Some(<EnrollmentFlags>::from_bits_truncate(
+                u32::from_ne_bytes(val.to_ne_bytes()),
+            ))),
+            Value::Null(()) => Ok(None),
+            _ => Err(Error::InvalidValueDetected(
+                value.to_string(),
+                "EnrollmentFlags (i32)",
+            )),
+        }
+    }
+}
+
+impl FromValue for CertificateNameFlags {
+    fn from_value_opt(value: &Value) -> Result<Option<Self>, Error>
+    where
+        Self: Sized,
+    {
+        match value {
+            Value::I32(val) => Ok(Some(<CertificateNameFlags>::from_bits_truncate(
+                u32::from_ne_bytes(val.to_ne_bytes()),
+            ))),
+            Value::Null(()) => Ok(None),
+            _ => Err(Error::InvalidValueDetected(
+                value.to_string(),
+                "CertificateNameFlags (i32)",
+            )),
+        }
+    }
+}
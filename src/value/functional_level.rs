@@ -0,0 +1,19 @@
+use crate::cache::Value;
+use num_traits::FromPrimitive;
+
+use crate::{ntds::Error, win32_types::FunctionalLevel};
+
+use super::FromValue;
+
+impl FromValue for FunctionalLevel {
+    fn from_value_opt(value: &Value) -> Result<Option<Self>, Error> {
+        match value {
+            Value::I32(val) => Ok(FromPrimitive::from_i32(*val)),
+            Value::Null(()) => Ok(None),
+            _ => Err(Error::InvalidValueDetected(
+                value.to_string(),
+                "FunctionalLevel (i32)",
+            )),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use crate::cache::Value;
+
+use crate::{ntds::Error, win32_types::GpLink};
+
+use super::FromValue;
+
+impl FromValue for GpLink {
+    fn from_value_opt(value: &Value) -> Result<Option<Self>, Error> {
+        match value {
+            Value::Text(val) | Value::LargeText(val) => Ok(Some(val.parse()?)),
+            Value::Null(()) => Ok(None),
+            _ => Err(Error::InvalidValueDetected(
+                value.to_string(),
+                "GpLink (text)",
+            )),
+        }
+    }
+}
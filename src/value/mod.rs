@@ -1,4 +1,5 @@
 mod bool;
+mod bytes;
 mod from_value;
 mod i32;
 mod i64;
@@ -7,6 +8,18 @@ mod sid;
 mod string;
 mod u32;
 mod user_acount_control;
+mod trust_attributes;
+mod trust_direction;
+mod trust_type;
+mod replication_metadata;
+mod link_value_metadata;
+mod retired_dsa_signatures;
+mod gp_link;
+mod functional_level;
+mod password_properties;
+mod time_interval;
+mod certificate_template_flags;
+mod group_type;
 mod to_string;
 
 pub use from_value::*;
@@ -0,0 +1,20 @@
+use crate::cache::Value;
+
+use crate::{ntds::Error, win32_types::ReplicationMetadata};
+
+use super::FromValue;
+
+impl FromValue for ReplicationMetadata {
+    fn from_value_opt(value: &Value) -> Result<Option<Self>, Error> {
+        match value {
+            Value::Binary(val) | Value::LargeBinary(val) | Value::Long(val) => {
+                Ok(Some(Self::try_from(&val[..])?))
+            }
+            Value::Null(()) => Ok(None),
+            _ => Err(Error::InvalidValueDetected(
+                value.to_string(),
+                "ReplicationMetadata (binary)",
+            )),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use crate::cache::Value;
+
+use crate::{ntds::Error, win32_types::TimeInterval};
+
+use super::FromValue;
+
+impl FromValue for TimeInterval {
+    fn from_value_opt(value: &Value) -> Result<Option<Self>, Error> {
+        Ok(i64::from_value_opt(value)?.map(TimeInterval::from))
+    }
+}
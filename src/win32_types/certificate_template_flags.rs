@@ -0,0 +1,63 @@
+use bitflags::bitflags;
+use serde::{Deserialize, Serialize};
+
+bitflags! {
+
+    /// the enrollment flags of a certificate template (`msPKI-Enrollment-Flag`)
+    ///
+    /// Source: https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-crtd/ec71fd43-61c2-407b-83c9-b52272dec8a1
+    #[derive(PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+    pub struct EnrollmentFlags : u32 {
+        const CT_FLAG_INCLUDE_SYMMETRIC_ALGORITHMS = 0x0000_0001;
+
+        /// Certificate requests must be approved by a certificate manager.
+        const CT_FLAG_PEND_ALL_REQUESTS = 0x0000_0002;
+        const CT_FLAG_PUBLISH_TO_KRA_CONTAINER = 0x0000_0004;
+        const CT_FLAG_PUBLISH_TO_DS = 0x0000_0008;
+        const CT_FLAG_AUTO_ENROLLMENT_CHECK_USER_DS_CERTIFICATE = 0x0000_0010;
+        const CT_FLAG_AUTO_ENROLLMENT = 0x0000_0020;
+        const CT_FLAG_PREVIOUS_APPROVAL_VALIDATE_REENROLLMENT = 0x0000_0040;
+        const CT_FLAG_USER_INTERACTION_REQUIRED = 0x0000_0100;
+        const CT_FLAG_REMOVE_INVALID_CERTIFICATE_FROM_PERSONAL_STORE = 0x0000_0400;
+        const CT_FLAG_ALLOW_ENROLL_ON_BEHALF_OF = 0x0000_0800;
+        const CT_FLAG_ADD_OCSP_NOCHECK = 0x0000_1000;
+        const CT_FLAG_ENABLE_KEY_REUSE_ON_NT_TOKEN_KEYSET_STORAGE_FULL = 0x0000_2000;
+        const CT_FLAG_NOREVOCATIONINFOINISSUEDCERTS = 0x0000_4000;
+        const CT_FLAG_INCLUDE_BASIC_CONSTRAINTS_FOR_EE_CERTS = 0x0000_8000;
+        const CT_FLAG_ALLOW_PREVIOUS_APPROVAL_KEYBASEDRENEWAL_VALIDATE_REENROLLMENT = 0x0001_0000;
+        const CT_FLAG_ISSUANCE_POLICIES_FROM_REQUEST = 0x0002_0000;
+        const CT_FLAG_SKIP_AUTO_RENEWAL = 0x0004_0000;
+
+        /// Issued certificates do not contain the SID of the requester.
+        const CT_FLAG_NO_SECURITY_EXTENSION = 0x0008_0000;
+    }
+}
+
+bitflags! {
+
+    /// the flags which specify the subject of the certificates which are
+    /// issued using a certificate template (`msPKI-Certificate-Name-Flag`)
+    ///
+    /// Source: https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-crtd/1192823c-d839-4bc3-9b6b-fa8c53507ae1
+    #[derive(PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+    pub struct CertificateNameFlags : u32 {
+
+        /// The requester specifies the subject of the certificate.
+        const CT_FLAG_ENROLLEE_SUPPLIES_SUBJECT = 0x0000_0001;
+        const CT_FLAG_OLD_CERT_SUPPLIES_SUBJECT_AND_ALT_NAME = 0x0000_0008;
+
+        /// The requester specifies the subject alternative name of the
+        /// certificate.
+        const CT_FLAG_ENROLLEE_SUPPLIES_SUBJECT_ALT_NAME = 0x0001_0000;
+        const CT_FLAG_SUBJECT_ALT_REQUIRE_DOMAIN_DNS = 0x0040_0000;
+        const CT_FLAG_SUBJECT_ALT_REQUIRE_SPN = 0x0080_0000;
+        const CT_FLAG_SUBJECT_ALT_REQUIRE_DIRECTORY_GUID = 0x0100_0000;
+        const CT_FLAG_SUBJECT_ALT_REQUIRE_UPN = 0x0200_0000;
+        const CT_FLAG_SUBJECT_ALT_REQUIRE_EMAIL = 0x0400_0000;
+        const CT_FLAG_SUBJECT_ALT_REQUIRE_DNS = 0x0800_0000;
+        const CT_FLAG_SUBJECT_REQUIRE_DNS_AS_CN = 0x1000_0000;
+        const CT_FLAG_SUBJECT_REQUIRE_EMAIL = 0x2000_0000;
+        const CT_FLAG_SUBJECT_REQUIRE_COMMON_NAME = 0x4000_0000;
+        const CT_FLAG_SUBJECT_REQUIRE_DIRECTORY_PATH = 0x8000_0000;
+    }
+}
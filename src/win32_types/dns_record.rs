@@ -0,0 +1,362 @@
+use std::fmt::Display;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use getset::Getters;
+
+use crate::ntds::Error;
+
+use super::WindowsFileTime;
+
+/// `DataLength` (2 bytes), `Type` (2 bytes), `Version` (1 byte), `Rank` (1
+/// byte), `Flags` (2 bytes), `Serial` (4 bytes), `TtlSeconds` (4 bytes),
+/// `Reserved` (4 bytes) and `TimeStamp` (4 bytes)
+const DNS_RECORD_HEADER_SIZE: usize = 24;
+
+/// number of 100ns intervals per hour
+const FILETIME_TICKS_PER_HOUR: u64 = 36_000_000_000;
+
+/// a single value of `dnsRecord`
+///
+/// Source: https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-dnsp/6912b338-5472-4f59-b912-0edb536b6ed8
+#[derive(Getters, PartialEq, Eq, Clone)]
+#[getset(get = "pub")]
+pub struct DnsRecord {
+    record_type: u16,
+    serial: u32,
+    ttl: u32,
+
+    /// the time when a dynamic record has been registered or refreshed the
+    /// last time; static records have no timestamp
+    timestamp: Option<WindowsFileTime>,
+    data: DnsRecordData,
+}
+
+/// the data of a DNS record. Names are stored as fully qualified domain
+/// names, including the trailing dot.
+#[derive(PartialEq, Eq, Clone)]
+pub enum DnsRecordData {
+    /// the node has been deleted (`DNS_TYPE_ZERO`) at the given time
+    Tombstone(WindowsFileTime),
+    A(Ipv4Addr),
+    Aaaa(Ipv6Addr),
+    Ns(String),
+    Cname(String),
+    Ptr(String),
+    Mx {
+        preference: u16,
+        exchange: String,
+    },
+    Srv {
+        priority: u16,
+        weight: u16,
+        port: u16,
+        target: String,
+    },
+    Txt(Vec<String>),
+    Soa {
+        serial: u32,
+        refresh: u32,
+        retry: u32,
+        expire: u32,
+        minimum_ttl: u32,
+        primary_server: String,
+        zone_administrator: String,
+    },
+
+    /// a record type which is not decoded
+    Other(Vec<u8>),
+}
+
+impl TryFrom<&[u8]> for DnsRecord {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        if value.len() < DNS_RECORD_HEADER_SIZE {
+            return Err(Error::InvalidDnsRecord("value is too short"));
+        }
+        let data_length = LittleEndian::read_u16(&value[0..2]) as usize;
+        let record_type = LittleEndian::read_u16(&value[2..4]);
+        let data = value
+            .get(DNS_RECORD_HEADER_SIZE..DNS_RECORD_HEADER_SIZE + data_length)
+            .ok_or(Error::InvalidDnsRecord("record data is too short"))?;
+        let timestamp = match LittleEndian::read_u32(&value[20..24]) {
+            0 => None,
+            hours => Some(WindowsFileTime::from(
+                u64::from(hours) * FILETIME_TICKS_PER_HOUR,
+            )),
+        };
+
+        Ok(Self {
+            record_type,
+            serial: LittleEndian::read_u32(&value[8..12]),
+            ttl: BigEndian::read_u32(&value[12..16]),
+            timestamp,
+            data: DnsRecordData::parse(record_type, data)?,
+        })
+    }
+}
+
+impl DnsRecord {
+    pub fn is_tombstone(&self) -> bool {
+        matches!(self.data, DnsRecordData::Tombstone(_))
+    }
+
+    /// returns the name of the record type, such as `A` or `SRV`
+    pub fn type_name(&self) -> String {
+        match self.record_type {
+            0 => "ZERO".to_string(),
+            1 => "A".to_string(),
+            2 => "NS".to_string(),
+            5 => "CNAME".to_string(),
+            6 => "SOA".to_string(),
+            12 => "PTR".to_string(),
+            15 => "MX".to_string(),
+            16 => "TXT".to_string(),
+            28 => "AAAA".to_string(),
+            33 => "SRV".to_string(),
+            record_type => format!("TYPE{record_type}"),
+        }
+    }
+
+    /// replaces all names in this record using `name`, and all texts using `text`
+    pub fn map_names(
+        mut self,
+        name: impl Fn(&str) -> String,
+        text: impl Fn(&str) -> String,
+    ) -> Self {
+        self.data = match self.data {
+            DnsRecordData::Ns(target) => DnsRecordData::Ns(name(&target)),
+            DnsRecordData::Cname(target) => DnsRecordData::Cname(name(&target)),
+            DnsRecordData::Ptr(target) => DnsRecordData::Ptr(name(&target)),
+            DnsRecordData::Mx {
+                preference,
+                exchange,
+            } => DnsRecordData::Mx {
+                preference,
+                exchange: name(&exchange),
+            },
+            DnsRecordData::Srv {
+                priority,
+                weight,
+                port,
+                target,
+            } => DnsRecordData::Srv {
+                priority,
+                weight,
+                port,
+                target: name(&target),
+            },
+            DnsRecordData::Txt(texts) => {
+                DnsRecordData::Txt(texts.iter().map(|t| text(t)).collect())
+            }
+            DnsRecordData::Soa {
+                serial,
+                refresh,
+                retry,
+                expire,
+                minimum_ttl,
+                primary_server,
+                zone_administrator,
+            } => DnsRecordData::Soa {
+                serial,
+                refresh,
+                retry,
+                expire,
+                minimum_ttl,
+                primary_server: name(&primary_server),
+                zone_administrator: name(&zone_administrator),
+            },
+            data => data,
+        };
+        self
+    }
+}
+
+impl DnsRecordData {
+    fn parse(record_type: u16, data: &[u8]) -> Result<Self, Error> {
+        let too_short = || Error::InvalidDnsRecord("record data is too short");
+        let u16_at = |offset: usize| {
+            data.get(offset..offset + 2)
+                .map(BigEndian::read_u16)
+                .ok_or_else(too_short)
+        };
+        let u32_at = |offset: usize| {
+            data.get(offset..offset + 4)
+                .map(BigEndian::read_u32)
+                .ok_or_else(too_short)
+        };
+
+        Ok(match record_type {
+            0 => Self::Tombstone(WindowsFileTime::from(
+                data.get(0..8).map(LittleEndian::read_u64).ok_or_else(too_short)?,
+            )),
+            1 => Self::A(Ipv4Addr::from(
+                <[u8; 4]>::try_from(data.get(0..4).ok_or_else(too_short)?).unwrap(),
+            )),
+            28 => Self::Aaaa(Ipv6Addr::from(
+                <[u8; 16]>::try_from(data.get(0..16).ok_or_else(too_short)?).unwrap(),
+            )),
+            2 => Self::Ns(read_count_name(data)?.0),
+            5 => Self::Cname(read_count_name(data)?.0),
+            12 => Self::Ptr(read_count_name(data)?.0),
+            15 => Self::Mx {
+                preference: u16_at(0)?,
+                exchange: read_count_name(data.get(2..).ok_or_else(too_short)?)?.0,
+            },
+            33 => Self::Srv {
+                priority: u16_at(0)?,
+                weight: u16_at(2)?,
+                port: u16_at(4)?,
+                target: read_count_name(data.get(6..).ok_or_else(too_short)?)?.0,
+            },
+            16 => {
+                let mut texts = Vec::new();
+                let mut rest = data;
+                while let Some((length, tail)) = rest.split_first() {
+                    let text = tail.get(..*length as usize).ok_or_else(too_short)?;
+                    texts.push(String::from_utf8_lossy(text).to_string());
+                    rest = &tail[*length as usize..];
+                }
+                Self::Txt(texts)
+            }
+            6 => {
+                let (primary_server, length) =
+                    read_count_name(data.get(20..).ok_or_else(too_short)?)?;
+                let (zone_administrator, _) =
+                    read_count_name(data.get(20 + length..).ok_or_else(too_short)?)?;
+                Self::Soa {
+                    serial: u32_at(0)?,
+                    refresh: u32_at(4)?,
+                    retry: u32_at(8)?,
+                    expire: u32_at(12)?,
+                    minimum_ttl: u32_at(16)?,
+                    primary_server,
+                    zone_administrator,
+                }
+            }
+            _ => Self::Other(data.to_vec()),
+        })
+    }
+}
+
+/// reads a `DNS_COUNT_NAME` and returns the fully qualified name, together
+/// with the number of bytes which have been read
+fn read_count_name(data: &[u8]) -> Result<(String, usize), Error> {
+    let too_short = || Error::InvalidDnsRecord("name is too short");
+    let length = *data.first().ok_or_else(too_short)? as usize;
+    let label_count = *data.get(1).ok_or_else(too_short)? as usize;
+    let mut labels = Vec::with_capacity(label_count);
+    let mut offset = 2;
+    for _ in 0..label_count {
+        let label_length = *data.get(offset).ok_or_else(too_short)? as usize;
+        let label = data
+            .get(offset + 1..offset + 1 + label_length)
+            .ok_or_else(too_short)?;
+        labels.push(String::from_utf8_lossy(label).to_string());
+        offset += 1 + label_length;
+    }
+    Ok((format!("{}.", labels.join(".")), length + 2))
+}
+
+impl Display for DnsRecordData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tombstone(timestamp) => {
+                write!(f, "tombstoned at {}", chrono::DateTime::<chrono::Utc>::from(timestamp))
+            }
+            Self::A(address) => write!(f, "{address}"),
+            Self::Aaaa(address) => write!(f, "{address}"),
+            Self::Ns(name) | Self::Cname(name) | Self::Ptr(name) => write!(f, "{name}"),
+            Self::Mx {
+                preference,
+                exchange,
+            } => write!(f, "{preference} {exchange}"),
+            Self::Srv {
+                priority,
+                weight,
+                port,
+                target,
+            } => write!(f, "{priority} {weight} {port} {target}"),
+            Self::Txt(texts) => write!(
+                f,
+                "{}",
+                texts
+                    .iter()
+                    .map(|text| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\"")))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+            Self::Soa {
+                serial,
+                refresh,
+                retry,
+                expire,
+                minimum_ttl,
+                primary_server,
+                zone_administrator,
+            } => write!(
+                f,
+                "{primary_server} {zone_administrator} \
+                {serial} {refresh} {retry} {expire} {minimum_ttl}"
+            ),
+            Self::Other(data) => write!(f, "\\# {} {}", data.len(), hex::encode(data)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DnsRecord, DnsRecordData};
+
+    fn raw_record(record_type: u16, timestamp: u32, data: &[u8]) -> Vec<u8> {
+        let mut value = Vec::new();
+        value.extend((data.len() as u16).to_le_bytes());
+        value.extend(record_type.to_le_bytes());
+        value.extend([5, 240, 0, 0]);
+        value.extend(42u32.to_le_bytes());
+        value.extend(3600u32.to_be_bytes());
+        value.extend(0u32.to_le_bytes());
+        value.extend(timestamp.to_le_bytes());
+        value.extend(data);
+        value
+    }
+
+    #[test]
+    fn test_a_record() {
+        let record = DnsRecord::try_from(&raw_record(1, 0, &[192, 168, 0, 10])[..]).unwrap();
+        assert_eq!(record.type_name(), "A");
+        assert_eq!(*record.ttl(), 3600);
+        assert_eq!(*record.serial(), 42);
+        assert!(record.timestamp().is_none());
+        assert_eq!(record.data().to_string(), "192.168.0.10");
+    }
+
+    #[test]
+    fn test_srv_record() {
+        let mut data = vec![0, 0, 0, 100, 0x01, 0x85];
+        data.extend([14, 2, 4, b'd', b'c', b'0', b'1', 7]);
+        data.extend(b"contoso\0");
+        let record = DnsRecord::try_from(&raw_record(33, 3_500_000, &data)[..]).unwrap();
+        assert_eq!(record.type_name(), "SRV");
+        assert!(record.timestamp().is_some());
+        assert_eq!(record.data().to_string(), "0 100 389 dc01.contoso.");
+    }
+
+    #[test]
+    fn test_tombstone() {
+        let entombed: u64 = 133_000_000_000_000_000;
+        let record = DnsRecord::try_from(&raw_record(0, 0, &entombed.to_le_bytes())[..]).unwrap();
+        assert!(record.is_tombstone());
+        assert!(matches!(record.data(), DnsRecordData::Tombstone(_)));
+    }
+
+    #[test]
+    fn test_txt_record() {
+        let record =
+            DnsRecord::try_from(&raw_record(16, 0, &[5, b'h', b'e', b'l', b'l', b'o', 1, b'"'])[..])
+                .unwrap();
+        assert_eq!(record.data().to_string(), "\"hello\" \"\\\"\"");
+        assert!(DnsRecord::try_from(&raw_record(16, 0, &[5, b'h'])[..]).is_err());
+    }
+}
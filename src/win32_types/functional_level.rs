@@ -0,0 +1,22 @@
+use num_derive::FromPrimitive;
+use serde::{Deserialize, Serialize};
+use strum::EnumString;
+
+/// the functional level of a domain or forest (`msDS-Behavior-Version`)
+///
+/// Source: https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-adts/d7422d35-448a-451a-8846-6a7def0044df
+#[derive(
+    EnumString, FromPrimitive, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone, Copy,
+)]
+#[allow(non_camel_case_types)]
+pub enum FunctionalLevel {
+    DS_BEHAVIOR_WIN2000 = 0,
+    DS_BEHAVIOR_WIN2003_WITH_MIXED_DOMAINS = 1,
+    DS_BEHAVIOR_WIN2003 = 2,
+    DS_BEHAVIOR_WIN2008 = 3,
+    DS_BEHAVIOR_WIN2008R2 = 4,
+    DS_BEHAVIOR_WIN2012 = 5,
+    DS_BEHAVIOR_WIN2012R2 = 6,
+    DS_BEHAVIOR_WIN2016 = 7,
+    DS_BEHAVIOR_WIN2025 = 10,
+}
@@ -0,0 +1,145 @@
+use std::{fmt::Display, str::FromStr};
+
+use bitflags::bitflags;
+use getset::Getters;
+
+use crate::ntds::Error;
+
+use super::Guid;
+
+bitflags! {
+
+    /// Source: https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-gpol/08090b22-bc16-49f4-8e10-f27a8fb16d18
+    #[derive(PartialEq, Eq, Clone, Copy, Default)]
+    pub struct GpLinkOptions : u32 {
+
+        /// The link is disabled, so the GPO is not applied.
+        const LINK_DISABLED = 0x0000_0001;
+
+        /// The link is enforced (no override), so that the GPO is applied
+        /// even if a descendant container blocks inheritance.
+        const LINK_ENFORCED = 0x0000_0002;
+    }
+}
+
+/// the decoded value of `gPLink`, which is a list of GPO links in the form
+/// `[LDAP://cn={GUID},cn=policies,cn=system,DC=...;options]`
+#[derive(PartialEq, Eq, Default)]
+pub struct GpLink(Vec<GpLinkEntry>);
+
+/// a single link to a Group Policy Object
+#[derive(Getters, PartialEq, Eq, Clone)]
+#[getset(get = "pub")]
+pub struct GpLinkEntry {
+    /// the distinguished name of the `groupPolicyContainer`
+    gpo_dn: String,
+    options: GpLinkOptions,
+}
+
+impl GpLink {
+    pub fn entries(&self) -> &[GpLinkEntry] {
+        &self.0
+    }
+}
+
+impl GpLinkEntry {
+    /// returns the GUID of the GPO, which is the common name of the
+    /// `groupPolicyContainer`
+    pub fn gpo_guid(&self) -> Option<Guid> {
+        let (att, value) = self.gpo_dn.split(',').next()?.split_once('=')?;
+        if !att.trim().eq_ignore_ascii_case("cn") {
+            return None;
+        }
+        value
+            .trim()
+            .trim_start_matches('{')
+            .trim_end_matches('}')
+            .parse()
+            .ok()
+    }
+
+    pub fn is_enforced(&self) -> bool {
+        self.options.contains(GpLinkOptions::LINK_ENFORCED)
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.options.contains(GpLinkOptions::LINK_DISABLED)
+    }
+}
+
+impl FromStr for GpLink {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut entries = Vec::new();
+        for link in s.split(']').map(str::trim).filter(|link| !link.is_empty()) {
+            let invalid = || Error::InvalidValueDetected(link.to_string(), "gPLink entry");
+            let (path, options) = link
+                .strip_prefix('[')
+                .and_then(|link| link.rsplit_once(';'))
+                .ok_or_else(invalid)?;
+            let gpo_dn = match path.get(..7) {
+                Some(prefix) if prefix.eq_ignore_ascii_case("LDAP://") => &path[7..],
+                _ => path,
+            };
+            entries.push(GpLinkEntry {
+                gpo_dn: gpo_dn.to_string(),
+                options: GpLinkOptions::from_bits_truncate(
+                    options.trim().parse().map_err(|_| invalid())?,
+                ),
+            });
+        }
+        Ok(Self(entries))
+    }
+}
+
+impl Display for GpLinkEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.gpo_guid() {
+            Some(guid) => write!(f, "{{{guid}}}")?,
+            None => write!(f, "{}", self.gpo_dn)?,
+        }
+        if self.is_enforced() {
+            write!(f, " (enforced)")?;
+        }
+        if self.is_disabled() {
+            write!(f, " (disabled)")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GpLink;
+
+    #[test]
+    fn test_gp_link() {
+        let gp_link: GpLink = concat!(
+            "[LDAP://cn={31B2F340-016D-11D2-945F-00C04FB984F9},cn=policies,cn=system,DC=corp,DC=local;0]",
+            "[LDAP://CN={6AC1786C-016F-11D2-945F-00C04FB984F9},CN=Policies,CN=System,DC=corp,DC=local;3]"
+        )
+        .parse()
+        .unwrap();
+        assert_eq!(gp_link.entries().len(), 2);
+
+        let entry = &gp_link.entries()[0];
+        assert!(!entry.is_enforced());
+        assert!(!entry.is_disabled());
+        assert_eq!(
+            entry.gpo_guid().unwrap().to_string(),
+            "31b2f340-016d-11d2-945f-00c04fb984f9"
+        );
+
+        let entry = &gp_link.entries()[1];
+        assert!(entry.is_enforced());
+        assert!(entry.is_disabled());
+        assert_eq!(
+            entry.to_string(),
+            "{6ac1786c-016f-11d2-945f-00c04fb984f9} (enforced) (disabled)"
+        );
+
+        assert!(" ".parse::<GpLink>().unwrap().entries().is_empty());
+        assert!("[LDAP://cn=x]".parse::<GpLink>().is_err());
+    }
+}
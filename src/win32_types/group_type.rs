@@ -0,0 +1,40 @@
+use bitflags::bitflags;
+use serde::{Deserialize, Serialize};
+
+bitflags! {
+
+    /// Source: [MS-ADTS] 2.2.12 Group Type Flags
+    #[derive(PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+    pub struct GroupType : u32 {
+
+        /// The group is created by the system.
+        const GROUP_TYPE_BUILTIN_LOCAL_GROUP = 0x0000_0001;
+
+        /// The group has global scope.
+        const GROUP_TYPE_ACCOUNT_GROUP = 0x0000_0002;
+
+        /// The group has domain local scope.
+        const GROUP_TYPE_RESOURCE_GROUP = 0x0000_0004;
+
+        /// The group has universal scope.
+        const GROUP_TYPE_UNIVERSAL_GROUP = 0x0000_0008;
+
+        /// The group is an application basic group (Authorization Manager).
+        const GROUP_TYPE_APP_BASIC_GROUP = 0x0000_0010;
+
+        /// The group is an application query group (Authorization Manager).
+        const GROUP_TYPE_APP_QUERY_GROUP = 0x0000_0020;
+
+        /// The group is a security group. Otherwise, it is a distribution
+        /// group.
+        const GROUP_TYPE_SECURITY_ENABLED = 0x8000_0000;
+    }
+}
+
+impl GroupType {
+    /// returns `true` for global security groups, which are the only groups
+    /// fine-grained password policies can be applied to
+    pub fn is_global_security_group(&self) -> bool {
+        self.contains(Self::GROUP_TYPE_ACCOUNT_GROUP | Self::GROUP_TYPE_SECURITY_ENABLED)
+    }
+}
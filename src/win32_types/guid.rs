@@ -8,6 +8,16 @@ use crate::{cache::Value, value::FromValue};
 #[derive(Serialize, Deserialize, Eq, PartialEq, Clone, Hash)]
 pub struct Guid(Uuid);
 
+impl Guid {
+    pub fn from_bytes_le(bytes: [u8; 16]) -> Self {
+        Self(Uuid::from_bytes_le(bytes))
+    }
+
+    pub fn to_bytes_le(&self) -> [u8; 16] {
+        self.0.to_bytes_le()
+    }
+}
+
 impl FromValue for Guid {
     fn from_value_opt(value: &crate::cache::Value) -> crate::ntds::Result<Option<Self>>
     where
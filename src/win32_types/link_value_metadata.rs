@@ -0,0 +1,86 @@
+use byteorder::{ByteOrder, LittleEndian};
+use getset::Getters;
+
+use crate::ntds::Error;
+
+use super::{Guid, WindowsFileTime};
+
+/// `timeCreated` (8 bytes), `dwVersion` (4 bytes and 4 bytes padding),
+/// `timeChanged` (8 bytes), `uuidDsaOriginating` (16 bytes) and
+/// `usnOriginating` (8 bytes)
+const LINK_VALUE_METADATA_SIZE: usize = 48;
+
+/// the replication metadata of a single linked value, which is stored in
+/// the `link_metadata` column of the link table. Its layout corresponds to
+/// `VALUE_META_DATA_EXT_V1` (see MS-DRSR 5.215).
+#[derive(Getters)]
+#[getset(get = "pub")]
+pub struct LinkValueMetadata {
+    /// the time when the value has been added (originally)
+    creation_time: WindowsFileTime,
+
+    /// the number of originating changes of the value
+    version: u32,
+
+    /// the time of the last originating change, which is the time when the
+    /// value has been removed, if it is absent
+    change_time: WindowsFileTime,
+
+    /// the invocation ID of the DC where the last originating change took place
+    originating_invocation_id: Guid,
+    originating_usn: i64,
+}
+
+impl TryFrom<&[u8]> for LinkValueMetadata {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        if value.len() < LINK_VALUE_METADATA_SIZE {
+            return Err(Error::InvalidReplicationMetadata(
+                "link value metadata is too short",
+            ));
+        }
+        let dstime = |bytes: &[u8]| WindowsFileTime::from_dstime(LittleEndian::read_u64(bytes));
+        Ok(Self {
+            creation_time: dstime(&value[0..8]),
+            version: LittleEndian::read_u32(&value[8..12]),
+            change_time: dstime(&value[16..24]),
+            originating_invocation_id: Guid::from_bytes_le(value[24..40].try_into().unwrap()),
+            originating_usn: LittleEndian::read_i64(&value[40..48]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LinkValueMetadata;
+
+    #[test]
+    fn test_link_value_metadata() {
+        let value = hex::decode(concat!(
+            "409e6b1c03000000",
+            "02000000",
+            "00000000",
+            "6fba6b1c03000000",
+            "33221100554477668899aabbccddeeff",
+            "1020000000000000",
+        ))
+        .unwrap();
+        let metadata = LinkValueMetadata::try_from(&value[..]).unwrap();
+        assert_eq!(*metadata.version(), 2);
+        assert_eq!(
+            metadata.creation_time().to_string(),
+            "2024-06-01T12:00:00+0000"
+        );
+        assert_eq!(
+            metadata.change_time().to_string(),
+            "2024-06-01T14:00:15+0000"
+        );
+        assert_eq!(
+            metadata.originating_invocation_id().to_string(),
+            "00112233-4455-6677-8899-aabbccddeeff"
+        );
+        assert_eq!(*metadata.originating_usn(), 0x2010);
+        assert!(LinkValueMetadata::try_from(&value[..40]).is_err());
+    }
+}
@@ -5,6 +5,20 @@ mod timestamp;
 mod rdn;
 mod guid;
 mod security_descriptor;
+mod replication_metadata;
+mod link_value_metadata;
+mod retired_dsa_signatures;
+mod trust_attributes;
+mod trust_direction;
+mod trust_type;
+mod gp_link;
+mod functional_level;
+mod password_properties;
+mod time_interval;
+mod dns_record;
+mod ntds_dsa_options;
+mod certificate_template_flags;
+mod group_type;
 
 pub use sam_account_type::*;
 pub use user_account_control::*;
@@ -12,4 +26,18 @@ pub use sid::*;
 pub use timestamp::*;
 pub use rdn::*;
 pub use guid::*;
-pub use security_descriptor::*;
\ No newline at end of file
+pub use security_descriptor::*;
+pub use replication_metadata::*;
+pub use link_value_metadata::*;
+pub use retired_dsa_signatures::*;
+pub use trust_attributes::*;
+pub use trust_direction::*;
+pub use trust_type::*;
+pub use gp_link::*;
+pub use functional_level::*;
+pub use password_properties::*;
+pub use time_interval::*;
+pub use dns_record::*;
+pub use ntds_dsa_options::*;
+pub use certificate_template_flags::*;
+pub use group_type::*;
\ No newline at end of file
@@ -0,0 +1,41 @@
+use bitflags::bitflags;
+use serde::{Deserialize, Serialize};
+
+bitflags! {
+
+    /// the options of a domain controller (`options` of its `nTDSDSA` object)
+    ///
+    /// Source: https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-adts/8ebf2419-1169-4413-88e2-12a5ad499cf5
+    #[derive(PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+    pub struct NtdsDsaOptions : u32 {
+
+        /// The domain controller is a global catalog server.
+        const NTDSDSA_OPT_IS_GC = 0x0000_0001;
+
+        /// The domain controller does not accept inbound replication.
+        const NTDSDSA_OPT_DISABLE_INBOUND_REPL = 0x0000_0002;
+
+        /// The domain controller does not perform outbound replication.
+        const NTDSDSA_OPT_DISABLE_OUTBOUND_REPL = 0x0000_0004;
+
+        /// The KCC does not translate `nTDSConnection` objects into
+        /// replication links.
+        const NTDSDSA_OPT_DISABLE_NTDSCONN_XLATE = 0x0000_0008;
+
+        /// The domain controller does not register its SPNs.
+        const NTDSDSA_OPT_DISABLE_SPN_REGISTRATION = 0x0000_0010;
+
+        /// The KCC generates the intra-site replication topology of this
+        /// domain controller, even if the site has disabled the KCC.
+        const NTDSDSA_OPT_GENERATE_OWN_TOPO = 0x0000_0020;
+
+        /// The domain controller does not accept RPC replication requests.
+        const NTDSDSA_OPT_BLOCK_RPC = 0x0000_0040;
+    }
+}
+
+impl From<i32> for NtdsDsaOptions {
+    fn from(value: i32) -> Self {
+        Self::from_bits_truncate(u32::from_ne_bytes(value.to_ne_bytes()))
+    }
+}
@@ -0,0 +1,31 @@
+use bitflags::bitflags;
+use serde::{Deserialize, Serialize};
+
+bitflags! {
+
+    /// the password policy flags of a domain (`pwdProperties`)
+    ///
+    /// Source: https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-samr/5fe3c4c4-e71b-440d-b2fd-8448bfaf6e04
+    #[derive(PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+    pub struct PasswordProperties : u32 {
+
+        /// Passwords must meet the complexity requirements.
+        const DOMAIN_PASSWORD_COMPLEX = 0x0000_0001;
+
+        /// The password cannot be changed without logging on.
+        const DOMAIN_PASSWORD_NO_ANON_CHANGE = 0x0000_0002;
+
+        /// Passwords cannot be changed using a protocol which transfers the
+        /// password in clear text.
+        const DOMAIN_PASSWORD_NO_CLEAR_CHANGE = 0x0000_0004;
+
+        /// The built-in administrator account can be locked out.
+        const DOMAIN_LOCKOUT_ADMINS = 0x0000_0008;
+
+        /// Passwords are stored using reversible encryption.
+        const DOMAIN_PASSWORD_STORE_CLEARTEXT = 0x0000_0010;
+
+        /// Passwords of machine accounts cannot be changed.
+        const DOMAIN_REFUSE_PASSWORD_CHANGE = 0x0000_0020;
+    }
+}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
@@ -9,6 +10,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::cache::Value;
 use crate::ntds;
+use crate::pseudonymize_name;
 use crate::value::FromValue;
 
 use super::Guid;
@@ -21,6 +23,20 @@ pub struct Rdn {
     conflicting_objects: Vec<Guid>,       //TODO: should be UUIDs
 }
 
+impl Rdn {
+    /// same as [`Display`], but with a pseudonymized name, if pseudonymization is enabled
+    pub fn to_pseudonymized_string(&self) -> String {
+        match pseudonymize_name(&self.name) {
+            Cow::Borrowed(_) => self.to_string(),
+            Cow::Owned(name) => Self {
+                name,
+                ..self.clone()
+            }
+            .to_string(),
+        }
+    }
+}
+
 impl Display for Rdn {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match &self.deleted_from_container {
@@ -113,10 +129,11 @@ impl Serialize for Rdn {
     where
         S: Serializer,
     {
+        let name = pseudonymize_name(&self.name);
         if let Some(guid) = &self.deleted_from_container {
-            serializer.serialize_str(&format!("{} -- DEL:{guid}", self.name))
+            serializer.serialize_str(&format!("{name} -- DEL:{guid}"))
         } else {
-            serializer.serialize_str(&self.name)
+            serializer.serialize_str(&name)
         }
     }
 }
@@ -0,0 +1,193 @@
+use byteorder::{ByteOrder, LittleEndian};
+use getset::Getters;
+use serde::Serialize;
+use term_table::row::Row;
+use term_table::table_cell::{Alignment, TableCell};
+
+use crate::cache::MetaDataCache;
+use crate::ntds::Error;
+
+use super::{Guid, WindowsFileTime};
+
+const META_DATA_VECTOR_VERSION: u32 = 1;
+const META_DATA_VECTOR_HEADER_SIZE: usize = 16;
+const META_DATA_ENTRY_SIZE: usize = 48;
+
+/// the decoded value of `replPropertyMetaData` (`PROPERTY_META_DATA_VECTOR`),
+/// which contains the replication metadata of every attribute of an object
+#[derive(Serialize, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct ReplicationMetadata(Vec<AttributeMetadata>);
+
+/// the replication metadata of a single attribute
+#[derive(Getters, Serialize, PartialEq, Eq)]
+#[getset(get = "pub")]
+pub struct AttributeMetadata {
+    /// the `lDAPDisplayName` of the attribute, if it could be resolved
+    attribute: Option<String>,
+    attribute_id: u32,
+
+    /// the number of originating writes to this attribute
+    version: u32,
+    originating_change_time: WindowsFileTime,
+
+    /// the invocation ID of the DC where the last originating write took place
+    originating_invocation_id: Guid,
+
+    /// the name of this DC, if it could be resolved
+    originating_dsa: Option<String>,
+    originating_usn: i64,
+    local_usn: i64,
+}
+
+impl TryFrom<&[u8]> for ReplicationMetadata {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        if value.len() < META_DATA_VECTOR_HEADER_SIZE {
+            return Err(Error::InvalidReplicationMetadata("value is too short"));
+        }
+        if LittleEndian::read_u32(&value[0..4]) != META_DATA_VECTOR_VERSION {
+            return Err(Error::InvalidReplicationMetadata("unsupported version"));
+        }
+        let count = LittleEndian::read_u32(&value[8..12]) as usize;
+        let entries = value
+            .get(
+                META_DATA_VECTOR_HEADER_SIZE
+                    ..META_DATA_VECTOR_HEADER_SIZE + count * META_DATA_ENTRY_SIZE,
+            )
+            .ok_or(Error::InvalidReplicationMetadata("value is too short"))?;
+
+        Ok(Self(
+            entries
+                .chunks_exact(META_DATA_ENTRY_SIZE)
+                .map(|entry| {
+                    AttributeMetadata {
+                        attribute: None,
+                        attribute_id: LittleEndian::read_u32(&entry[0..4]),
+                        version: LittleEndian::read_u32(&entry[4..8]),
+                        originating_change_time: WindowsFileTime::from_dstime(
+                            LittleEndian::read_u64(&entry[8..16]),
+                        ),
+                        originating_invocation_id: Guid::from_bytes_le(
+                            entry[16..32].try_into().unwrap(),
+                        ),
+                        originating_dsa: None,
+                        originating_usn: LittleEndian::read_i64(&entry[32..40]),
+                        local_usn: LittleEndian::read_i64(&entry[40..48]),
+                    }
+                })
+                .collect(),
+        ))
+    }
+}
+
+impl ReplicationMetadata {
+    pub fn entries(&self) -> &[AttributeMetadata] {
+        &self.0
+    }
+
+    /// resolves the attribute ids to their `lDAPDisplayName`s, using the schema
+    pub fn with_attribute_names(mut self, metadata: &MetaDataCache) -> Self {
+        for entry in self.0.iter_mut() {
+            let attribute_id = i32::from_ne_bytes(entry.attribute_id.to_ne_bytes());
+            entry.attribute = metadata
+                .attribute_name(attribute_id)
+                .map(|name| name.to_string());
+        }
+        self
+    }
+
+    /// resolves the invocation ids to the names of the domain controllers
+    pub fn with_dsa_names(mut self, resolve: impl Fn(&Guid) -> Option<String>) -> Self {
+        for entry in self.0.iter_mut() {
+            entry.originating_dsa = resolve(&entry.originating_invocation_id);
+        }
+        self
+    }
+}
+
+impl AttributeMetadata {
+    /// returns the name of the originating DC, or its invocation id if the
+    /// name is unknown
+    pub fn originating_dsa_name(&self) -> String {
+        self.originating_dsa
+            .clone()
+            .unwrap_or_else(|| self.originating_invocation_id.to_string())
+    }
+}
+
+impl From<&ReplicationMetadata> for term_table::Table {
+    fn from(value: &ReplicationMetadata) -> Self {
+        let mut table = term_table::Table::new();
+        table.add_row(Row::new(
+            [
+                "Attribute",
+                "Version",
+                "Originating change",
+                "Originating DSA",
+                "Originating USN",
+                "Local USN",
+            ]
+            .map(|caption| {
+                TableCell::builder(caption)
+                    .alignment(Alignment::Center)
+                    .build()
+            }),
+        ));
+
+        for entry in value.entries() {
+            table.add_row(Row::new(vec![
+                TableCell::new(
+                    entry
+                        .attribute()
+                        .clone()
+                        .unwrap_or_else(|| entry.attribute_id().to_string()),
+                ),
+                TableCell::new(entry.version()),
+                TableCell::new(entry.originating_change_time()),
+                TableCell::new(entry.originating_dsa_name()),
+                TableCell::new(entry.originating_usn()),
+                TableCell::new(entry.local_usn()),
+            ]));
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReplicationMetadata;
+
+    #[test]
+    fn test_replication_metadata() {
+        let value = hex::decode(concat!(
+            "01000000",
+            "00000000",
+            "01000000",
+            "00000000",
+            // whenCreated
+            "02000200",
+            "03000000",
+            "0080c5cd03000000",
+            "33221100554477668899aabbccddeeff",
+            "1020000000000000",
+            "2030000000000000",
+        ))
+        .unwrap();
+        let metadata = ReplicationMetadata::try_from(&value[..]).unwrap();
+        assert_eq!(metadata.entries().len(), 1);
+
+        let entry = &metadata.entries()[0];
+        assert_eq!(*entry.attribute_id(), 0x20002);
+        assert_eq!(*entry.version(), 3);
+        assert_eq!(
+            entry.originating_invocation_id().to_string(),
+            "00112233-4455-6677-8899-aabbccddeeff"
+        );
+        assert_eq!(*entry.originating_usn(), 0x2010);
+        assert_eq!(*entry.local_usn(), 0x3020);
+
+        assert!(ReplicationMetadata::try_from(&value[..60]).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use byteorder::{ByteOrder, LittleEndian};
+use getset::Getters;
+
+use crate::ntds::Error;
+
+use super::{Guid, WindowsFileTime};
+
+const SIGNATURE_VECTOR_VERSION: u32 = 1;
+const SIGNATURE_VECTOR_HEADER_SIZE: usize = 8;
+
+/// `uuidDsaSignature` (16 bytes), `timeRetired` (8 bytes) and
+/// `usnRetired` (8 bytes)
+const SIGNATURE_SIZE: usize = 32;
+
+/// the decoded value of `retiredReplDSASignatures`
+/// (`REPL_DSA_SIGNATURE_VECTOR`), which contains the invocation ids a
+/// domain controller has used before, e.g. before it has been restored
+/// from a backup
+#[derive(PartialEq, Eq)]
+pub struct RetiredDsaSignatures(Vec<RetiredDsaSignature>);
+
+#[derive(Getters, PartialEq, Eq)]
+#[getset(get = "pub")]
+pub struct RetiredDsaSignature {
+    invocation_id: Guid,
+    retired: WindowsFileTime,
+    usn_retired: i64,
+}
+
+impl TryFrom<&[u8]> for RetiredDsaSignatures {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        if value.len() < SIGNATURE_VECTOR_HEADER_SIZE {
+            return Err(Error::InvalidReplicationMetadata(
+                "retired DSA signatures are too short",
+            ));
+        }
+        if LittleEndian::read_u32(&value[0..4]) != SIGNATURE_VECTOR_VERSION {
+            return Err(Error::InvalidReplicationMetadata(
+                "unsupported version of retired DSA signatures",
+            ));
+        }
+        let count = LittleEndian::read_u32(&value[4..8]) as usize;
+        let signatures = value
+            .get(
+                SIGNATURE_VECTOR_HEADER_SIZE..SIGNATURE_VECTOR_HEADER_SIZE + count * SIGNATURE_SIZE,
+            )
+            .ok_or(Error::InvalidReplicationMetadata(
+                "retired DSA signatures are too short",
+            ))?;
+
+        Ok(Self(
+            signatures
+                .chunks_exact(SIGNATURE_SIZE)
+                .map(|signature| RetiredDsaSignature {
+                    invocation_id: Guid::from_bytes_le(signature[0..16].try_into().unwrap()),
+                    retired: WindowsFileTime::from_dstime(LittleEndian::read_u64(
+                        &signature[16..24],
+                    )),
+                    usn_retired: LittleEndian::read_i64(&signature[24..32]),
+                })
+                .collect(),
+        ))
+    }
+}
+
+impl RetiredDsaSignatures {
+    pub fn signatures(&self) -> &[RetiredDsaSignature] {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RetiredDsaSignatures;
+
+    #[test]
+    fn test_retired_dsa_signatures() {
+        let value = hex::decode(concat!(
+            "01000000",
+            "01000000",
+            "33221100554477668899aabbccddeeff",
+            "409e6b1c03000000",
+            "1020000000000000",
+        ))
+        .unwrap();
+        let signatures = RetiredDsaSignatures::try_from(&value[..]).unwrap();
+        assert_eq!(signatures.signatures().len(), 1);
+
+        let signature = &signatures.signatures()[0];
+        assert_eq!(
+            signature.invocation_id().to_string(),
+            "00112233-4455-6677-8899-aabbccddeeff"
+        );
+        assert_eq!(signature.retired().to_string(), "2024-06-01T12:00:00+0000");
+        assert_eq!(*signature.usn_retired(), 0x2010);
+
+        assert!(RetiredDsaSignatures::try_from(&value[..30]).is_err());
+    }
+}
@@ -1,14 +1,17 @@
-use std::{fmt::Display, io::Cursor};
+use std::{fmt::Display, io::Cursor, str::FromStr};
 
 use anyhow::{ensure, Result};
 use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
+use serde::de::Visitor;
 use serde::{Deserialize, Serialize};
 
+use crate::pseudonymize_text;
+
 mod sid_visitor;
 
 ///
 /// https://devblogs.microsoft.com/oldnewthing/20040315-00/?p=40253
-#[derive(PartialEq, Eq, Clone)]
+#[derive(PartialEq, Eq, Clone, Hash)]
 pub struct Sid {
     revision: u8,
     authority: u64,
@@ -26,6 +29,43 @@ impl Sid {
             numbers,
         }
     }
+
+    /// returns the string representation of this SID, where the domain part
+    /// of domain SIDs is pseudonymized, so that well-known RIDs can still be
+    /// recognized
+    pub fn to_pseudonymized_string(&self) -> String {
+        pseudonymize_text(&self.to_string()).to_string()
+    }
+
+    /// returns `true` if this SID belongs to an account of a domain
+    /// (`S-1-5-21-...`)
+    pub fn is_domain_sid(&self) -> bool {
+        self.authority == 5 && self.numbers.len() > 1 && self.numbers[0] == 21
+    }
+
+    /// returns the binary representation of this SID, as it is used by the
+    /// Windows API (in contrast to the `ntds.dit`, where the RID is stored
+    /// in big endian byte order)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.revision, self.numbers.len() as u8];
+        bytes.extend_from_slice(&self.authority.to_be_bytes()[2..]);
+        for number in &self.numbers {
+            bytes.extend_from_slice(&number.to_le_bytes());
+        }
+        bytes
+    }
+}
+
+impl From<&sddl::Sid> for Sid {
+    fn from(sid: &sddl::Sid) -> Self {
+        let mut authority = [0u8; 8];
+        authority[2..].copy_from_slice(sid.identifier_authority().value());
+        Self {
+            revision: *sid.revision(),
+            authority: u64::from_be_bytes(authority),
+            numbers: sid.sub_authority().clone(),
+        }
+    }
 }
 
 impl TryFrom<&Vec<u8>> for Sid {
@@ -54,6 +94,14 @@ impl TryFrom<&Vec<u8>> for Sid {
     }
 }
 
+impl FromStr for Sid {
+    type Err = serde::de::value::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        sid_visitor::SIDVisitor::default().visit_str(s)
+    }
+}
+
 impl Display for Sid {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let numbers = self
@@ -74,7 +122,7 @@ impl Serialize for Sid {
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&format!("{self}"))
+        serializer.serialize_str(&self.to_pseudonymized_string())
     }
 }
 
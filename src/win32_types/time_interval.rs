@@ -0,0 +1,100 @@
+use std::fmt::Display;
+
+use chrono::Duration;
+use serde::Serialize;
+
+use super::FILETIME_TICKS_PER_SECOND;
+
+/// a time interval such as `maxPwdAge` or `lockoutDuration`, which is stored
+/// as negative number of 100-nanosecond intervals. The smallest possible
+/// value stands for an infinite interval.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub struct TimeInterval(i64);
+
+impl TimeInterval {
+    /// returns the length of this interval, or `None` if it is infinite
+    pub fn duration(&self) -> Option<Duration> {
+        if self.is_infinite() {
+            None
+        } else {
+            let ticks = self.0.unsigned_abs();
+            Some(
+                Duration::seconds((ticks / FILETIME_TICKS_PER_SECOND) as i64)
+                    + Duration::nanoseconds(((ticks % FILETIME_TICKS_PER_SECOND) * 100) as i64),
+            )
+        }
+    }
+
+    pub fn is_infinite(&self) -> bool {
+        self.0 == i64::MIN
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl From<i64> for TimeInterval {
+    fn from(value: i64) -> Self {
+        Self(value)
+    }
+}
+
+impl Display for TimeInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let duration = match self.duration() {
+            Some(duration) => duration,
+            None => return write!(f, "never"),
+        };
+        let seconds = duration.num_seconds();
+        let parts: Vec<_> = [
+            (seconds / 86400, "day"),
+            (seconds / 3600 % 24, "hour"),
+            (seconds / 60 % 60, "minute"),
+            (seconds % 60, "second"),
+        ]
+        .into_iter()
+        .filter(|(value, _)| *value != 0)
+        .map(|(value, unit)| {
+            if value == 1 {
+                format!("{value} {unit}")
+            } else {
+                format!("{value} {unit}s")
+            }
+        })
+        .collect();
+        if parts.is_empty() {
+            write!(f, "0 seconds")
+        } else {
+            write!(f, "{}", parts.join(" "))
+        }
+    }
+}
+
+impl Serialize for TimeInterval {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TimeInterval;
+
+    #[test]
+    fn test_display() {
+        // the default maximum password age of 42 days
+        assert_eq!(TimeInterval::from(-36_288_000_000_000).to_string(), "42 days");
+        assert_eq!(TimeInterval::from(-18_000_000_000).to_string(), "30 minutes");
+        assert_eq!(
+            TimeInterval::from(-(86400 + 7200 + 1) * 10_000_000).to_string(),
+            "1 day 2 hours 1 second"
+        );
+        assert_eq!(TimeInterval::from(0).to_string(), "0 seconds");
+        assert_eq!(TimeInterval::from(i64::MIN).to_string(), "never");
+        assert!(TimeInterval::from(i64::MIN).duration().is_none());
+    }
+}
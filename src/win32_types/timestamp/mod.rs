@@ -11,6 +11,10 @@ pub use truncated_windows_file_time::TruncatedWindowsFileTime;
 pub use unix_timestamp::*;
 pub use windows_file_time::WindowsFileTime;
 
+/// number of 100-nanosecond intervals, which are the unit of a `FILETIME`,
+/// per second
+pub const FILETIME_TICKS_PER_SECOND: u64 = 10_000_000;
+
 lazy_static! {
     pub static ref TIMESTAMP_FORMAT: String = {
         if let Ok(format) = std::env::var("DFIR_DATE") {
@@ -95,6 +99,14 @@ macro_rules! impl_timestamp {
             }
         }
 
+        impl std::fmt::Display for $type {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                self.0
+                    .format(&$crate::win32_types::timestamp::TIMESTAMP_FORMAT)
+                    .fmt(f)
+            }
+        }
+
         impl $crate::win32_types::UnixTimestamp for $type {
             #[allow(dead_code)]
             fn timestamp(&self) -> i64 {
@@ -3,7 +3,7 @@ use libesedb::systemtime_from_filetime;
 
 use crate::impl_timestamp;
 
-#[derive(Eq, PartialEq)]
+#[derive(Eq, PartialEq, Clone)]
 pub struct TruncatedWindowsFileTime(DateTime<Utc>);
 
 impl_timestamp!(TruncatedWindowsFileTime);
@@ -3,11 +3,19 @@ use libesedb::systemtime_from_filetime;
 
 use crate::impl_timestamp;
 
-#[derive(Eq, PartialEq)]
+#[derive(Eq, PartialEq, Clone)]
 pub struct WindowsFileTime(DateTime<Utc>);
 
 impl_timestamp!(WindowsFileTime);
 
+impl WindowsFileTime {
+    /// converts a `DSTIME`, which is the number of seconds since 1601-01-01
+    /// and is used by the replication metadata
+    pub fn from_dstime(dstime: u64) -> Self {
+        Self::from(dstime.saturating_mul(super::FILETIME_TICKS_PER_SECOND))
+    }
+}
+
 impl From<u64> for WindowsFileTime {
     fn from(value: u64) -> Self {
         Self(systemtime_from_filetime(value).into())
@@ -22,4 +30,9 @@ impl From<WindowsFileTime> for DateTime<Utc> {
     fn from(value: WindowsFileTime) -> Self {
         value.0
     }
-}
\ No newline at end of file
+}
+impl From<&WindowsFileTime> for DateTime<Utc> {
+    fn from(value: &WindowsFileTime) -> Self {
+        value.0
+    }
+}
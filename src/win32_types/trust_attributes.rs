@@ -0,0 +1,74 @@
+use bitflags::bitflags;
+use serde::{Deserialize, Serialize};
+
+bitflags! {
+
+    /// Source: https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-adts/e9a2d23c-c31e-4a6f-88a0-6646fdb51a3c
+    #[derive(PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+    pub struct TrustAttributes : u32 {
+
+        /// The trust is not transitive.
+        const TRUST_ATTRIBUTE_NON_TRANSITIVE = 0x0000_0001;
+
+        /// Only Windows 2000 and newer clients may use the trust.
+        const TRUST_ATTRIBUTE_UPLEVEL_ONLY = 0x0000_0002;
+
+        /// The trusted domain is quarantined, which means that SID filtering
+        /// is enabled: only SIDs of the trusted domain are accepted.
+        const TRUST_ATTRIBUTE_QUARANTINED_DOMAIN = 0x0000_0004;
+
+        /// The trust is a cross-forest trust.
+        const TRUST_ATTRIBUTE_FOREST_TRANSITIVE = 0x0000_0008;
+
+        /// The trust is to a domain or forest which is not part of the
+        /// enterprise (selective authentication).
+        const TRUST_ATTRIBUTE_CROSS_ORGANIZATION = 0x0000_0010;
+
+        /// The trusted domain is within the same forest.
+        const TRUST_ATTRIBUTE_WITHIN_FOREST = 0x0000_0020;
+
+        /// A cross-forest trust is treated as external trust for the purpose
+        /// of SID filtering, which allows SID history of the trusted forest.
+        const TRUST_ATTRIBUTE_TREAT_AS_EXTERNAL = 0x0000_0040;
+
+        /// The trust uses RC4 keys, which applies only to MIT realms.
+        const TRUST_ATTRIBUTE_USES_RC4_ENCRYPTION = 0x0000_0080;
+
+        /// The trust uses AES keys.
+        const TRUST_ATTRIBUTE_USES_AES_KEYS = 0x0000_0100;
+
+        /// Kerberos TGTs are not delegated across the trust.
+        const TRUST_ATTRIBUTE_CROSS_ORGANIZATION_NO_TGT_DELEGATION = 0x0000_0200;
+
+        /// The trust is to a privileged identity management (PAM) forest.
+        const TRUST_ATTRIBUTE_PIM_TRUST = 0x0000_0400;
+
+        /// Kerberos TGTs are delegated across the trust.
+        const TRUST_ATTRIBUTE_CROSS_ORGANIZATION_ENABLE_TGT_DELEGATION = 0x0000_0800;
+
+        /// Authentication target validation is disabled.
+        const TRUST_ATTRIBUTE_DISABLE_AUTH_TARGET_VALIDATION = 0x0000_1000;
+    }
+}
+
+impl TrustAttributes {
+    /// describes which SIDs are filtered when a principal of the trusted
+    /// domain authenticates in this domain: `none` (within the forest),
+    /// `quarantined` (only SIDs of the trusted domain), `forest`, `relaxed`
+    /// (SID history of the trusted forest is allowed) or `disabled`
+    pub fn sid_filtering(&self) -> &'static str {
+        if self.contains(Self::TRUST_ATTRIBUTE_WITHIN_FOREST) {
+            "none"
+        } else if self.contains(Self::TRUST_ATTRIBUTE_QUARANTINED_DOMAIN) {
+            "quarantined"
+        } else if self.contains(Self::TRUST_ATTRIBUTE_FOREST_TRANSITIVE) {
+            if self.contains(Self::TRUST_ATTRIBUTE_TREAT_AS_EXTERNAL) {
+                "relaxed"
+            } else {
+                "forest"
+            }
+        } else {
+            "disabled"
+        }
+    }
+}
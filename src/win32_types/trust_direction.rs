@@ -0,0 +1,17 @@
+use num_derive::FromPrimitive;
+use serde::{Deserialize, Serialize};
+use strum::EnumString;
+
+/// Source: https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-adts/5026a939-44ba-47b2-99cf-386a9e674b04
+#[derive(EnumString, FromPrimitive, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+#[allow(non_camel_case_types)]
+pub enum TrustDirection {
+    TRUST_DIRECTION_DISABLED = 0,
+
+    /// the other domain trusts this domain
+    TRUST_DIRECTION_INBOUND = 1,
+
+    /// this domain trusts the other domain
+    TRUST_DIRECTION_OUTBOUND = 2,
+    TRUST_DIRECTION_BIDIRECTIONAL = 3,
+}
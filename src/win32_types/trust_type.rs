@@ -0,0 +1,21 @@
+use num_derive::FromPrimitive;
+use serde::{Deserialize, Serialize};
+use strum::EnumString;
+
+/// Source: https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-adts/36565693-b5e4-4f37-b0a8-c1b12138e18e
+#[derive(EnumString, FromPrimitive, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+#[allow(non_camel_case_types)]
+pub enum TrustType {
+    /// a Windows domain which is not running Active Directory
+    TRUST_TYPE_DOWNLEVEL = 1,
+
+    /// a Windows domain running Active Directory
+    TRUST_TYPE_UPLEVEL = 2,
+
+    /// a non-Windows Kerberos realm
+    TRUST_TYPE_MIT = 3,
+    TRUST_TYPE_DCE = 4,
+
+    /// a Microsoft Entra ID (Azure AD) tenant
+    TRUST_TYPE_AAD = 5,
+}